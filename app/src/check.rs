@@ -0,0 +1,137 @@
+//! `app check <file> [--interval 1m]`: headless integrity lint for candle files.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use core::{
+    Candle, Interval, LoadOptions, SessionCalendar, SourceFormat, detect_format, find_gaps,
+    load_csv, load_parquet,
+};
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+/// Exit code when the file loads but has integrity issues.
+pub const ISSUES_EXIT_CODE: i32 = 1;
+
+/// Runs the check over `args` (everything after `check`), printing one line per issue.
+/// Returns the process exit code.
+pub fn run(args: &[String]) -> Result<i32> {
+    let path = super::positional_source(args).context("usage: check <file> [--interval 1m]")?;
+    let interval = flag_value(args, "--interval")
+        .map(|value| value.parse::<Interval>())
+        .transpose()
+        .context("invalid --interval")?;
+
+    let candles = load(Path::new(&path)).with_context(|| format!("failed to load {path}"))?;
+    let issues = find_issues(&candles, interval);
+    for issue in &issues {
+        println!("{issue}");
+    }
+    if issues.is_empty() {
+        println!("ok: {} candles, no issues", candles.len());
+        Ok(0)
+    } else {
+        println!("{} issue(s) in {} candles", issues.len(), candles.len());
+        Ok(ISSUES_EXIT_CODE)
+    }
+}
+
+fn flag_value(args: &[String], name: &str) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == name {
+            return iter.next().cloned();
+        }
+    }
+    None
+}
+
+fn load(path: &Path) -> Result<Vec<Candle>> {
+    // Keep infinities so they are reported as issues rather than failing the load.
+    let options = LoadOptions {
+        non_finite_is_null: false,
+        ..Default::default()
+    };
+    let candles = match detect_format(&path.to_string_lossy()) {
+        SourceFormat::Parquet => load_parquet(path, options)?,
+        SourceFormat::Csv => load_csv(path, options)?,
+        SourceFormat::Url => anyhow::bail!("check only reads local files"),
+    };
+    Ok(candles)
+}
+
+fn format_ts(ts: OffsetDateTime) -> String {
+    ts.format(&Rfc3339).unwrap_or_else(|_| ts.to_string())
+}
+
+/// Unsorted rows, duplicate timestamps, OHLC violations and (with `interval`) gaps, in that
+/// order. Rows are 1-based data rows, excluding the header.
+pub fn find_issues(candles: &[Candle], interval: Option<Interval>) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    for (idx, pair) in candles.windows(2).enumerate() {
+        if pair[1].timestamp < pair[0].timestamp {
+            issues.push(format!(
+                "unsorted: row {} ({}) is earlier than row {}",
+                idx + 2,
+                format_ts(pair[1].timestamp),
+                idx + 1
+            ));
+        }
+    }
+
+    let mut counts: BTreeMap<OffsetDateTime, usize> = BTreeMap::new();
+    for candle in candles {
+        *counts.entry(candle.timestamp).or_default() += 1;
+    }
+    for (ts, count) in counts.iter().filter(|(_, count)| **count > 1) {
+        issues.push(format!(
+            "duplicate: {} appears {count} times",
+            format_ts(*ts)
+        ));
+    }
+
+    for (idx, c) in candles.iter().enumerate() {
+        let values = [c.open, c.high, c.low, c.close, c.volume];
+        if values.iter().any(|v| !v.is_finite()) {
+            issues.push(format!("ohlc: row {} has a non-finite value", idx + 1));
+        } else if c.open < c.low || c.open > c.high || c.close < c.low || c.close > c.high {
+            issues.push(format!(
+                "ohlc: row {} open {} / close {} outside [low {}, high {}]",
+                idx + 1,
+                c.open,
+                c.close,
+                c.low,
+                c.high
+            ));
+        } else if c.volume < 0.0 {
+            issues.push(format!(
+                "ohlc: row {} has negative volume {}",
+                idx + 1,
+                c.volume
+            ));
+        }
+    }
+
+    if let Some(interval) = interval {
+        let mut sorted = candles.to_vec();
+        sorted.sort_by_key(|c| c.timestamp);
+        sorted.dedup_by_key(|c| c.timestamp);
+        let step = interval.as_duration();
+        for (start, end) in find_gaps(&sorted, interval, &SessionCalendar::always_open()) {
+            let missing = missing_buckets(end - start, step);
+            issues.push(format!(
+                "gap: {} .. {} ({missing} missing)",
+                format_ts(start),
+                format_ts(end)
+            ));
+        }
+    }
+
+    issues
+}
+
+fn missing_buckets(span: Duration, step: Duration) -> i128 {
+    span.whole_nanoseconds() / step.whole_nanoseconds().max(1)
+}
@@ -1,6 +1,62 @@
-use anyhow::Result;
+mod check;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use core::{Interval, SourceFormat, detect_format};
+
+/// Path loaded on startup when no `--symbol` is given (kiosk-style launches).
+const DEFAULT_SOURCE_ENV: &str = "GPUI_KBAR_DEFAULT_SOURCE";
+
+/// Candles shown on first load unless `--visible N` overrides it (`0` shows everything).
+const DEFAULT_VISIBLE: usize = 300;
+
+fn default_source(initial_symbol: Option<&str>, env_value: Option<String>) -> Option<PathBuf> {
+    if initial_symbol.is_some() {
+        return None;
+    }
+    env_value
+        .filter(|value| !value.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+/// First bare argument, e.g. `app data/AAPL.csv`; every `--flag` takes one value.
+fn positional_source(args: &[String]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg.starts_with("--") {
+            iter.next();
+        } else {
+            return Some(arg.clone());
+        }
+    }
+    None
+}
+
+/// Downloads URL sources to a temp file so the runtime only ever sees local paths.
+fn resolve_source(source: PathBuf) -> Result<PathBuf> {
+    let raw = source.to_string_lossy().into_owned();
+    if detect_format(&raw) != SourceFormat::Url {
+        return Ok(source);
+    }
+    #[cfg(feature = "reqwest")]
+    {
+        Ok(core::fetch_to_temp(&raw)?)
+    }
+    #[cfg(not(feature = "reqwest"))]
+    {
+        anyhow::bail!("loading {raw} requires building with `--features reqwest`")
+    }
+}
 
 fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "check") {
+        let code = check::run(&args[1..])?;
+        std::process::exit(code);
+    }
+
     fn parse_arg_u64(name: &str) -> Option<u64> {
         let mut args = std::env::args().skip(1);
         while let Some(arg) = args.next() {
@@ -31,6 +87,28 @@ fn main() -> Result<()> {
     }
 
     let initial_symbol = parse_arg_string("--symbol");
+    let initial_source = positional_source(&args)
+        .map(PathBuf::from)
+        .or_else(|| {
+            default_source(
+                initial_symbol.as_deref(),
+                std::env::var(DEFAULT_SOURCE_ENV).ok(),
+            )
+        })
+        .map(resolve_source)
+        .transpose()?;
+
+    let initial_visible = Some(
+        parse_arg_u64("--visible")
+            .map(|v| v as usize)
+            .unwrap_or(DEFAULT_VISIBLE),
+    )
+    .filter(|n| *n > 0);
+
+    let initial_interval = parse_arg_string("--interval")
+        .map(|value| value.parse::<Interval>())
+        .transpose()
+        .context("invalid --interval")?;
 
     let step_secs = parse_arg_u64("--step-secs").map(|v| v as i64);
     let preset = parse_arg_string("--preset");
@@ -42,9 +120,52 @@ fn main() -> Result<()> {
         step_secs: step_secs.unwrap_or(60),
     });
 
+    let live_refresh_interval = parse_arg_u64("--refresh-ms").map(Duration::from_millis);
+
     ui::launch_runtime_with_options(ui::RuntimeOptions {
         initial_symbol,
+        initial_source,
+        initial_visible,
+        initial_interval,
         perf,
+        live_refresh_interval,
     });
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_default_source_used_without_symbol_arg() {
+        let source = default_source(None, Some("data/candles/AAPL.csv".to_string()));
+        assert_eq!(source, Some(PathBuf::from("data/candles/AAPL.csv")));
+    }
+
+    #[test]
+    fn positional_source_skips_flag_values() {
+        let args = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            positional_source(&args(&["--visible", "100", "https://example.com/AAPL.csv"])),
+            Some("https://example.com/AAPL.csv".to_string())
+        );
+        assert_eq!(positional_source(&args(&["--symbol", "MSFT"])), None);
+    }
+
+    #[test]
+    fn local_sources_resolve_without_fetching() {
+        let path = PathBuf::from("data/candles/AAPL.csv");
+        assert_eq!(resolve_source(path.clone()).unwrap(), path);
+    }
+
+    #[test]
+    fn symbol_arg_or_empty_env_falls_through_to_picker() {
+        assert_eq!(
+            default_source(Some("MSFT"), Some("data/candles/AAPL.csv".to_string())),
+            None
+        );
+        assert_eq!(default_source(None, Some("  ".to_string())), None);
+        assert_eq!(default_source(None, None), None);
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn write_fixture(rows: &[&str]) -> PathBuf {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("gpui-kbar-check-{nonce}.csv"));
+    let mut body = String::from("timestamp,open,high,low,close,volume\n");
+    for row in rows {
+        body.push_str(row);
+        body.push('\n');
+    }
+    std::fs::write(&path, body).unwrap();
+    path
+}
+
+fn run_check(path: &PathBuf) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_app"))
+        .args(["check", path.to_str().unwrap(), "--interval", "1m"])
+        .output()
+        .expect("run app check");
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    )
+}
+
+#[test]
+fn check_reports_gap_and_duplicate_with_nonzero_exit() {
+    let path = write_fixture(&[
+        "2024-01-01T00:00:00Z,1.0,2.0,0.5,1.5,100",
+        "2024-01-01T00:01:00Z,1.5,2.5,1.0,2.0,150",
+        "2024-01-01T00:01:00Z,1.5,2.5,1.0,2.0,150",
+        "2024-01-01T00:04:00Z,2.0,3.0,1.5,2.5,200",
+    ]);
+    let (code, stdout) = run_check(&path);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(code, Some(1), "{stdout}");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "duplicate: 2024-01-01T00:01:00Z appears 2 times",
+            "gap: 2024-01-01T00:02:00Z .. 2024-01-01T00:04:00Z (2 missing)",
+            "2 issue(s) in 4 candles",
+        ]
+    );
+}
+
+#[test]
+fn check_passes_clean_file() {
+    let path = write_fixture(&[
+        "2024-01-01T00:00:00Z,1.0,2.0,0.5,1.5,100",
+        "2024-01-01T00:01:00Z,1.5,2.5,1.0,2.0,150",
+    ]);
+    let (code, stdout) = run_check(&path);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(code, Some(0), "{stdout}");
+    assert_eq!(stdout.trim(), "ok: 2 candles, no issues");
+}
@@ -0,0 +1,145 @@
+use crate::Candle;
+
+/// Segment tree over candle lows/highs answering range `bounds` queries in O(log n).
+#[derive(Debug, Clone, Default)]
+pub struct BoundsIndex {
+    len: usize,
+    lows: Vec<f64>,
+    highs: Vec<f64>,
+}
+
+impl BoundsIndex {
+    /// Builds the index in O(n).
+    pub fn new(candles: &[Candle]) -> Self {
+        let len = candles.len();
+        let mut lows = vec![f64::MAX; 2 * len];
+        let mut highs = vec![f64::MIN; 2 * len];
+        // Non-finite values keep the identity, so they never widen a range.
+        for (i, candle) in candles.iter().enumerate() {
+            if candle.low.is_finite() {
+                lows[len + i] = candle.low;
+            }
+            if candle.high.is_finite() {
+                highs[len + i] = candle.high;
+            }
+        }
+        for i in (1..len).rev() {
+            lows[i] = lows[2 * i].min(lows[2 * i + 1]);
+            highs[i] = highs[2 * i].max(highs[2 * i + 1]);
+        }
+        Self { len, lows, highs }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Min low / max high over `start..end`, matching `bounds(&candles[start..end])`.
+    pub fn bounds(&self, start: usize, end: usize) -> Option<(f64, f64)> {
+        let end = end.min(self.len);
+        if start >= end {
+            return None;
+        }
+
+        let mut min = f64::MAX;
+        let mut max = f64::MIN;
+        let mut lo = start + self.len;
+        let mut hi = end + self.len;
+        while lo < hi {
+            if lo & 1 == 1 {
+                min = min.min(self.lows[lo]);
+                max = max.max(self.highs[lo]);
+                lo += 1;
+            }
+            if hi & 1 == 1 {
+                hi -= 1;
+                min = min.min(self.lows[hi]);
+                max = max.max(self.highs[hi]);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        (min <= max).then_some((min, max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds;
+    use time::{Duration, macros::datetime};
+
+    fn candles(n: usize) -> Vec<Candle> {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) as f64 / (1u64 << 31) as f64
+        };
+        (0..n)
+            .map(|i| {
+                let mid = 100.0 + next() * 50.0;
+                let spread = next() * 5.0;
+                Candle {
+                    timestamp: datetime!(2024-01-01 00:00:00 UTC) + Duration::minutes(i as i64),
+                    open: mid,
+                    high: mid + spread,
+                    low: mid - spread,
+                    close: mid,
+                    volume: 1.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_naive_bounds_over_random_ranges() {
+        let data = candles(1_037);
+        let index = BoundsIndex::new(&data);
+
+        let mut seed = 7_u64;
+        for _ in 0..500 {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            let a = (seed >> 33) as usize % (data.len() + 1);
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            let b = (seed >> 33) as usize % (data.len() + 1);
+            let (start, end) = (a.min(b), a.max(b));
+            assert_eq!(index.bounds(start, end), bounds(&data[start..end]));
+        }
+    }
+
+    #[test]
+    fn empty_and_out_of_range_queries_return_none() {
+        let index = BoundsIndex::new(&[]);
+        assert!(index.is_empty());
+        assert_eq!(index.bounds(0, 10), None);
+
+        let data = candles(3);
+        let index = BoundsIndex::new(&data);
+        assert_eq!(index.bounds(2, 2), None);
+        assert_eq!(index.bounds(0, 99), bounds(&data));
+    }
+
+    #[test]
+    fn non_finite_values_are_ignored_like_naive_bounds() {
+        let mut data = candles(8);
+        data[2].high = f64::INFINITY;
+        data[5].low = f64::NAN;
+        data[6].low = f64::NEG_INFINITY;
+        data[6].high = f64::NAN;
+        let index = BoundsIndex::new(&data);
+        for start in 0..data.len() {
+            for end in start..=data.len() {
+                assert_eq!(index.bounds(start, end), bounds(&data[start..end]));
+            }
+        }
+        let (min, max) = index.bounds(0, data.len()).unwrap();
+        assert!(min.is_finite() && max.is_finite());
+        assert_eq!(index.bounds(6, 7), None);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+use time::{Date, Duration, OffsetDateTime, Time, Weekday};
+
+use crate::Candle;
+
+/// Trading days used to tell expected closures from missing data.
+///
+/// The default calendar is open 24/7 (crypto); use [`SessionCalendar::weekdays`] for equities.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionCalendar {
+    weekdays_only: bool,
+    holidays: HashSet<Date>,
+}
+
+impl SessionCalendar {
+    /// Open every day.
+    pub fn always_open() -> Self {
+        Self::default()
+    }
+
+    /// Closed on Saturdays and Sundays.
+    pub fn weekdays() -> Self {
+        Self {
+            weekdays_only: true,
+            holidays: HashSet::new(),
+        }
+    }
+
+    /// Adds full-day closures on top of the weekly schedule.
+    pub fn with_holidays(mut self, holidays: impl IntoIterator<Item = Date>) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    /// Whether the session is open on `date`.
+    pub fn is_open_on(&self, date: Date) -> bool {
+        if self.weekdays_only && matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday) {
+            return false;
+        }
+        !self.holidays.contains(&date)
+    }
+
+    /// Whether the session is open at `ts`, judged by its calendar date in its own offset.
+    pub fn is_open(&self, ts: OffsetDateTime) -> bool {
+        self.is_open_on(ts.date())
+    }
+}
+
+/// Gaps longer than this (and than the usual candle spacing) separate sessions.
+const SESSION_BREAK: Duration = Duration::HOUR;
+
+/// Typical trading session as `(open, close)` time-of-day, in the candles' own offset.
+///
+/// `open` is the median first candle time per session and `close` the median last candle
+/// time plus the median candle spacing, so `open..close` covers every in-session candle.
+/// Sessions are split at overnight gaps, so a `close` earlier than `open` means the session
+/// wraps past midnight (e.g. 22:00..06:00). Series without such gaps (24/7 markets) are
+/// split by calendar day. Medians keep pre-market starts and half days from skewing the
+/// result. Input is expected to be sorted ascending; `None` when empty.
+pub fn detect_session(candles: &[Candle]) -> Option<(Time, Time)> {
+    let gap = |a: &Candle, b: &Candle| b.timestamp - a.timestamp;
+    let mut spacings: Vec<Duration> = candles.windows(2).map(|w| gap(&w[0], &w[1])).collect();
+    let spacing = median(&mut spacings).unwrap_or(Duration::ZERO);
+    let break_gap = spacing.max(SESSION_BREAK);
+    let sessions: Vec<&[Candle]> = if candles.windows(2).any(|w| gap(&w[0], &w[1]) > break_gap) {
+        candles.chunk_by(|a, b| gap(a, b) <= break_gap).collect()
+    } else {
+        candles
+            .chunk_by(|a, b| a.timestamp.date() == b.timestamp.date())
+            .collect()
+    };
+
+    let mut firsts: Vec<Time> = sessions.iter().map(|s| s[0].timestamp.time()).collect();
+    let mut lasts: Vec<Time> = sessions
+        .iter()
+        .map(|s| s[s.len() - 1].timestamp.time())
+        .collect();
+    let open = median(&mut firsts)?;
+    let last = median(&mut lasts)?;
+    Some((open, last + spacing))
+}
+
+fn median<T: Ord + Copy>(values: &mut [T]) -> Option<T> {
+    values.sort_unstable();
+    values.get(values.len().checked_sub(1)? / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime, time};
+
+    #[test]
+    fn weekday_calendar_skips_weekends_and_holidays() {
+        let calendar = SessionCalendar::weekdays().with_holidays([date!(2024 - 01 - 15)]);
+        assert!(calendar.is_open_on(date!(2024 - 01 - 12)));
+        assert!(!calendar.is_open_on(date!(2024 - 01 - 13)));
+        assert!(!calendar.is_open_on(date!(2024 - 01 - 14)));
+        assert!(!calendar.is_open_on(date!(2024 - 01 - 15)));
+        assert!(calendar.is_open_on(date!(2024 - 01 - 16)));
+        assert!(SessionCalendar::always_open().is_open_on(date!(2024 - 01 - 13)));
+    }
+
+    #[test]
+    fn detects_regular_equity_session_despite_outlier_days() {
+        let day = |start: OffsetDateTime, minutes: i64| {
+            (0..minutes)
+                .map(move |m| Candle::new(start + Duration::minutes(m), 1.0, 1.0, 1.0, 1.0, 1.0))
+        };
+        let regular = 390; // 09:30..16:00 in 1m bars
+        let candles: Vec<Candle> = day(datetime!(2024-01-08 09:30 UTC), regular)
+            .chain(day(datetime!(2024-01-09 04:00 UTC), 720)) // pre-market through 16:00
+            .chain(day(datetime!(2024-01-10 09:30 UTC), regular))
+            .chain(day(datetime!(2024-01-11 09:30 UTC), 210)) // half day
+            .chain(day(datetime!(2024-01-12 09:30 UTC), regular))
+            .collect();
+
+        assert_eq!(detect_session(&candles), Some((time!(09:30), time!(16:00))));
+        assert_eq!(detect_session(&[]), None);
+    }
+
+    #[test]
+    fn detects_session_that_wraps_past_midnight() {
+        let night = |start: OffsetDateTime| {
+            (0..480)
+                .map(move |m| Candle::new(start + Duration::minutes(m), 1.0, 1.0, 1.0, 1.0, 1.0))
+        };
+        // 22:00..06:00 in 1m bars, each night crossing a calendar date.
+        let candles: Vec<Candle> = night(datetime!(2024-01-08 22:00 UTC))
+            .chain(night(datetime!(2024-01-09 22:00 UTC)))
+            .chain(night(datetime!(2024-01-10 22:00 UTC)))
+            .collect();
+
+        assert_eq!(detect_session(&candles), Some((time!(22:00), time!(06:00))));
+    }
+}
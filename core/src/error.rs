@@ -17,6 +17,31 @@ pub enum LoadError {
         row: usize,
         value: String,
     },
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "reqwest")]
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
     #[error(transparent)]
     Polars(#[from] PolarsError),
 }
+
+/// A unix-millisecond timestamp outside the range `OffsetDateTime` can represent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("invalid ts_ms={0}")]
+pub struct InvalidTimestamp(pub i64);
+
+/// Why an interval label such as `"5m"` could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum IntervalParseError {
+    #[error("interval is empty")]
+    Empty,
+    #[error("unknown interval unit in '{0}' (expected s, m, h or d)")]
+    UnknownUnit(String),
+    #[error("invalid interval amount in '{0}'")]
+    InvalidAmount(String),
+    #[error("interval amount must be greater than zero")]
+    ZeroAmount,
+    #[error("interval amount in '{0}' is too large")]
+    Overflow(String),
+}
@@ -0,0 +1,71 @@
+use std::fmt::Write;
+
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::Candle;
+
+/// Header written by [`write_csv`]; matches the default `ColumnMapping`.
+pub const CSV_HEADER: &str = "timestamp,open,high,low,close,volume";
+
+/// Header written by [`write_indicator_csv`].
+pub const INDICATOR_CSV_HEADER: &str = "timestamp,value";
+
+/// Writes `candles` as CSV (header included) with RFC3339 timestamps, so the output loads
+/// back through `load_csv`/`load_csv_reader` with default options.
+pub fn write_csv<W: Write>(out: &mut W, candles: &[Candle]) -> std::fmt::Result {
+    writeln!(out, "{CSV_HEADER}")?;
+    for c in candles {
+        let ts = c.timestamp.format(&Rfc3339).map_err(|_| std::fmt::Error)?;
+        writeln!(
+            out,
+            "{ts},{},{},{},{},{}",
+            c.open, c.high, c.low, c.close, c.volume
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes indicator points (e.g. from `DuckDbStore::load_indicator_values`) as
+/// `timestamp,value` CSV with RFC3339 timestamps, header included.
+pub fn write_indicator_csv<W: Write>(
+    out: &mut W,
+    values: &[(OffsetDateTime, f64)],
+) -> std::fmt::Result {
+    writeln!(out, "{INDICATOR_CSV_HEADER}")?;
+    for (ts, value) in values {
+        let ts = ts.format(&Rfc3339).map_err(|_| std::fmt::Error)?;
+        writeln!(out, "{ts},{value}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn indicator_values_export_to_csv_and_reparse() {
+        let points = vec![
+            (datetime!(2024-01-01 00:00:00 UTC), 10.0),
+            (datetime!(2024-01-01 00:01:00 UTC), 11.125),
+            (datetime!(2024-01-01 00:02:00 UTC), -0.3),
+        ];
+        let mut csv = String::new();
+        write_indicator_csv(&mut csv, &points).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(INDICATOR_CSV_HEADER));
+        let reparsed: Vec<(OffsetDateTime, f64)> = lines
+            .map(|line| {
+                let (ts, value) = line.split_once(',').expect("two columns");
+                (
+                    OffsetDateTime::parse(ts, &Rfc3339).expect("rfc3339"),
+                    value.parse().expect("float"),
+                )
+            })
+            .collect();
+        assert_eq!(reparsed, points);
+    }
+}
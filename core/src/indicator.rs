@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+
+use crate::Candle;
+
+/// Running simple moving average over the last `window` values; O(1) per [`push`](Self::push).
+#[derive(Debug, Clone)]
+pub struct SmaState {
+    window: usize,
+    values: VecDeque<f64>,
+    sum: f64,
+}
+
+impl SmaState {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            values: VecDeque::with_capacity(window),
+            sum: 0.0,
+        }
+    }
+
+    /// Adds `value` and returns the average, or `None` until a full window is available.
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        if self.window == 0 {
+            return None;
+        }
+        if self.values.len() == self.window {
+            self.sum -= self.values.pop_front().unwrap_or(0.0);
+        }
+        self.values.push_back(value);
+        self.sum += value;
+        self.value()
+    }
+
+    /// Current average, `None` until a full window has been pushed.
+    pub fn value(&self) -> Option<f64> {
+        (self.window > 0 && self.values.len() == self.window).then(|| self.sum / self.window as f64)
+    }
+}
+
+/// Running exponential moving average with `alpha = 2 / (period + 1)`, seeded with the SMA of
+/// the first `period` values.
+#[derive(Debug, Clone)]
+pub struct EmaState {
+    period: usize,
+    seed: SmaState,
+    value: Option<f64>,
+}
+
+impl EmaState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            seed: SmaState::new(period),
+            value: None,
+        }
+    }
+
+    /// Adds `value` and returns the average, or `None` until `period` values have been pushed.
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        self.value = match self.value {
+            Some(prev) => {
+                let alpha = 2.0 / (self.period as f64 + 1.0);
+                Some(prev + alpha * (value - prev))
+            }
+            None => self.seed.push(value),
+        };
+        self.value
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+/// Simple moving average aligned with `values`; entries are `None` until a full window exists.
+pub fn sma(values: &[f64], window: usize) -> Vec<Option<f64>> {
+    let mut state = SmaState::new(window);
+    values.iter().map(|&v| state.push(v)).collect()
+}
+
+/// Exponential moving average aligned with `values`; entries are `None` for the first
+/// `period - 1` values.
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut state = EmaState::new(period);
+    values.iter().map(|&v| state.push(v)).collect()
+}
+
+/// Direction of a fast/slow moving-average crossover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossDir {
+    /// Fast crosses above slow (golden cross).
+    Up,
+    /// Fast crosses below slow (death cross).
+    Down,
+}
+
+/// Indices where `fast` moves to the other side of `slow`, compared with the last index where
+/// both were defined and apart. Touching without crossing is not a crossover.
+pub fn crossovers(fast: &[Option<f64>], slow: &[Option<f64>]) -> Vec<(usize, CrossDir)> {
+    let mut out = Vec::new();
+    let mut above: Option<bool> = None;
+    for (idx, (fast, slow)) in fast.iter().zip(slow).enumerate() {
+        let (Some(fast), Some(slow)) = (fast, slow) else {
+            continue;
+        };
+        if fast == slow {
+            continue;
+        }
+        let now_above = fast > slow;
+        if above.is_some_and(|was_above| was_above != now_above) {
+            out.push((
+                idx,
+                if now_above {
+                    CrossDir::Up
+                } else {
+                    CrossDir::Down
+                },
+            ));
+        }
+        above = Some(now_above);
+    }
+    out
+}
+
+/// Percentage decline of each close from the running peak so far: `0.0` at a new high,
+/// `-50.0` at half the peak. Non-positive peaks yield `0.0`.
+pub fn drawdown(closes: &[f64]) -> Vec<f64> {
+    let mut peak = f64::NEG_INFINITY;
+    closes
+        .iter()
+        .map(|&close| {
+            peak = peak.max(close);
+            if peak > 0.0 {
+                (close - peak) / peak * 100.0
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Deepest [`drawdown`] in percent (e.g. `-50.0`); `0.0` for empty or never-falling series.
+pub fn max_drawdown(closes: &[f64]) -> f64 {
+    drawdown(closes).into_iter().fold(0.0, f64::min)
+}
+
+/// Classic floor-trader pivot levels derived from one session's high, low and close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PivotLevels {
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub s1: f64,
+    pub s2: f64,
+}
+
+/// Pivot levels for the session after `prev`, where `prev` spans the whole previous session
+/// (its high, low and close). `P = (H + L + C) / 3`, `R1 = 2P - L`, `S1 = 2P - H`,
+/// `R2 = P + (H - L)`, `S2 = P - (H - L)`.
+pub fn pivot(prev: &Candle) -> PivotLevels {
+    let range = prev.high - prev.low;
+    let pivot = (prev.high + prev.low + prev.close) / 3.0;
+    PivotLevels {
+        pivot,
+        r1: 2.0 * pivot - prev.low,
+        r2: pivot + range,
+        s1: 2.0 * pivot - prev.high,
+        s2: pivot - range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLOSES: [f64; 8] = [10.0, 11.0, 12.5, 12.0, 13.0, 12.5, 14.0, 15.5];
+
+    fn assert_close(a: Option<f64>, b: Option<f64>) {
+        match (a, b) {
+            (Some(a), Some(b)) => assert!((a - b).abs() < 1e-9, "{a} != {b}"),
+            _ => assert_eq!(a, b),
+        }
+    }
+
+    #[test]
+    fn incremental_sma_matches_batch_and_naive_windows() {
+        let batch = sma(&CLOSES, 3);
+        let mut state = SmaState::new(3);
+        for (i, &close) in CLOSES.iter().enumerate() {
+            let naive = (i >= 2).then(|| CLOSES[i - 2..=i].iter().sum::<f64>() / 3.0);
+            let pushed = state.push(close);
+            assert_close(pushed, batch[i]);
+            assert_close(pushed, naive);
+        }
+        assert_close(state.value(), batch.last().copied().flatten());
+    }
+
+    #[test]
+    fn incremental_ema_matches_batch_and_is_seeded_with_sma() {
+        let batch = ema(&CLOSES, 3);
+        assert_eq!(batch[..2], [None, None]);
+        assert_close(batch[2], Some((10.0 + 11.0 + 12.5) / 3.0));
+        assert_close(
+            batch[3],
+            Some(batch[2].unwrap() + 0.5 * (12.0 - batch[2].unwrap())),
+        );
+
+        let mut state = EmaState::new(3);
+        for (i, &close) in CLOSES.iter().enumerate() {
+            assert_close(state.push(close), batch[i]);
+        }
+    }
+
+    #[test]
+    fn crossovers_report_golden_and_death_crosses() {
+        let closes = [10.0, 10.0, 10.0, 13.0, 16.0, 16.0, 12.0, 8.0, 8.0, 8.0];
+        let fast = sma(&closes, 2);
+        let slow = sma(&closes, 4);
+        // fast: -, 10, 10, 11.5, 14.5, 16, 14, 10, 8, 8
+        // slow: -, -, -, 10.75, 12.25, 13.75, 14.25, 13, 11, 9
+        assert_eq!(
+            crossovers(&fast, &slow),
+            vec![(6, CrossDir::Down)],
+            "already above when slow becomes defined, so index 3 is not a cross"
+        );
+
+        let fast = [
+            Some(1.0),
+            Some(2.0),
+            Some(2.0),
+            Some(3.0),
+            Some(1.0),
+            None,
+            Some(4.0),
+        ];
+        let slow = [
+            Some(2.0),
+            Some(2.0),
+            Some(2.0),
+            Some(2.0),
+            Some(2.0),
+            Some(2.0),
+            Some(2.0),
+        ];
+        assert_eq!(
+            crossovers(&fast, &slow),
+            vec![(3, CrossDir::Up), (4, CrossDir::Down), (6, CrossDir::Up)]
+        );
+    }
+
+    #[test]
+    fn drawdown_tracks_decline_from_running_peak() {
+        let rising = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(drawdown(&rising), vec![0.0; 4]);
+        assert_eq!(max_drawdown(&rising), 0.0);
+
+        let halves_then_recovers = [100.0, 80.0, 50.0, 75.0, 120.0, 90.0];
+        let dd = drawdown(&halves_then_recovers);
+        assert_eq!(dd, vec![0.0, -20.0, -50.0, -25.0, 0.0, -25.0]);
+        assert_eq!(max_drawdown(&halves_then_recovers), -50.0);
+        assert_eq!(max_drawdown(&[]), 0.0);
+    }
+
+    #[test]
+    fn pivot_matches_standard_formulas() {
+        let prev = Candle::new(
+            time::macros::datetime!(2024-01-02 00:00:00 UTC),
+            105.0,
+            110.0,
+            100.0,
+            108.0,
+            1_000.0,
+        );
+        let levels = pivot(&prev);
+        let p = (110.0 + 100.0 + 108.0) / 3.0;
+        assert!((levels.pivot - 106.0).abs() < 1e-9);
+        assert!((levels.r1 - (2.0 * p - 100.0)).abs() < 1e-9);
+        assert!((levels.s1 - (2.0 * p - 110.0)).abs() < 1e-9);
+        assert!((levels.r2 - (p + 10.0)).abs() < 1e-9);
+        assert!((levels.s2 - (p - 10.0)).abs() < 1e-9);
+        assert!(levels.s2 < levels.s1 && levels.s1 < levels.pivot);
+        assert!(levels.pivot < levels.r1 && levels.r1 < levels.r2);
+    }
+
+    #[test]
+    fn zero_window_yields_no_values() {
+        assert_eq!(sma(&CLOSES, 0), vec![None; CLOSES.len()]);
+        assert_eq!(ema(&CLOSES, 0), vec![None; CLOSES.len()]);
+        assert!(sma(&[], 3).is_empty());
+    }
+}
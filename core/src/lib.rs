@@ -1,26 +1,52 @@
+mod bounds_index;
+mod calendar;
 mod error;
+mod export;
+mod indicator;
 mod load;
 mod resample;
+mod source;
+mod stats;
 mod store;
 mod types;
 
-pub use error::LoadError;
-pub use load::{load_csv, load_parquet};
-pub use resample::{bounds, resample};
-pub use store::{DuckDbStore, StorageMode, StoreError, UniverseRow, UserSession};
-pub use types::{Candle, ColumnMapping, Interval, LoadOptions};
+pub use bounds_index::BoundsIndex;
+pub use calendar::{SessionCalendar, detect_session};
+pub use error::{IntervalParseError, InvalidTimestamp, LoadError};
+pub use export::{CSV_HEADER, INDICATOR_CSV_HEADER, write_csv, write_indicator_csv};
+pub use indicator::{
+    CrossDir, EmaState, PivotLevels, SmaState, crossovers, drawdown, ema, max_drawdown, pivot, sma,
+};
+pub use load::{
+    load_csv, load_csv_ext, load_csv_reader, load_csv_tail, load_parquet, load_parquet_ext,
+    load_parquet_range,
+};
+pub use resample::{
+    STANDARD_INTERVALS, StreamResampler, aggregate, bounds, decimate, find_gaps, resample,
+    resample_to_count, time_to_close,
+};
+#[cfg(feature = "reqwest")]
+pub use source::fetch_to_temp;
+pub use source::{SourceFormat, detect_format, format_for_response};
+pub use stats::{returns, rolling_volatility};
+pub use store::{
+    CandleCoverage, DUCKDB_PRAGMAS_ENV, DataRange, DuckDbStore, RAW_INTERVAL, StorageMode,
+    StoreError, SymbolStats, UniverseRow, UserSession,
+};
+pub use types::{Candle, CandleExt, ColumnMapping, Interval, LoadOptions, LoadSort, NullPolicy};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use polars::datatypes::TimeUnit;
     use polars::prelude::{
-        DataFrame, DataType, Int64Chunked, IntoSeries, NamedFrom, ParquetWriter, Series,
+        DataFrame, DataType, Int64Chunked, IntoSeries, NamedFrom, ParquetReader, ParquetWriter,
+        SerReader, Series,
     };
     use std::fs;
     use std::path::Path;
     use std::time::{SystemTime, UNIX_EPOCH};
-    use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+    use time::{OffsetDateTime, format_description::well_known::Rfc3339, macros::datetime};
 
     fn temp_path(ext: &str) -> std::path::PathBuf {
         let nonce = SystemTime::now()
@@ -57,6 +83,413 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_csv_accepts_fractional_epoch_seconds() {
+        let body = [
+            "timestamp,open,high,low,close,volume",
+            "1704067200.25,1.0,2.0,0.5,1.5,100",
+            "1704067201.5,1.5,2.5,1.0,2.0,150",
+        ]
+        .join("\n");
+        let candles = load_csv_reader(body.as_bytes(), LoadOptions::default()).unwrap();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].timestamp, datetime!(2024-01-01 00:00:00.25 UTC));
+        assert_eq!(candles[0].timestamp.millisecond(), 250);
+        assert_eq!(candles[1].timestamp, datetime!(2024-01-01 00:00:01.5 UTC));
+    }
+
+    #[test]
+    fn load_csv_honors_limit() {
+        let path = temp_path("csv");
+        fs::write(&path, sample_csv()).unwrap();
+
+        let options = LoadOptions {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let candles = load_csv(&path, options).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[1].close, 2.0);
+    }
+
+    #[test]
+    fn load_csv_decompresses_gzipped_files() {
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(sample_csv().as_bytes()).unwrap();
+        let gz = encoder.finish().unwrap();
+        let path = temp_path("csv.gz");
+        fs::write(&path, &gz).unwrap();
+
+        let candles = load_csv(&path, LoadOptions::default());
+        let tail = load_csv_tail(&path, 2, LoadOptions::default());
+        fs::remove_file(&path).ok();
+
+        let plain = load_csv_reader(sample_csv().as_bytes(), LoadOptions::default()).unwrap();
+        let candles = candles.unwrap();
+        assert_eq!(candles.len(), 3);
+        assert_eq!(candles, plain);
+        assert_eq!(tail.unwrap(), plain[1..]);
+        assert_eq!(
+            load_csv_reader(gz.as_slice(), LoadOptions::default()).unwrap(),
+            plain
+        );
+    }
+
+    #[test]
+    fn load_csv_reads_semicolon_delimited_files() {
+        let path = temp_path("csv");
+        fs::write(
+            &path,
+            "timestamp;open;high;low;close;volume\n\
+             2024-01-01T00:00:00Z;1.0;2.0;0.5;1.5;100\n\
+             2024-01-01T00:01:00Z;1.5;2.5;1.0;2.0;150\n",
+        )
+        .unwrap();
+        let semicolon = || LoadOptions {
+            delimiter: b';',
+            ..Default::default()
+        };
+
+        let candles = load_csv(&path, semicolon());
+        let misread = load_csv(&path, LoadOptions::default());
+        fs::remove_file(&path).ok();
+
+        let candles = candles.unwrap();
+        assert_eq!(
+            candles,
+            vec![
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC),
+                    1.0,
+                    2.0,
+                    0.5,
+                    1.5,
+                    100.0
+                ),
+                Candle::new(
+                    datetime!(2024-01-01 00:01:00 UTC),
+                    1.5,
+                    2.5,
+                    1.0,
+                    2.0,
+                    150.0
+                ),
+            ]
+        );
+        assert!(matches!(misread, Err(LoadError::MissingColumn(name)) if name == "timestamp"));
+
+        let no_volume = "timestamp;open;high;low;close\n2024-01-01T00:00:00Z;1;2;0.5;1.5\n";
+        match load_csv_reader(no_volume.as_bytes(), semicolon()) {
+            Err(LoadError::MissingColumn(name)) => assert_eq!(name, "volume"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_csv_tail_returns_last_rows() {
+        let path = temp_path("csv");
+        let mut body = String::from("timestamp,open,high,low,close,volume\n");
+        for i in 0..100 {
+            let ts = OffsetDateTime::from_unix_timestamp(1_704_067_200 + i * 60)
+                .unwrap()
+                .format(&Rfc3339)
+                .unwrap();
+            body.push_str(&format!("{ts},{i},{},{i},{i},1\n", i + 1));
+        }
+        // A trailing blank line must not shift the tail window.
+        body.push('\n');
+        fs::write(&path, body).unwrap();
+
+        let candles = load_csv_tail(&path, 10, LoadOptions::default()).unwrap();
+        let all = load_csv_tail(&path, 500, LoadOptions::default()).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(candles.len(), 10);
+        assert_eq!(candles[0].open, 90.0);
+        assert_eq!(candles[9].open, 99.0);
+        assert!(candles.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+        assert_eq!(all.len(), 100);
+    }
+
+    #[test]
+    fn load_sort_breaks_timestamp_ties_deterministically() {
+        // `close` records the file row; rows 0, 2 and 3 share a timestamp.
+        let body = [
+            "timestamp,open,high,low,close,volume,seq",
+            "2024-01-01T00:01:00Z,1.0,2.0,0.5,0.0,1,7",
+            "2024-01-01T00:00:00Z,1.0,2.0,0.5,1.0,1,1",
+            "2024-01-01T00:01:00Z,1.0,2.0,0.5,2.0,1,3",
+            "2024-01-01T00:01:00Z,1.0,2.0,0.5,3.0,1,5",
+        ]
+        .join("\n");
+        let closes = |sort: LoadSort| -> Vec<f64> {
+            let options = LoadOptions {
+                sort,
+                ..Default::default()
+            };
+            load_csv_reader(body.as_bytes(), options)
+                .unwrap()
+                .iter()
+                .map(|c| c.close)
+                .collect()
+        };
+
+        assert_eq!(closes(LoadSort::FileOrder), vec![0.0, 1.0, 2.0, 3.0]);
+        // Stable: tied rows keep file order.
+        assert_eq!(closes(LoadSort::Timestamp), vec![1.0, 0.0, 2.0, 3.0]);
+        assert_eq!(
+            closes(LoadSort::TimestampThen("seq".into())),
+            vec![1.0, 2.0, 3.0, 0.0]
+        );
+        assert!(matches!(
+            load_csv_reader(
+                body.as_bytes(),
+                LoadOptions {
+                    sort: LoadSort::TimestampThen("missing".into()),
+                    ..Default::default()
+                }
+            ),
+            Err(LoadError::MissingColumn(column)) if column == "missing"
+        ));
+    }
+
+    #[test]
+    fn forward_fill_replaces_nan_close_and_zeroes_missing_volume() {
+        let path = temp_path("csv");
+        fs::write(
+            &path,
+            "timestamp,open,high,low,close,volume\n\
+             2024-01-01T00:00:00Z,1,2,0.5,1.5,10\n\
+             2024-01-01T00:01:00Z,1.5,2.5,1,NaN,\n\
+             2024-01-01T00:02:00Z,2,3,1.5,2.5,null\n",
+        )
+        .unwrap();
+
+        let options = LoadOptions {
+            null_policy: NullPolicy::ForwardFill,
+            ..Default::default()
+        };
+        let filled = load_csv(&path, options).unwrap();
+        let skipped = load_csv(
+            &path,
+            LoadOptions {
+                null_policy: NullPolicy::Skip,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let strict = load_csv(&path, LoadOptions::default());
+        fs::remove_file(&path).ok();
+
+        assert_eq!(filled.len(), 3);
+        assert_eq!(filled[1].open, 1.5);
+        assert_eq!(filled[1].close, 1.5);
+        assert_eq!(filled[1].volume, 0.0);
+        assert_eq!(filled[2].volume, 0.0);
+        assert_eq!(skipped.len(), 2);
+        assert!(matches!(strict, Err(LoadError::InvalidNumber { .. })));
+    }
+
+    #[test]
+    fn non_finite_prices_follow_the_null_policy() {
+        let csv = "timestamp,open,high,low,close,volume\n\
+                   2024-01-01T00:00:00Z,1,2,0.5,1.5,10\n\
+                   2024-01-01T00:01:00Z,1.5,inf,1,NaN,inf\n\
+                   2024-01-01T00:02:00Z,2,3,1.5,2.5,5\n";
+        let filled = load_csv_reader(
+            csv.as_bytes(),
+            LoadOptions {
+                null_policy: NullPolicy::ForwardFill,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            filled[1],
+            Candle::new(datetime!(2024-01-01 00:01:00 UTC), 1.5, 2.0, 1.0, 1.5, 0.0)
+        );
+        assert!(filled.iter().all(|c| {
+            [c.open, c.high, c.low, c.close, c.volume]
+                .iter()
+                .all(|v| v.is_finite())
+        }));
+
+        let skipped = load_csv_reader(
+            csv.as_bytes(),
+            LoadOptions {
+                null_policy: NullPolicy::Skip,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(skipped.len(), 2);
+        let strict = load_csv_reader(csv.as_bytes(), LoadOptions::default());
+        assert!(matches!(strict, Err(LoadError::InvalidNumber { .. })));
+
+        let raw = load_csv_reader(
+            csv.as_bytes(),
+            LoadOptions {
+                null_values: Vec::new(),
+                non_finite_is_null: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(raw[1].high.is_infinite() && raw[1].close.is_nan());
+        assert_eq!(bounds(&raw), Some((0.5, 3.0)));
+    }
+
+    #[test]
+    fn ext_loader_reads_optional_bid_ask_columns() {
+        let path = temp_path("csv");
+        fs::write(
+            &path,
+            "timestamp,open,high,low,close,volume,Bid,ask\n\
+             2024-01-01T00:00:00Z,1,2,0.5,1.5,10,1.49,1.51\n\
+             2024-01-01T00:01:00Z,1.5,2.5,1,2,15,,2.02\n",
+        )
+        .unwrap();
+        let plain_path = temp_path("csv");
+        fs::write(
+            &plain_path,
+            "timestamp,open,high,low,close,volume\n2024-01-01T00:00:00Z,1,2,0.5,1.5,10\n",
+        )
+        .unwrap();
+
+        let rows = load_csv_ext(&path, LoadOptions::default()).unwrap();
+        let candles = load_csv(&path, LoadOptions::default()).unwrap();
+        let plain = load_csv_ext(&plain_path, LoadOptions::default()).unwrap();
+        fs::remove_file(&path).ok();
+        fs::remove_file(&plain_path).ok();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].bid, rows[0].ask), (Some(1.49), Some(1.51)));
+        assert_eq!((rows[1].bid, rows[1].ask), (None, Some(2.02)));
+        let ext_candles: Vec<Candle> = rows.into_iter().map(|row| row.candle).collect();
+        assert_eq!(ext_candles, candles);
+        assert_eq!((plain[0].bid, plain[0].ask), (None, None));
+    }
+
+    #[test]
+    fn messy_headers_resolve_case_and_whitespace_insensitively() {
+        let csv = " Volume,Close , TIMESTAMP,open,HIGH,Low \n\
+                   10,1.5,2024-01-01T00:00:00Z,1,2,0.5\n\
+                   20,2.5,2024-01-01T00:01:00Z,1.5,3,1\n";
+        let candles = load_csv_reader(csv.as_bytes(), LoadOptions::default()).unwrap();
+        assert_eq!(
+            candles[1],
+            Candle::new(datetime!(2024-01-01 00:01:00 UTC), 1.5, 3.0, 1.0, 2.5, 20.0)
+        );
+
+        let strict = LoadOptions {
+            exact_column_names: true,
+            ..Default::default()
+        };
+        match load_csv_reader(csv.as_bytes(), strict) {
+            Err(LoadError::MissingColumn(name)) => assert_eq!(name, "timestamp"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        // Mapping overrides resolve the same way.
+        let csv = "Time ,o,h,l,c,qty\n2024-01-01T00:00:00Z,1,2,0.5,1.5,7\n";
+        let options = LoadOptions {
+            columns: ColumnMapping {
+                timestamp: "time".into(),
+                open: "O".into(),
+                high: "H".into(),
+                low: "L".into(),
+                close: "C".into(),
+                volume: "QTY".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let candles = load_csv_reader(csv.as_bytes(), options).unwrap();
+        assert_eq!((candles[0].close, candles[0].volume), (1.5, 7.0));
+    }
+
+    #[test]
+    fn column_mapping_reads_vendor_headers_and_reports_field_names() {
+        let vendor = || ColumnMapping {
+            timestamp: "t".into(),
+            open: "o".into(),
+            high: "h".into(),
+            low: "l".into(),
+            close: "c".into(),
+            volume: "v".into(),
+            ..Default::default()
+        };
+        let path = temp_path("parquet");
+        let t = Int64Chunked::new("t".into(), &[1_704_067_200_000i64, 1_704_067_260_000])
+            .into_series()
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+            .unwrap();
+        let mut df = DataFrame::new(vec![
+            t.into(),
+            Series::new("o".into(), &[1.0_f64, 1.5]).into(),
+            Series::new("h".into(), &[2.0_f64, 2.5]).into(),
+            Series::new("l".into(), &[0.5_f64, 1.0]).into(),
+            Series::new("c".into(), &[1.5_f64, 2.0]).into(),
+            Series::new("v".into(), &[100.0_f64, 150.0]).into(),
+        ])
+        .unwrap();
+        ParquetWriter::new(fs::File::create(&path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+        let options = LoadOptions {
+            columns: vendor(),
+            ..Default::default()
+        };
+        let from_parquet = load_parquet(&path, options.clone());
+        fs::remove_file(&path).ok();
+
+        let csv = "t,o,h,l,c,v\n2024-01-01T00:00:00Z,1,2,0.5,1.5,100\n\
+                   2024-01-01T00:01:00Z,1.5,2.5,1,2,150\n";
+        let from_csv = load_csv_reader(csv.as_bytes(), options).unwrap();
+        assert_eq!(from_csv.len(), 2);
+        assert_eq!(
+            from_csv[1],
+            Candle::new(
+                datetime!(2024-01-01 00:01:00 UTC),
+                1.5,
+                2.5,
+                1.0,
+                2.0,
+                150.0
+            )
+        );
+        assert_eq!(from_parquet.unwrap(), from_csv);
+
+        // Blank entries read the canonical column.
+        let partial = LoadOptions {
+            columns: ColumnMapping {
+                volume: String::new(),
+                ..vendor()
+            },
+            ..Default::default()
+        };
+        let csv = "t,o,h,l,c,volume\n2024-01-01T00:00:00Z,1,2,0.5,1.5,42\n";
+        let candles = load_csv_reader(csv.as_bytes(), partial).unwrap();
+        assert_eq!(candles[0].volume, 42.0);
+
+        // A mapped column that isn't there is reported by the field it was meant to fill.
+        let csv = "t,o,h,l,close,v\n2024-01-01T00:00:00Z,1,2,0.5,1.5,42\n";
+        let options = LoadOptions {
+            columns: vendor(),
+            ..Default::default()
+        };
+        match load_csv_reader(csv.as_bytes(), options) {
+            Err(LoadError::MissingColumn(name)) => assert_eq!(name, "close"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
     #[test]
     fn errors_on_missing_column() {
         let path = temp_path("csv");
@@ -75,6 +508,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn volume_optional_loads_ohlc_without_volume_as_zero() {
+        let path = temp_path("csv");
+        fs::write(
+            &path,
+            "timestamp,open,high,low,close\n\
+             2024-01-01T00:00:00Z,1.1,1.2,1.0,1.15\n\
+             2024-01-01T00:01:00Z,1.15,1.3,1.1,1.25\n",
+        )
+        .unwrap();
+
+        let options = LoadOptions {
+            volume_optional: true,
+            ..Default::default()
+        };
+        let candles = load_csv(&path, options).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[1].close, 1.25);
+        assert!(candles.iter().all(|c| c.volume == 0.0));
+    }
+
+    #[test]
+    fn header_only_csv_loads_empty() {
+        let path = temp_path("csv");
+        fs::write(&path, "timestamp,open,high,low,close,volume\n").unwrap();
+
+        let candles = load_csv(&path, LoadOptions::default()).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert!(candles.is_empty());
+        assert_eq!(bounds(&candles), None);
+        assert!(resample(&candles, Interval::Minute(1)).is_empty());
+    }
+
+    #[test]
+    fn single_row_csv_loads_and_resamples() {
+        let path = temp_path("csv");
+        fs::write(
+            &path,
+            "timestamp,open,high,low,close,volume\n2024-01-01T00:00:00Z,1.0,1.0,1.0,1.0,5\n",
+        )
+        .unwrap();
+
+        let candles = load_csv(&path, LoadOptions::default()).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(candles.len(), 1);
+        assert_eq!(bounds(&candles), Some((1.0, 1.0)));
+        let resampled = resample(&candles, Interval::Hour(1));
+        assert_eq!(resampled.len(), 1);
+        assert_eq!(resampled[0].volume, 5.0);
+    }
+
     fn write_parquet_fixture(path: &Path) {
         let ts_ms: Series = Int64Chunked::new(
             "timestamp".into(),
@@ -115,6 +603,316 @@ mod tests {
         assert_eq!(candles[2].volume, 200.0);
     }
 
+    #[test]
+    fn load_parquet_casts_integer_volume_and_float32_prices() {
+        let path = temp_path("parquet");
+        let ts_ms: Series = Int64Chunked::new(
+            "timestamp".into(),
+            &[1_704_300_000_000i64, 1_704_300_060_000],
+        )
+        .into_series()
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .unwrap();
+        let mut df = DataFrame::new(vec![
+            ts_ms.into(),
+            Series::new("open".into(), &[1.0_f32, 1.5]).into(),
+            Series::new("high".into(), &[2.0_f32, 2.5]).into(),
+            Series::new("low".into(), &[0.5_f32, 1.25]).into(),
+            Series::new("close".into(), &[1.5_f32, 2.25]).into(),
+            Series::new("volume".into(), &[100_i64, 3_000_000_000]).into(),
+        ])
+        .unwrap();
+        let mut file = fs::File::create(&path).unwrap();
+        ParquetWriter::new(&mut file).finish(&mut df).unwrap();
+
+        let candles = load_parquet(&path, LoadOptions::default()).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(
+            (
+                candles[1].open,
+                candles[1].high,
+                candles[1].low,
+                candles[1].close
+            ),
+            (1.5, 2.5, 1.25, 2.25)
+        );
+        assert_eq!(candles[0].volume, 100.0);
+        assert_eq!(candles[1].volume, 3_000_000_000.0);
+
+        let csv = "timestamp,open,high,low,close,volume\n\
+                   2024-01-01T00:00:00Z,1.5e2,1.6E2,1.4e2,1.55e2,2.5e6\n";
+        let candles = load_csv_reader(csv.as_bytes(), LoadOptions::default()).unwrap();
+        assert_eq!((candles[0].open, candles[0].volume), (150.0, 2_500_000.0));
+    }
+
+    #[test]
+    fn load_parquet_honors_limit() {
+        let path = temp_path("parquet");
+        write_parquet_fixture(&path);
+
+        let options = LoadOptions {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let candles = load_parquet(&path, options).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(candles.len(), 2);
+    }
+
+    #[test]
+    fn load_parquet_range_prunes_row_groups_outside_the_window() {
+        // 400 one-minute candles written as four row groups of 100.
+        let ts_ms: Series = Int64Chunked::new(
+            "timestamp".into(),
+            &(0..400)
+                .map(|i| 1_704_067_200_000i64 + i * 60_000)
+                .collect::<Vec<_>>(),
+        )
+        .into_series()
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .unwrap();
+        let prices: Vec<f64> = (0..400).map(|i| i as f64).collect();
+        let mut df = DataFrame::new(vec![
+            ts_ms.into(),
+            Series::new("open".into(), &prices).into(),
+            Series::new("high".into(), &prices).into(),
+            Series::new("low".into(), &prices).into(),
+            Series::new("close".into(), &prices).into(),
+            Series::new("volume".into(), &prices).into(),
+        ])
+        .unwrap();
+        let path = temp_path("parquet");
+        let mut file = fs::File::create(&path).unwrap();
+        ParquetWriter::new(&mut file)
+            .with_row_group_size(Some(100))
+            .finish(&mut df)
+            .unwrap();
+        let mut reader = ParquetReader::new(fs::File::open(&path).unwrap());
+        assert_eq!(reader.get_metadata().unwrap().row_groups.len(), 4);
+
+        // Rows 150..=170, all inside the second row group.
+        let start = datetime!(2024-01-01 02:30:00 UTC);
+        let end = datetime!(2024-01-01 02:50:00 UTC);
+        let range = DataRange::Between { start, end };
+        let candles = load_parquet_range(&path, LoadOptions::default(), range.clone()).unwrap();
+        let plan = load::parquet_range_scan(&path, &LoadOptions::default(), &range)
+            .unwrap()
+            .explain(true)
+            .unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(candles.len(), 21);
+        assert_eq!(candles[0].timestamp, start);
+        assert_eq!(candles[20].timestamp, end);
+        assert!(candles.iter().all(|c| (150.0..=170.0).contains(&c.close)));
+        // The bound reaches the parquet scan itself, which is what lets row-group
+        // statistics skip the other three groups instead of decoding them.
+        assert!(plan.contains("SELECTION"), "{plan}");
+        assert!(!plan.contains("SELECTION: None"), "{plan}");
+    }
+
+    #[test]
+    fn resample_to_count_picks_finest_interval_within_target() {
+        let candles: Vec<Candle> = (0..100_000)
+            .map(|i| {
+                Candle::new(
+                    OffsetDateTime::from_unix_timestamp(i).unwrap(),
+                    1.0,
+                    2.0,
+                    0.5,
+                    1.5,
+                    1.0,
+                )
+            })
+            .collect();
+
+        let (resampled, interval) = resample_to_count(&candles, 500);
+        assert_eq!(interval, Interval::Minute(5));
+        assert!(resampled.len() <= 500);
+        assert!(resample(&candles, Interval::Minute(1)).len() > 500);
+        assert_eq!(resampled.iter().map(|c| c.volume).sum::<f64>(), 100_000.0);
+
+        let (all, finest) = resample_to_count(&candles[..10], 500);
+        assert_eq!((all.len(), finest), (10, Interval::Second(1)));
+    }
+
+    #[test]
+    fn decimate_keeps_global_extremes_of_long_series() {
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        let candles: Vec<Candle> = (0..10_000)
+            .map(|i| {
+                let close = match i {
+                    4_321 => 250.0,
+                    7_777 => -40.0,
+                    _ => 100.0 + (i as f64 * 0.01).sin() * 10.0,
+                };
+                Candle::new(
+                    start + time::Duration::minutes(i),
+                    close,
+                    close,
+                    close,
+                    close,
+                    1.0,
+                )
+            })
+            .collect();
+
+        let points = decimate(&candles, 100);
+        assert!(
+            points.len() <= 100 && points.len() >= 50,
+            "{}",
+            points.len()
+        );
+        let min = points.iter().copied().fold(f64::MAX, f64::min);
+        let max = points.iter().copied().fold(f64::MIN, f64::max);
+        assert_eq!((min, max), (-40.0, 250.0));
+        let spike = points.iter().position(|&p| p == 250.0).unwrap();
+        let dip = points.iter().position(|&p| p == -40.0).unwrap();
+        assert!(spike < dip, "points stay in series order");
+
+        assert_eq!(
+            decimate(&candles[..3], 100),
+            vec![candles[0].close, candles[1].close, candles[2].close]
+        );
+        assert!(decimate(&candles, 0).is_empty());
+        assert_eq!(decimate(&candles, 1), vec![candles[9_999].close]);
+    }
+
+    #[test]
+    fn stream_resampler_emits_each_completed_minute() {
+        let candles: Vec<Candle> = (0..120)
+            .map(|i| {
+                let price = 100.0 + i as f64;
+                Candle::new(
+                    datetime!(2024-01-01 09:30:00 UTC) + time::Duration::seconds(i),
+                    price,
+                    price + 0.5,
+                    price - 0.5,
+                    price + 0.25,
+                    1.0,
+                )
+            })
+            .collect();
+
+        let mut resampler = StreamResampler::new(Interval::Second(1), Interval::Minute(1));
+        let mut completed = Vec::new();
+        for (i, candle) in candles.iter().enumerate() {
+            let done = resampler.push(candle.clone());
+            // Each minute completes on its 60th second, not when the next one opens.
+            assert_eq!(done.len(), usize::from(i % 60 == 59), "candle {i}");
+            completed.extend(done);
+        }
+
+        assert_eq!(completed, resample(&candles, Interval::Minute(1)));
+        assert_eq!(completed.len(), 2);
+        assert_eq!(completed[1].timestamp, datetime!(2024-01-01 09:31:00 UTC));
+        assert_eq!(completed[1].volume, 60.0);
+        assert!(resampler.pending().is_none());
+        assert!(resampler.finish().is_none());
+    }
+
+    #[test]
+    fn resample_conserves_volume_across_bucket_boundaries() {
+        // Irregular 37s spacing starting before the epoch, so buckets split mid-run on both
+        // sides of zero.
+        let candles: Vec<Candle> = (0..2_000)
+            .map(|i| {
+                Candle::new(
+                    OffsetDateTime::from_unix_timestamp(-18_000 + i * 37).unwrap(),
+                    1.0,
+                    2.0,
+                    0.5,
+                    1.5,
+                    (i % 7 + 1) as f64,
+                )
+            })
+            .collect();
+        let total: f64 = candles.iter().map(|c| c.volume).sum();
+
+        for interval in STANDARD_INTERVALS {
+            let resampled = resample(&candles, interval);
+            assert_eq!(
+                resampled.iter().map(|c| c.volume).sum::<f64>(),
+                total,
+                "{interval}"
+            );
+            let step = interval.as_duration().whole_seconds();
+            for pair in resampled.windows(2) {
+                assert!(pair[0].timestamp < pair[1].timestamp, "{interval}");
+            }
+            for bucket in &resampled {
+                assert_eq!(
+                    bucket.timestamp.unix_timestamp().rem_euclid(step),
+                    0,
+                    "{interval}"
+                );
+            }
+            assert!(resampled[0].timestamp <= candles[0].timestamp, "{interval}");
+        }
+
+        let (resampled, _) = resample_to_count(&candles, 50);
+        assert_eq!(resampled.iter().map(|c| c.volume).sum::<f64>(), total);
+    }
+
+    #[test]
+    fn time_to_close_counts_down_within_the_forming_bar() {
+        let open = datetime!(2024-01-01 09:30:00 UTC);
+        let cases = [
+            (
+                Interval::Second(1),
+                time::Duration::milliseconds(250),
+                time::Duration::milliseconds(750),
+            ),
+            (
+                Interval::Minute(1),
+                time::Duration::seconds(18),
+                time::Duration::seconds(42),
+            ),
+            (
+                Interval::Minute(5),
+                time::Duration::ZERO,
+                time::Duration::minutes(5),
+            ),
+            (
+                Interval::Minute(5),
+                time::Duration::seconds(299),
+                time::Duration::seconds(1),
+            ),
+            (
+                Interval::Hour(1),
+                time::Duration::minutes(45),
+                time::Duration::minutes(15),
+            ),
+            (
+                Interval::Day(1),
+                time::Duration::hours(6),
+                time::Duration::hours(18),
+            ),
+            // Past the close with no newer bar: the next bucket is forming.
+            (
+                Interval::Minute(1),
+                time::Duration::seconds(80),
+                time::Duration::seconds(40),
+            ),
+            // Clock skew puts `now` before the bar.
+            (
+                Interval::Minute(1),
+                time::Duration::seconds(-5),
+                time::Duration::minutes(1),
+            ),
+        ];
+        for (interval, elapsed, expected) in cases {
+            assert_eq!(
+                time_to_close(interval, open, open + elapsed),
+                expected,
+                "{interval} after {elapsed}"
+            );
+        }
+    }
+
     #[test]
     fn bounds_and_resample() {
         let candles = vec![
@@ -161,4 +959,167 @@ mod tests {
         assert_eq!(resampled[1].close, 3.5);
         assert_eq!(resampled[1].volume, 20.0);
     }
+
+    #[test]
+    fn candle_exact_and_approx_equality() {
+        let a = Candle {
+            timestamp: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            open: 0.1 + 0.2,
+            high: 1.0,
+            low: 0.0,
+            close: 0.5,
+            volume: 10.0,
+        };
+        let mut b = a.clone();
+        assert_eq!(a, b);
+
+        b.open = 0.3;
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-9));
+
+        b.close = 0.6;
+        assert!(!a.approx_eq(&b, 1e-9));
+
+        let mut later = a.clone();
+        later.timestamp = OffsetDateTime::from_unix_timestamp(60).unwrap();
+        assert!(!a.approx_eq(&later, f64::MAX));
+    }
+
+    #[test]
+    fn interval_parse_rejects_zero_and_overflow() {
+        assert_eq!("15s".parse(), Ok(Interval::Second(15)));
+        assert_eq!("5m".parse(), Ok(Interval::Minute(5)));
+        assert_eq!(" 4h ".parse(), Ok(Interval::Hour(4)));
+        assert_eq!("1d".parse(), Ok(Interval::Day(1)));
+
+        assert_eq!(
+            "0m".parse::<Interval>(),
+            Err(IntervalParseError::ZeroAmount)
+        );
+        assert_eq!(
+            "99999999999h".parse::<Interval>(),
+            Err(IntervalParseError::Overflow("99999999999h".into()))
+        );
+        assert_eq!("".parse::<Interval>(), Err(IntervalParseError::Empty));
+        assert_eq!(
+            "5w".parse::<Interval>(),
+            Err(IntervalParseError::UnknownUnit("5w".into()))
+        );
+        assert_eq!(
+            "-5m".parse::<Interval>(),
+            Err(IntervalParseError::InvalidAmount("-5m".into()))
+        );
+        assert_eq!(
+            "m".parse::<Interval>(),
+            Err(IntervalParseError::InvalidAmount("m".into()))
+        );
+    }
+
+    #[test]
+    fn interval_display_round_trips_through_parse() {
+        for interval in [
+            Interval::Second(3),
+            Interval::Minute(15),
+            Interval::Hour(4),
+            Interval::Day(1),
+        ] {
+            assert_eq!(interval.to_string().parse(), Ok(interval));
+        }
+        assert_eq!(Interval::Minute(5).to_string(), "5m");
+    }
+
+    #[test]
+    fn candle_from_ts_ms_round_trips_and_rejects_out_of_range() {
+        let candle = Candle::from_ts_ms(1_700_000_000_123, 1.0, 2.0, 0.5, 1.5, 10.0).unwrap();
+        assert_eq!(
+            candle,
+            Candle::new(
+                datetime!(2023-11-14 22:13:20.123 UTC),
+                1.0,
+                2.0,
+                0.5,
+                1.5,
+                10.0
+            )
+        );
+        assert_eq!(candle.ts_ms(), 1_700_000_000_123);
+
+        assert_eq!(
+            Candle::from_ts_ms(i64::MAX, 1.0, 1.0, 1.0, 1.0, 0.0),
+            Err(InvalidTimestamp(i64::MAX))
+        );
+    }
+
+    #[test]
+    fn find_gaps_returns_aligned_missing_spans() {
+        let at = |secs: i64| Candle {
+            timestamp: OffsetDateTime::from_unix_timestamp(secs).unwrap(),
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+        };
+        // Minutes 0, 1, then 4 (with an unaligned second), then 5: minutes 2 and 3 are missing.
+        let candles = vec![at(0), at(60), at(245), at(300)];
+
+        let gaps = find_gaps(
+            &candles,
+            Interval::Minute(1),
+            &SessionCalendar::always_open(),
+        );
+        assert_eq!(
+            gaps,
+            vec![(
+                OffsetDateTime::from_unix_timestamp(120).unwrap(),
+                OffsetDateTime::from_unix_timestamp(240).unwrap(),
+            )]
+        );
+        let calendar = SessionCalendar::always_open();
+        assert!(find_gaps(&candles[..2], Interval::Minute(1), &calendar).is_empty());
+        assert!(find_gaps(&[], Interval::Minute(1), &calendar).is_empty());
+    }
+
+    #[test]
+    fn weekday_calendar_ignores_weekend_gaps() {
+        let at = |ts: OffsetDateTime| Candle {
+            timestamp: ts,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+        };
+        // Friday 23:00 UTC, then Monday 00:00 and 01:00 UTC.
+        let candles = vec![
+            at(datetime!(2024-01-05 23:00 UTC)),
+            at(datetime!(2024-01-08 00:00 UTC)),
+            at(datetime!(2024-01-08 01:00 UTC)),
+        ];
+
+        let weekdays = SessionCalendar::weekdays();
+        assert!(find_gaps(&candles, Interval::Hour(1), &weekdays).is_empty());
+
+        let always = find_gaps(&candles, Interval::Hour(1), &SessionCalendar::always_open());
+        assert_eq!(
+            always,
+            vec![(
+                datetime!(2024-01-06 00:00 UTC),
+                datetime!(2024-01-08 00:00 UTC)
+            )]
+        );
+
+        // A missing Monday session is still reported, stopping at the Tuesday data.
+        let skipped_monday = vec![
+            at(datetime!(2024-01-05 23:00 UTC)),
+            at(datetime!(2024-01-09 00:00 UTC)),
+        ];
+        assert_eq!(
+            find_gaps(&skipped_monday, Interval::Hour(1), &weekdays),
+            vec![(
+                datetime!(2024-01-08 00:00 UTC),
+                datetime!(2024-01-09 00:00 UTC)
+            )]
+        );
+    }
 }
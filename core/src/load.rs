@@ -1,32 +1,199 @@
-use crate::{Candle, ColumnMapping, LoadError, LoadOptions};
+use crate::{
+    Candle, CandleExt, ColumnMapping, DataRange, LoadError, LoadOptions, LoadSort, NullPolicy,
+};
+use flate2::read::MultiGzDecoder;
 use polars::datatypes::TimeUnit;
 use polars::prelude::PlPathRef;
 use polars::prelude::*;
+use std::fs::File;
+use std::io::{BufReader, Read};
 use std::path::Path;
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 
 pub fn load_csv(path: impl AsRef<Path>, options: LoadOptions) -> Result<Vec<Candle>, LoadError> {
-    let pl_path = PlPathRef::from_local_path(path.as_ref()).into_owned();
-    let columns = &options.columns;
+    let lf = csv_scan(path.as_ref(), &options)?;
+    collect_candles(lf, &options)
+}
+
+/// [`load_csv`] that also reads the optional `columns.bid` / `columns.ask` quote columns.
+pub fn load_csv_ext(
+    path: impl AsRef<Path>,
+    options: LoadOptions,
+) -> Result<Vec<CandleExt>, LoadError> {
+    let lf = csv_scan(path.as_ref(), &options)?;
+    collect_candles_ext(lf, &options)
+}
+
+/// Leading bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Scans a CSV file; gzip-compressed files (`.csv.gz`) are recognised by their magic bytes
+/// and decompressed in memory first.
+fn csv_scan(path: &Path, options: &LoadOptions) -> Result<LazyFrame, LoadError> {
+    if is_gzip(path)? {
+        let mut bytes = Vec::new();
+        MultiGzDecoder::new(File::open(path)?).read_to_end(&mut bytes)?;
+        return csv_frame(bytes, options);
+    }
+    let pl_path = PlPathRef::from_local_path(path).into_owned();
     let lf = LazyCsvReader::new(pl_path)
         .with_has_header(true)
         // Avoid scanning the entire file to infer types.
         .with_infer_schema_length(Some(1_024))
         // Try to parse ISO-ish timestamps eagerly (e.g. RFC3339).
-        .with_try_parse_dates(true);
-    let mut lf = lf.finish()?;
-    ensure_columns(&mut lf, columns)?;
-    let df = lf
-        .select([
-            col(&columns.timestamp),
-            col(&columns.open),
-            col(&columns.high),
-            col(&columns.low),
-            col(&columns.close),
-            col(&columns.volume),
-        ])
-        .collect()?;
-    parse_frame(df, &options.columns)
+        .with_try_parse_dates(true)
+        .with_null_values(csv_null_values(options))
+        .with_separator(options.delimiter)
+        .with_quote_char(options.quote)
+        // Stop reading early when only a preview is requested.
+        .with_n_rows(options.limit);
+    Ok(lf.finish()?)
+}
+
+/// Loads only the last `n` candles of a CSV, sorted ascending by timestamp (or by
+/// `options.sort` when it names a tie-break column).
+///
+/// Uses two passes: a cheap byte scan to count data rows, then a scan that skips everything
+/// but the tail, so the full file is never materialized. Quoted fields containing newlines
+/// are not supported. Gzip-compressed files are decompressed whole instead.
+pub fn load_csv_tail(
+    path: impl AsRef<Path>,
+    n: usize,
+    options: LoadOptions,
+) -> Result<Vec<Candle>, LoadError> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let path = path.as_ref();
+    let sort = match &options.sort {
+        LoadSort::FileOrder => LoadSort::Timestamp,
+        sort => sort.clone(),
+    };
+    if is_gzip(path)? {
+        // No cheap row count through the compression; decompress and keep the tail.
+        let lf = csv_scan(
+            path,
+            &LoadOptions {
+                limit: None,
+                ..options.clone()
+            },
+        )?;
+        let options = LoadOptions {
+            limit: Some(n),
+            sort,
+            ..options
+        };
+        return collect_candles(lf.tail(n as IdxSize), &options);
+    }
+    let data_rows = count_lines(path)?.saturating_sub(1);
+    let skip = data_rows.saturating_sub(n);
+
+    let pl_path = PlPathRef::from_local_path(path).into_owned();
+    let lf = LazyCsvReader::new(pl_path)
+        .with_has_header(true)
+        .with_infer_schema_length(Some(1_024))
+        .with_try_parse_dates(true)
+        .with_null_values(csv_null_values(&options))
+        .with_separator(options.delimiter)
+        .with_quote_char(options.quote)
+        .with_skip_rows_after_header(skip);
+    let options = LoadOptions {
+        limit: Some(n),
+        sort,
+        ..options
+    };
+    collect_candles(lf.finish()?, &options)
+}
+
+/// Loads CSV text from any reader (e.g. clipboard contents) with the same rules as
+/// [`load_csv`], including transparent gzip decompression.
+pub fn load_csv_reader(
+    mut reader: impl Read,
+    options: LoadOptions,
+) -> Result<Vec<Candle>, LoadError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut plain = Vec::new();
+        MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut plain)?;
+        bytes = plain;
+    }
+    let lf = csv_frame(bytes, &options)?;
+    collect_candles(lf, &options)
+}
+
+/// Parses in-memory CSV bytes eagerly, with the same reader settings as [`csv_scan`].
+fn csv_frame(bytes: Vec<u8>, options: &LoadOptions) -> Result<LazyFrame, LoadError> {
+    let null_values = csv_null_values(options);
+    let df = CsvReadOptions::default()
+        .with_has_header(true)
+        .with_infer_schema_length(Some(1_024))
+        .with_n_rows(options.limit)
+        .map_parse_options(|parse| {
+            parse
+                .with_try_parse_dates(true)
+                .with_null_values(null_values.clone())
+                .with_separator(options.delimiter)
+                .with_quote_char(options.quote)
+        })
+        .into_reader_with_file_handle(std::io::Cursor::new(bytes))
+        .finish()?;
+    Ok(df.lazy())
+}
+
+fn is_gzip(path: &Path) -> Result<bool, LoadError> {
+    let mut magic = [0u8; 2];
+    let mut file = File::open(path)?;
+    let mut read = 0;
+    while read < magic.len() {
+        match file.read(&mut magic[read..])? {
+            0 => return Ok(false),
+            n => read += n,
+        }
+    }
+    Ok(magic == GZIP_MAGIC)
+}
+
+fn csv_null_values(options: &LoadOptions) -> Option<NullValues> {
+    (!options.null_values.is_empty()).then(|| {
+        NullValues::AllColumns(
+            options
+                .null_values
+                .iter()
+                .map(|v| PlSmallStr::from(v.as_str()))
+                .collect(),
+        )
+    })
+}
+
+/// Counts non-blank lines, including a final line without a trailing newline.
+fn count_lines(path: &Path) -> Result<usize, LoadError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buf = [0u8; 64 * 1024];
+    let mut lines = 0;
+    let mut line_has_content = false;
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        for &byte in &buf[..read] {
+            match byte {
+                b'\n' => {
+                    if line_has_content {
+                        lines += 1;
+                    }
+                    line_has_content = false;
+                }
+                b'\r' | b' ' | b'\t' => {}
+                _ => line_has_content = true,
+            }
+        }
+    }
+    if line_has_content {
+        lines += 1;
+    }
+    Ok(lines)
 }
 
 pub fn load_parquet(
@@ -34,49 +201,262 @@ pub fn load_parquet(
     options: LoadOptions,
 ) -> Result<Vec<Candle>, LoadError> {
     let pl_path = PlPathRef::from_local_path(path.as_ref()).into_owned();
-    let columns = &options.columns;
-    let mut lf = LazyFrame::scan_parquet(pl_path, ScanArgsParquet::default())?;
-    ensure_columns(&mut lf, columns)?;
-    let df = lf
-        .select([
-            col(&columns.timestamp),
-            col(&columns.open),
-            col(&columns.high),
-            col(&columns.low),
-            col(&columns.close),
-            col(&columns.volume),
-        ])
-        .collect()?;
-    parse_frame(df, &options.columns)
+    let lf = LazyFrame::scan_parquet(pl_path, ScanArgsParquet::default())?;
+    collect_candles(lf, &options)
+}
+
+/// [`load_parquet`] that also reads the optional `columns.bid` / `columns.ask` quote columns.
+pub fn load_parquet_ext(
+    path: impl AsRef<Path>,
+    options: LoadOptions,
+) -> Result<Vec<CandleExt>, LoadError> {
+    let pl_path = PlPathRef::from_local_path(path.as_ref()).into_owned();
+    let lf = LazyFrame::scan_parquet(pl_path, ScanArgsParquet::default())?;
+    collect_candles_ext(lf, &options)
+}
+
+/// Loads the candles of a Parquet file whose timestamps fall inside `range` (inclusive, as in
+/// the store). For datetime, date and epoch-second timestamp columns the bounds are pushed
+/// into the scan, so Polars skips row groups whose statistics lie outside the window; other
+/// timestamp types are filtered after parsing. `options.limit` counts in-range candles.
+pub fn load_parquet_range(
+    path: impl AsRef<Path>,
+    options: LoadOptions,
+    range: DataRange,
+) -> Result<Vec<Candle>, LoadError> {
+    let lf = parquet_range_scan(path.as_ref(), &options, &range)?;
+    let limit = options.limit;
+    let options = LoadOptions {
+        limit: None,
+        ..options
+    };
+    let mut candles = collect_candles(lf, &options)?;
+    // Pushed-down bounds are rounded to the column's unit, so trim the edges exactly.
+    let (start, end) = range_bounds(&range);
+    candles
+        .retain(|c| start.is_none_or(|s| c.timestamp >= s) && end.is_none_or(|e| c.timestamp <= e));
+    if let Some(limit) = limit {
+        candles.truncate(limit);
+    }
+    Ok(candles)
 }
 
-fn ensure_columns(lf: &mut LazyFrame, columns: &ColumnMapping) -> Result<(), LoadError> {
+pub(crate) fn parquet_range_scan(
+    path: &Path,
+    options: &LoadOptions,
+    range: &DataRange,
+) -> Result<LazyFrame, LoadError> {
+    let pl_path = PlPathRef::from_local_path(path).into_owned();
+    let args = ScanArgsParquet {
+        use_statistics: true,
+        ..ScanArgsParquet::default()
+    };
+    let mut lf = LazyFrame::scan_parquet(pl_path, args)?;
     let schema = lf.collect_schema()?;
-    for required in [
-        &columns.timestamp,
-        &columns.open,
-        &columns.high,
-        &columns.low,
-        &columns.close,
-        &columns.volume,
-    ] {
-        if schema.get(required.as_str()).is_none() {
-            return Err(LoadError::MissingColumn(required.clone()));
+    let column = resolve_column(&schema, &options.columns.or_canonical().timestamp, options)
+        .ok_or_else(|| LoadError::MissingColumn("timestamp".into()))?;
+    let dtype = schema
+        .get(column.as_str())
+        .cloned()
+        .ok_or_else(|| LoadError::MissingColumn(column.to_string()))?;
+
+    let (start, end) = range_bounds(range);
+    let lower = start.and_then(|ts| timestamp_literal(ts, &dtype));
+    let upper = end.and_then(|ts| timestamp_literal(ts, &dtype));
+    let predicate = match (lower, upper) {
+        (Some(lower), Some(upper)) => Some(
+            col(column.as_str())
+                .gt_eq(lower)
+                .and(col(column.as_str()).lt_eq(upper)),
+        ),
+        (Some(lower), None) => Some(col(column.as_str()).gt_eq(lower)),
+        (None, Some(upper)) => Some(col(column.as_str()).lt_eq(upper)),
+        (None, None) => None,
+    };
+    if let Some(predicate) = predicate {
+        lf = lf.filter(predicate);
+    }
+    Ok(lf)
+}
+
+fn range_bounds(range: &DataRange) -> (Option<OffsetDateTime>, Option<OffsetDateTime>) {
+    match range {
+        DataRange::All => (None, None),
+        DataRange::From(start) => (Some(*start), None),
+        DataRange::Until(end) => (None, Some(*end)),
+        DataRange::Between { start, end } => (Some(*start), Some(*end)),
+    }
+}
+
+/// `ts` as a literal comparable with a timestamp column of `dtype`, floored to its unit.
+/// `None` for types whose ordering doesn't follow time (e.g. strings).
+fn timestamp_literal(ts: OffsetDateTime, dtype: &DataType) -> Option<Expr> {
+    match dtype {
+        DataType::Datetime(unit, tz) => {
+            let per_unit: i128 = match unit {
+                TimeUnit::Nanoseconds => 1,
+                TimeUnit::Microseconds => 1_000,
+                TimeUnit::Milliseconds => 1_000_000,
+            };
+            let value = ts.unix_timestamp_nanos().div_euclid(per_unit) as i64;
+            Some(lit(value).cast(DataType::Datetime(*unit, tz.clone())))
+        }
+        DataType::Date => {
+            let days = ts.unix_timestamp().div_euclid(86_400) as i32;
+            Some(lit(days).cast(DataType::Date))
         }
+        DataType::Int64 => Some(lit(ts.unix_timestamp())),
+        DataType::Float64 => Some(lit(ts.unix_timestamp_nanos() as f64 / 1e9)),
+        _ => None,
     }
-    Ok(())
 }
 
-fn parse_frame(df: DataFrame, columns: &ColumnMapping) -> Result<Vec<Candle>, LoadError> {
+fn collect_candles(lf: LazyFrame, options: &LoadOptions) -> Result<Vec<Candle>, LoadError> {
+    Ok(collect_rows(lf, options, false)?
+        .into_iter()
+        .map(|row| row.candle)
+        .collect())
+}
+
+fn collect_candles_ext(lf: LazyFrame, options: &LoadOptions) -> Result<Vec<CandleExt>, LoadError> {
+    collect_rows(lf, options, true)
+}
+
+/// Shared by every loader; the quote columns are only selected when `quotes` is set.
+fn collect_rows(
+    mut lf: LazyFrame,
+    options: &LoadOptions,
+    quotes: bool,
+) -> Result<Vec<CandleExt>, LoadError> {
+    let options = &LoadOptions {
+        columns: options.columns.or_canonical(),
+        ..options.clone()
+    };
+    let columns = &options.columns;
+    let resolved = resolve_columns(&mut lf, options)?;
+    let tie_break = match &options.sort {
+        LoadSort::TimestampThen(column) => Some(
+            resolve_column(&lf.collect_schema()?, column, options)
+                .ok_or_else(|| LoadError::MissingColumn(column.clone()))?,
+        ),
+        _ => None,
+    };
+    // Source headers are renamed to the mapped names, which is what `parse_frame` reads.
+    let mut selected = vec![
+        col(resolved.timestamp).alias(columns.timestamp.as_str()),
+        col(resolved.open).alias(columns.open.as_str()),
+        col(resolved.high).alias(columns.high.as_str()),
+        col(resolved.low).alias(columns.low.as_str()),
+        col(resolved.close).alias(columns.close.as_str()),
+        match resolved.volume {
+            Some(volume) => col(volume).alias(columns.volume.as_str()),
+            None => lit(0.0).alias(columns.volume.as_str()),
+        },
+    ];
+    if quotes {
+        let schema = lf.collect_schema()?;
+        for name in [&columns.bid, &columns.ask] {
+            if let Some(source) = resolve_column(&schema, name, options) {
+                selected.push(col(source).alias(name.as_str()));
+            }
+        }
+    }
+    selected.extend(tie_break.clone().map(col));
+    let mut lf = lf.select(selected);
+    if let Some(limit) = options.limit {
+        lf = lf.limit(limit.min(IdxSize::MAX as usize) as IdxSize);
+    }
+    if let Some(column) = tie_break {
+        lf = lf.sort(
+            [column],
+            SortMultipleOptions::default().with_maintain_order(true),
+        );
+    }
+    let df = lf.collect()?;
+    let mut rows = parse_frame(df, options)?;
+    if options.sort != LoadSort::FileOrder {
+        // Stable: ties keep the tie-break column's order applied above, else file order.
+        rows.sort_by_key(|row| row.candle.timestamp);
+    }
+    Ok(rows)
+}
+
+/// Source header for each mapped column.
+struct ResolvedColumns {
+    timestamp: PlSmallStr,
+    open: PlSmallStr,
+    high: PlSmallStr,
+    low: PlSmallStr,
+    close: PlSmallStr,
+    /// `None` only under [`LoadOptions::volume_optional`].
+    volume: Option<PlSmallStr>,
+}
+
+/// Finds the source header for each mapped column; see [`LoadOptions::exact_column_names`].
+/// Missing columns are reported by field name rather than mapped header.
+fn resolve_columns(
+    lf: &mut LazyFrame,
+    options: &LoadOptions,
+) -> Result<ResolvedColumns, LoadError> {
+    let columns = &options.columns;
+    let schema = lf.collect_schema()?;
+    let required = |field: &str, name: &String| {
+        resolve_column(&schema, name, options).ok_or_else(|| LoadError::MissingColumn(field.into()))
+    };
+    let resolved = ResolvedColumns {
+        timestamp: required("timestamp", &columns.timestamp)?,
+        open: required("open", &columns.open)?,
+        high: required("high", &columns.high)?,
+        low: required("low", &columns.low)?,
+        close: required("close", &columns.close)?,
+        volume: resolve_column(&schema, &columns.volume, options),
+    };
+    if resolved.volume.is_none() && !options.volume_optional {
+        return Err(LoadError::MissingColumn("volume".into()));
+    }
+    Ok(resolved)
+}
+
+fn resolve_column(schema: &Schema, name: &str, options: &LoadOptions) -> Option<PlSmallStr> {
+    if schema.get(name).is_some() {
+        return Some(PlSmallStr::from(name));
+    }
+    if options.exact_column_names {
+        return None;
+    }
+    let wanted = name.trim();
+    schema
+        .iter_names()
+        .find(|header| header.trim().eq_ignore_ascii_case(wanted))
+        .cloned()
+}
+
+fn parse_frame(df: DataFrame, options: &LoadOptions) -> Result<Vec<CandleExt>, LoadError> {
+    let columns = &options.columns;
     let ts = df
         .column(&columns.timestamp)
         .map_err(|_| LoadError::MissingColumn(columns.timestamp.clone()))?;
+    if ts.is_empty() {
+        // Header-only files infer no useful dtypes; there is nothing to parse.
+        return Ok(Vec::new());
+    }
 
     let open = float64_col(&df, &columns.open)?;
     let high = float64_col(&df, &columns.high)?;
     let low = float64_col(&df, &columns.low)?;
     let close = float64_col(&df, &columns.close)?;
     let volume = float64_col(&df, &columns.volume)?;
+    // Only present when selected by an `_ext` loader and found in the source.
+    let bid = df
+        .column(&columns.bid)
+        .is_ok()
+        .then(|| float64_col(&df, &columns.bid))
+        .transpose()?;
+    let ask = df
+        .column(&columns.ask)
+        .is_ok()
+        .then(|| float64_col(&df, &columns.ask))
+        .transpose()?;
     let numeric = NumericCols {
         columns,
         open: &open,
@@ -84,6 +464,14 @@ fn parse_frame(df: DataFrame, columns: &ColumnMapping) -> Result<Vec<Candle>, Lo
         low: &low,
         close: &close,
         volume: &volume,
+        bid: bid.as_ref(),
+        ask: ask.as_ref(),
+        null_policy: options.null_policy,
+        nan_is_null: options
+            .null_values
+            .iter()
+            .any(|v| v.eq_ignore_ascii_case("nan")),
+        non_finite_is_null: options.non_finite_is_null,
     };
 
     let len = ts.len();
@@ -164,6 +552,19 @@ fn parse_frame(df: DataFrame, columns: &ColumnMapping) -> Result<Vec<Candle>, Lo
                 build_row(&mut candles, row, timestamp, &numeric)?;
             }
         }
+        DataType::Float32 | DataType::Float64 => {
+            let secs = ts.cast(&DataType::Float64)?;
+            let secs = secs.f64().expect("cast above ensures Float64");
+            for row in 0..len {
+                let secs = secs
+                    .get(row)
+                    .ok_or_else(|| LoadError::UnsupportedTimestamp {
+                        row,
+                        value: "null".to_string(),
+                    })?;
+                build_row(&mut candles, row, from_epoch_secs_f64(secs, row)?, &numeric)?;
+            }
+        }
         DataType::String => {
             let strings = ts
                 .str()
@@ -204,8 +605,15 @@ struct NumericCols<'a> {
     low: &'a Float64Chunked,
     close: &'a Float64Chunked,
     volume: &'a Float64Chunked,
+    bid: Option<&'a Float64Chunked>,
+    ask: Option<&'a Float64Chunked>,
+    null_policy: NullPolicy,
+    nan_is_null: bool,
+    non_finite_is_null: bool,
 }
 
+/// Reads a price/volume column as `f64`, casting from any numeric dtype (integer volumes,
+/// `Float32` prices, decimals) and parsing string columns.
 fn float64_col(df: &DataFrame, name: &str) -> Result<Float64Chunked, LoadError> {
     let s = df
         .column(name)
@@ -218,63 +626,64 @@ fn float64_col(df: &DataFrame, name: &str) -> Result<Float64Chunked, LoadError>
 }
 
 fn build_row(
-    out: &mut Vec<Candle>,
+    out: &mut Vec<CandleExt>,
     row: usize,
     timestamp: OffsetDateTime,
     numeric: &NumericCols<'_>,
 ) -> Result<(), LoadError> {
-    let open = numeric
-        .open
-        .get(row)
-        .ok_or_else(|| LoadError::InvalidNumber {
-            column: numeric.columns.open.clone(),
-            row,
-            value: "null".to_string(),
-        })?;
-    let high = numeric
-        .high
-        .get(row)
-        .ok_or_else(|| LoadError::InvalidNumber {
-            column: numeric.columns.high.clone(),
-            row,
-            value: "null".to_string(),
-        })?;
-    let low = numeric
-        .low
-        .get(row)
-        .ok_or_else(|| LoadError::InvalidNumber {
-            column: numeric.columns.low.clone(),
-            row,
-            value: "null".to_string(),
-        })?;
-    let close = numeric
-        .close
-        .get(row)
-        .ok_or_else(|| LoadError::InvalidNumber {
-            column: numeric.columns.close.clone(),
-            row,
-            value: "null".to_string(),
-        })?;
-    let volume = numeric
-        .volume
-        .get(row)
-        .ok_or_else(|| LoadError::InvalidNumber {
-            column: numeric.columns.volume.clone(),
-            row,
-            value: "null".to_string(),
-        })?;
+    let get = |values: &Float64Chunked| {
+        values.get(row).filter(|v| {
+            !(numeric.nan_is_null && v.is_nan() || numeric.non_finite_is_null && !v.is_finite())
+        })
+    };
+    let columns = numeric.columns;
+    let mut prices = [
+        (get(numeric.open), &columns.open),
+        (get(numeric.high), &columns.high),
+        (get(numeric.low), &columns.low),
+        (get(numeric.close), &columns.close),
+    ];
+
+    if let Some((_, column)) = prices.iter().find(|(value, _)| value.is_none()) {
+        match numeric.null_policy {
+            NullPolicy::Error => {
+                return Err(LoadError::InvalidNumber {
+                    column: (*column).clone(),
+                    row,
+                    value: "null".to_string(),
+                });
+            }
+            NullPolicy::Skip => return Ok(()),
+            NullPolicy::ForwardFill => {
+                let Some(prev) = out.last().map(|row| &row.candle) else {
+                    return Ok(());
+                };
+                let previous = [prev.open, prev.high, prev.low, prev.close];
+                for ((value, _), fill) in prices.iter_mut().zip(previous) {
+                    value.get_or_insert(fill);
+                }
+            }
+        }
+    }
+
+    let [open, high, low, close] = prices.map(|(value, _)| value.unwrap_or_default());
+    let volume = get(numeric.volume).unwrap_or(0.0);
 
     if low > high {
         return Err(LoadError::InvertedRange { row, low, high });
     }
 
-    out.push(Candle {
-        timestamp,
-        open,
-        high,
-        low,
-        close,
-        volume,
+    out.push(CandleExt {
+        candle: Candle {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        },
+        bid: numeric.bid.and_then(get),
+        ask: numeric.ask.and_then(get),
     });
 
     Ok(())
@@ -296,6 +705,7 @@ fn to_datetime(value: AnyValue, row: usize) -> Result<OffsetDateTime, LoadError>
                 value: secs.to_string(),
             })
         }
+        AnyValue::Float64(secs) => from_epoch_secs_f64(secs, row),
         AnyValue::String(s) => {
             OffsetDateTime::parse(s, &Rfc3339).map_err(|err| LoadError::UnsupportedTimestamp {
                 row,
@@ -310,6 +720,22 @@ fn to_datetime(value: AnyValue, row: usize) -> Result<OffsetDateTime, LoadError>
     }
 }
 
+/// Fractional Unix seconds (e.g. `1704067200.25`). The fraction is kept to the microsecond,
+/// about as fine as an f64 resolves at current epochs.
+fn from_epoch_secs_f64(secs: f64, row: usize) -> Result<OffsetDateTime, LoadError> {
+    let invalid = || LoadError::UnsupportedTimestamp {
+        row,
+        value: secs.to_string(),
+    };
+    if !secs.is_finite() {
+        return Err(invalid());
+    }
+    let whole = secs.floor();
+    let micros = ((secs - whole) * 1e6).round() as i128;
+    let nanos = whole as i128 * 1_000_000_000 + micros * 1_000;
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| invalid())
+}
+
 fn from_timestamp(value: i64, unit: TimeUnit, row: usize) -> Result<OffsetDateTime, LoadError> {
     let nanos = match unit {
         TimeUnit::Nanoseconds => value,
@@ -1,6 +1,7 @@
-use crate::{Candle, Interval};
+use crate::{Candle, Interval, SessionCalendar};
 use time::{Duration, OffsetDateTime};
 
+/// Min low / max high, skipping non-finite values; `None` when no finite value is left.
 pub fn bounds(candles: &[Candle]) -> Option<(f64, f64)> {
     if candles.is_empty() {
         return None;
@@ -8,10 +9,14 @@ pub fn bounds(candles: &[Candle]) -> Option<(f64, f64)> {
     let mut min = f64::MAX;
     let mut max = f64::MIN;
     for c in candles {
-        min = min.min(c.low);
-        max = max.max(c.high);
+        if c.low.is_finite() {
+            min = min.min(c.low);
+        }
+        if c.high.is_finite() {
+            max = max.max(c.high);
+        }
     }
-    Some((min, max))
+    (min <= max).then_some((min, max))
 }
 
 pub fn resample(candles: &[Candle], interval: Interval) -> Vec<Candle> {
@@ -21,59 +26,279 @@ pub fn resample(candles: &[Candle], interval: Interval) -> Vec<Candle> {
 
     let duration = interval.as_duration();
     let mut out: Vec<Candle> = Vec::new();
+    let mut first = 0;
     let mut bucket_start = align_timestamp(candles[0].timestamp, duration);
-    let mut bucket_end = bucket_start + duration;
-
-    let mut acc_open = candles[0].open;
-    let mut acc_high = candles[0].high;
-    let mut acc_low = candles[0].low;
-    let mut acc_close = candles[0].close;
-    let mut acc_volume = candles[0].volume;
-
-    for c in candles.iter().skip(1) {
-        if c.timestamp < bucket_end {
-            acc_high = acc_high.max(c.high);
-            acc_low = acc_low.min(c.low);
-            acc_close = c.close;
-            acc_volume += c.volume;
-        } else {
-            out.push(Candle {
-                timestamp: bucket_start,
-                open: acc_open,
-                high: acc_high,
-                low: acc_low,
-                close: acc_close,
-                volume: acc_volume,
-            });
 
+    for (idx, c) in candles.iter().enumerate().skip(1) {
+        if c.timestamp >= bucket_start + duration {
+            out.push(merge(&candles[first..idx], bucket_start));
+            first = idx;
             bucket_start = align_timestamp(c.timestamp, duration);
-            bucket_end = bucket_start + duration;
-            acc_open = c.open;
-            acc_high = c.high;
-            acc_low = c.low;
-            acc_close = c.close;
-            acc_volume = c.volume;
         }
     }
+    out.push(merge(&candles[first..], bucket_start));
+
+    out
+}
+
+/// Incremental [`resample`] for recording a stream: candles of the `source` interval go in
+/// one at a time, in timestamp order, and each `target` bucket comes out once it is complete,
+/// without keeping the raw candles around.
+#[derive(Debug, Clone)]
+pub struct StreamResampler {
+    source: Duration,
+    target: Duration,
+    /// The bucket being filled, stamped at its aligned start.
+    pending: Option<Candle>,
+}
+
+impl StreamResampler {
+    pub fn new(source: Interval, target: Interval) -> Self {
+        Self {
+            source: source.as_duration(),
+            target: target.as_duration(),
+            pending: None,
+        }
+    }
+
+    /// Folds `candle` in and returns the buckets it completed, oldest first: the pending one
+    /// when `candle` opens a later bucket, and its own bucket when `candle` fills that
+    /// bucket's final `source` slot. Out-of-order candles fold into the pending bucket, as in
+    /// [`resample`].
+    pub fn push(&mut self, candle: Candle) -> Vec<Candle> {
+        let mut done = Vec::new();
+        let slot_end = candle.timestamp + self.source;
+        match self.pending.as_mut() {
+            Some(acc) if candle.timestamp < acc.timestamp + self.target => {
+                acc.high = acc.high.max(candle.high);
+                acc.low = acc.low.min(candle.low);
+                acc.close = candle.close;
+                acc.volume += candle.volume;
+            }
+            _ => {
+                done.extend(self.pending.take());
+                self.pending = Some(Candle {
+                    timestamp: align_timestamp(candle.timestamp, self.target),
+                    ..candle
+                });
+            }
+        }
+        if let Some(acc) = &self.pending
+            && slot_end >= acc.timestamp + self.target
+        {
+            done.extend(self.pending.take());
+        }
+        done
+    }
 
-    out.push(Candle {
-        timestamp: bucket_start,
-        open: acc_open,
-        high: acc_high,
-        low: acc_low,
-        close: acc_close,
-        volume: acc_volume,
-    });
+    /// The partly filled bucket, if any.
+    pub fn pending(&self) -> Option<&Candle> {
+        self.pending.as_ref()
+    }
+
+    /// Ends the stream, returning the partly filled last bucket.
+    pub fn finish(self) -> Option<Candle> {
+        self.pending
+    }
+}
 
+/// `candles` as one candle: first open, highest high, lowest low, last close and summed
+/// volume, stamped at the first timestamp. `None` when empty.
+pub fn aggregate(candles: &[Candle]) -> Option<Candle> {
+    let first = candles.first()?;
+    Some(merge(candles, first.timestamp))
+}
+
+/// At most `n` closes tracing `candles`, for sparklines: the series is split into `n / 2`
+/// equal index buckets and each contributes its lowest and highest close in order, so
+/// spikes survive. Series of at most `n` candles come back whole; ignores timestamps.
+pub fn decimate(candles: &[Candle], n: usize) -> Vec<f64> {
+    if candles.len() <= n {
+        return candles.iter().map(|c| c.close).collect();
+    }
+    if n < 2 {
+        return candles
+            .last()
+            .map(|c| c.close)
+            .into_iter()
+            .take(n)
+            .collect();
+    }
+    let buckets = n / 2;
+    let mut out = Vec::with_capacity(buckets * 2);
+    for b in 0..buckets {
+        let start = b * candles.len() / buckets;
+        let end = (b + 1) * candles.len() / buckets;
+        let bucket = &candles[start..end];
+        let (mut lo, mut hi) = (0, 0);
+        for (i, c) in bucket.iter().enumerate() {
+            if c.close < bucket[lo].close {
+                lo = i;
+            }
+            if c.close > bucket[hi].close {
+                hi = i;
+            }
+        }
+        match lo.cmp(&hi) {
+            std::cmp::Ordering::Less => out.extend([bucket[lo].close, bucket[hi].close]),
+            std::cmp::Ordering::Greater => out.extend([bucket[hi].close, bucket[lo].close]),
+            std::cmp::Ordering::Equal => out.push(bucket[lo].close),
+        }
+    }
     out
 }
 
+/// Folds a non-empty bucket into one candle stamped `timestamp`.
+fn merge(bucket: &[Candle], timestamp: OffsetDateTime) -> Candle {
+    let first = Candle {
+        timestamp,
+        ..bucket[0].clone()
+    };
+    bucket[1..].iter().fold(first, |mut acc, c| {
+        acc.high = acc.high.max(c.high);
+        acc.low = acc.low.min(c.low);
+        acc.close = c.close;
+        acc.volume += c.volume;
+        acc
+    })
+}
+
+/// Time left until the candle forming at `last_ts` closes at `last_ts + interval`. Once that
+/// close has passed without a newer candle, counts down to the end of the bucket `now` falls
+/// in instead; a `now` before `last_ts` reports the full interval.
+pub fn time_to_close(interval: Interval, last_ts: OffsetDateTime, now: OffsetDateTime) -> Duration {
+    let step = interval.as_duration().whole_nanoseconds();
+    if step <= 0 {
+        return Duration::ZERO;
+    }
+    let elapsed = (now - last_ts).whole_nanoseconds().max(0);
+    i64::try_from(step - elapsed % step).map_or(Duration::MAX, Duration::nanoseconds)
+}
+
+/// Intervals [`resample_to_count`] picks from, finest first.
+pub const STANDARD_INTERVALS: [Interval; 15] = [
+    Interval::Second(1),
+    Interval::Second(5),
+    Interval::Second(15),
+    Interval::Second(30),
+    Interval::Minute(1),
+    Interval::Minute(5),
+    Interval::Minute(15),
+    Interval::Minute(30),
+    Interval::Hour(1),
+    Interval::Hour(2),
+    Interval::Hour(4),
+    Interval::Hour(12),
+    Interval::Day(1),
+    Interval::Day(7),
+    Interval::Day(30),
+];
+
+/// Resamples to the finest [`STANDARD_INTERVALS`] entry that yields at most `target`
+/// candles, falling back to the coarsest when none does.
+pub fn resample_to_count(candles: &[Candle], target: usize) -> (Vec<Candle>, Interval) {
+    let target = target.max(1);
+    let interval = STANDARD_INTERVALS
+        .into_iter()
+        .find(|interval| bucket_count(candles, interval.as_duration()) <= target)
+        .unwrap_or(STANDARD_INTERVALS[STANDARD_INTERVALS.len() - 1]);
+    (resample(candles, interval), interval)
+}
+
+/// Number of candles [`resample`] would produce, without building them.
+fn bucket_count(candles: &[Candle], duration: Duration) -> usize {
+    let Some(first) = candles.first() else {
+        return 0;
+    };
+    let mut count = 1;
+    let mut bucket_end = align_timestamp(first.timestamp, duration) + duration;
+    for c in &candles[1..] {
+        if c.timestamp >= bucket_end {
+            count += 1;
+            bucket_end = align_timestamp(c.timestamp, duration) + duration;
+        }
+    }
+    count
+}
+
+/// Returns each run of missing `interval` buckets as a half-open `[start, end)` span.
+///
+/// Timestamps are aligned to the interval first, so `start` is the first missing bucket and
+/// `end` is the next bucket that has data (or where `calendar` closes). Buckets on days the
+/// calendar is closed are never reported. Input is expected to be sorted ascending.
+pub fn find_gaps(
+    candles: &[Candle],
+    interval: Interval,
+    calendar: &SessionCalendar,
+) -> Vec<(OffsetDateTime, OffsetDateTime)> {
+    let duration = interval.as_duration();
+    if duration.is_zero() {
+        return Vec::new();
+    }
+
+    let mut gaps = Vec::new();
+    let mut prev: Option<OffsetDateTime> = None;
+    for c in candles {
+        let bucket = align_timestamp(c.timestamp, duration);
+        if let Some(prev_bucket) = prev {
+            let expected = prev_bucket + duration;
+            if bucket > expected {
+                push_open_spans(&mut gaps, expected, bucket, duration, calendar);
+            }
+            if bucket <= prev_bucket {
+                continue;
+            }
+        }
+        prev = Some(bucket);
+    }
+    gaps
+}
+
+/// Splits `[start, end)` at day boundaries and keeps only the parts on open days.
+fn push_open_spans(
+    gaps: &mut Vec<(OffsetDateTime, OffsetDateTime)>,
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    duration: Duration,
+    calendar: &SessionCalendar,
+) {
+    let mut run_start = None;
+    let mut t = start;
+    while t < end {
+        let next_day = t
+            .date()
+            .next_day()
+            .map(|d| d.midnight().assume_offset(t.offset()))
+            .map_or(end, |midnight| align_up(midnight, duration).min(end));
+        if calendar.is_open(t) {
+            run_start.get_or_insert(t);
+        } else if let Some(run) = run_start.take() {
+            gaps.push((run, t));
+        }
+        t = next_day.max(t + duration);
+    }
+    if let Some(run) = run_start {
+        gaps.push((run, end));
+    }
+}
+
+fn align_up(ts: OffsetDateTime, duration: Duration) -> OffsetDateTime {
+    let aligned = align_timestamp(ts, duration);
+    if aligned < ts {
+        aligned + duration
+    } else {
+        aligned
+    }
+}
+
 fn align_timestamp(ts: OffsetDateTime, duration: Duration) -> OffsetDateTime {
     if duration.is_zero() {
         return ts;
     }
     let nanos = duration.whole_nanoseconds();
     let ts_nanos = ts.unix_timestamp_nanos();
-    let bucket = ts_nanos - (ts_nanos % nanos);
+    // Euclidean remainder floors pre-epoch timestamps too, instead of rounding them up into
+    // the following bucket.
+    let bucket = ts_nanos - ts_nanos.rem_euclid(nanos);
     OffsetDateTime::from_unix_timestamp_nanos(bucket).unwrap_or(ts)
 }
@@ -0,0 +1,159 @@
+use std::path::Path;
+
+#[cfg(feature = "reqwest")]
+use crate::LoadError;
+#[cfg(feature = "reqwest")]
+use std::path::PathBuf;
+
+/// How a user-supplied source string should be opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    Csv,
+    Parquet,
+    /// `http://` or `https://`; fetch it first (see `fetch_to_temp`).
+    Url,
+}
+
+/// Classifies `source` by scheme, then by file extension; anything unknown is CSV.
+pub fn detect_format(source: &str) -> SourceFormat {
+    let lower = source.trim().to_ascii_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        return SourceFormat::Url;
+    }
+    format_for_path(Path::new(source))
+}
+
+fn format_for_path(path: &Path) -> SourceFormat {
+    let is_parquet = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"));
+    if is_parquet {
+        SourceFormat::Parquet
+    } else {
+        SourceFormat::Csv
+    }
+}
+
+/// Picks the on-disk format for a downloaded body, preferring the `Content-Type` header and
+/// falling back to the extension of the final (post-redirect) URL path.
+pub fn format_for_response(content_type: Option<&str>, url_path: &str) -> SourceFormat {
+    let mime = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase());
+    match mime.as_deref() {
+        Some(m) if m.contains("parquet") => SourceFormat::Parquet,
+        Some("text/csv" | "application/csv" | "text/plain") => SourceFormat::Csv,
+        _ => format_for_path(Path::new(url_path)),
+    }
+}
+
+/// Downloads `url` (following redirects) into the temp dir and returns the local path.
+///
+/// The file extension reflects the detected format so it can be passed to `load_csv` or
+/// `load_parquet` via [`detect_format`].
+#[cfg(feature = "reqwest")]
+pub fn fetch_to_temp(url: &str) -> Result<PathBuf, LoadError> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let response = reqwest::blocking::get(url)?.error_for_status()?;
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let format = format_for_response(content_type.as_deref(), response.url().path());
+    let body = response.bytes()?;
+
+    let ext = match format {
+        SourceFormat::Parquet => "parquet",
+        SourceFormat::Csv | SourceFormat::Url => "csv",
+    };
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let path = std::env::temp_dir().join(format!("gpui-kbar-fetch-{nonce}.{ext}"));
+    std::fs::write(&path, &body)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_urls_and_file_extensions() {
+        assert_eq!(
+            detect_format("https://example.com/AAPL.csv"),
+            SourceFormat::Url
+        );
+        assert_eq!(detect_format("HTTP://example.com/x"), SourceFormat::Url);
+        assert_eq!(
+            detect_format("data/candles/AAPL.PARQUET"),
+            SourceFormat::Parquet
+        );
+        assert_eq!(detect_format("data/candles/AAPL.csv"), SourceFormat::Csv);
+        assert_eq!(detect_format("data/candles/AAPL.csv.gz"), SourceFormat::Csv);
+        assert_eq!(detect_format("AAPL"), SourceFormat::Csv);
+    }
+
+    #[test]
+    fn response_format_prefers_content_type() {
+        assert_eq!(
+            format_for_response(Some("application/vnd.apache.parquet"), "/download"),
+            SourceFormat::Parquet
+        );
+        assert_eq!(
+            format_for_response(Some("text/csv; charset=utf-8"), "/x.parquet"),
+            SourceFormat::Csv
+        );
+        assert_eq!(
+            format_for_response(Some("application/octet-stream"), "/x.parquet"),
+            SourceFormat::Parquet
+        );
+        assert_eq!(format_for_response(None, "/x"), SourceFormat::Csv);
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[test]
+    fn fetch_follows_redirects_into_temp_file() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                reader.read_line(&mut request_line).unwrap();
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+
+                let response = if request_line.starts_with("GET /start ") {
+                    "HTTP/1.1 302 Found\r\nLocation: /data\r\nContent-Length: 0\r\n\r\n".to_string()
+                } else {
+                    let body = "timestamp,open,high,low,close,volume\n\
+                                2024-01-01T00:00:00Z,1.0,2.0,0.5,1.5,100\n";
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/csv\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let path = fetch_to_temp(&format!("http://{addr}/start")).unwrap();
+        let loaded = crate::load_csv(&path, crate::LoadOptions::default());
+        std::fs::remove_file(&path).ok();
+        server.join().unwrap();
+
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("csv"));
+        assert_eq!(loaded.unwrap().len(), 1);
+    }
+}
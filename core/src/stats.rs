@@ -0,0 +1,66 @@
+/// Log returns `ln(p[i] / p[i - 1])`; one element shorter than `closes`.
+pub fn returns(closes: &[f64]) -> Vec<f64> {
+    closes
+        .windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect()
+}
+
+/// Rolling sample standard deviation of `returns` over `window` values.
+///
+/// Output is aligned with the input: entry `i` covers `returns[i + 1 - window..=i]` and is
+/// `None` until a full window is available (or always, when `window < 2`).
+pub fn rolling_volatility(returns: &[f64], window: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; returns.len()];
+    if window < 2 || returns.len() < window {
+        return out;
+    }
+
+    for (end, slot) in out.iter_mut().enumerate().skip(window - 1) {
+        let slice = &returns[end + 1 - window..=end];
+        let mean = slice.iter().sum::<f64>() / window as f64;
+        let var = slice.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (window - 1) as f64;
+        *slot = Some(var.sqrt());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{a} != {b}");
+    }
+
+    #[test]
+    fn constant_prices_have_zero_returns_and_volatility() {
+        let rets = returns(&[10.0; 6]);
+        assert_eq!(rets, vec![0.0; 5]);
+
+        let vol = rolling_volatility(&rets, 3);
+        assert_eq!(vol[..2], [None, None]);
+        assert!(vol[2..].iter().all(|v| *v == Some(0.0)));
+    }
+
+    #[test]
+    fn known_series_matches_hand_computed_values() {
+        let rets = returns(&[100.0, 110.0, 99.0]);
+        assert_eq!(rets.len(), 2);
+        assert_close(rets[0], 1.1_f64.ln());
+        assert_close(rets[1], 0.9_f64.ln());
+
+        let vol = rolling_volatility(&rets, 2);
+        assert_eq!(vol[0], None);
+        let expected = (rets[0] - rets[1]).abs() / 2.0_f64.sqrt();
+        assert_close(vol[1].unwrap(), expected);
+    }
+
+    #[test]
+    fn short_inputs_yield_no_values() {
+        assert!(returns(&[]).is_empty());
+        assert!(returns(&[1.0]).is_empty());
+        assert_eq!(rolling_volatility(&[0.1, 0.2], 1), vec![None, None]);
+        assert_eq!(rolling_volatility(&[0.1], 2), vec![None]);
+    }
+}
@@ -11,6 +11,12 @@ use time::{
 
 use crate::Candle;
 
+/// Interval tag for candles written without an explicit timeframe.
+pub const RAW_INTERVAL: &str = "raw";
+
+/// Default pragmas applied to every connection, e.g. `threads=4,memory_limit=2GB`.
+pub const DUCKDB_PRAGMAS_ENV: &str = "GPUI_KBAR_DUCKDB_PRAGMAS";
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct UniverseRow {
     pub filters: String,
@@ -39,6 +45,28 @@ pub enum DataRange {
     },
 }
 
+/// First/last stored timestamp and row count for one symbol and interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CandleCoverage {
+    pub first: OffsetDateTime,
+    pub last: OffsetDateTime,
+    pub count: usize,
+}
+
+/// Store-side aggregates over a symbol's candles; see [`DuckDbStore::symbol_stats`].
+/// Timestamps and prices are `None` when no candle falls in the range.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolStats {
+    pub count: usize,
+    pub first: Option<OffsetDateTime>,
+    pub last: Option<OffsetDateTime>,
+    /// Lowest low.
+    pub min_price: Option<f64>,
+    /// Highest high.
+    pub max_price: Option<f64>,
+    pub total_volume: f64,
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct UserSession {
     pub active_source: Option<String>,
@@ -56,6 +84,15 @@ pub struct UserSession {
     pub chunk_rep: Option<String>,
     pub live_source_id: Option<String>,
     pub live_interval: Option<String>,
+    pub fast_wicks: Option<bool>,
+    pub tooltip_placement: Option<String>,
+    pub cache_candles: Option<bool>,
+    /// Loads forward-fill bad prices instead of failing.
+    pub forward_fill: Option<bool>,
+    pub show_volume: Option<bool>,
+    pub theme_preset: Option<String>,
+    /// Interval labels the user added to the interval menu, e.g. `4h`.
+    pub custom_intervals: Vec<String>,
 }
 
 #[derive(Debug, Error)]
@@ -70,6 +107,32 @@ pub enum StoreError {
     TimeParse { value: String, source: Parse },
     #[error("failed to format timestamp: {0}")]
     TimeFormat(#[from] Format),
+    /// A write failed on one backend and every backend was rolled back.
+    #[error("write to {backend} backend failed, all backends rolled back: {source}")]
+    WriteRolledBack {
+        backend: &'static str,
+        source: Box<StoreError>,
+    },
+    /// COMMIT failed on one backend after the backends listed in `committed` had
+    /// already committed; those keep the write while the rest were rolled back.
+    #[error("commit to {backend} backend failed after {committed:?} committed: {source}")]
+    PartialCommit {
+        backend: &'static str,
+        committed: Vec<&'static str>,
+        source: Box<StoreError>,
+    },
+    #[error("invalid pragma '{0}', expected name=value with an alphanumeric name")]
+    InvalidPragma(String),
+    /// DuckDB rejected the pragma, e.g. an unknown name or an out-of-range value.
+    #[error("PRAGMA {name} failed: {source}")]
+    Pragma { name: String, source: duckdb::Error },
+}
+
+/// What [`DuckDbStore::set_mode`] copies from the old backends into the new ones.
+struct CarriedState {
+    session: Vec<(String, String)>,
+    watchlist: Vec<String>,
+    candles: Vec<(String, String, Vec<Candle>)>,
 }
 
 #[derive(Default)]
@@ -81,6 +144,8 @@ struct StoreBackend {
 
 pub struct DuckDbStore {
     mode: StorageMode,
+    namespace: Option<String>,
+    pragmas: Vec<(String, String)>,
     shared_disk: Option<Connection>,
     config: StoreBackend,
     data: StoreBackend,
@@ -102,6 +167,12 @@ impl DuckDbStore {
     ) -> Result<Self, StoreError> {
         let mut store = Self {
             mode,
+            namespace: None,
+            pragmas: std::env::var(DUCKDB_PRAGMAS_ENV)
+                .ok()
+                .map(|raw| parse_pragmas(&raw))
+                .transpose()?
+                .unwrap_or_default(),
             shared_disk: None,
             config: StoreBackend {
                 disk_path: Some(config_path.as_ref().to_path_buf()),
@@ -117,17 +188,146 @@ impl DuckDbStore {
         Ok(store)
     }
 
+    /// Prefixes every session-state key with `prefix` so several runtimes can share one DB.
+    pub fn with_namespace(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        self.namespace = (!prefix.is_empty()).then_some(prefix);
+        self
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    fn session_key(&self, key: &str) -> String {
+        match &self.namespace {
+            Some(prefix) => format!("{prefix}:{key}"),
+            None => key.to_string(),
+        }
+    }
+
+    /// Issues `PRAGMA name=value` on every open connection and remembers the pragmas so
+    /// connections opened by later mode switches get them too. Stops at the first pragma
+    /// DuckDB rejects.
+    pub fn configure(&mut self, pragmas: &[(&str, &str)]) -> Result<(), StoreError> {
+        let pragmas = pragmas
+            .iter()
+            .map(|&(name, value)| validate_pragma(name, value))
+            .collect::<Result<Vec<_>, _>>()?;
+        for conn in self.all_connections() {
+            apply_pragmas(conn, &pragmas)?;
+        }
+        for (name, value) in pragmas {
+            self.pragmas.retain(|(existing, _)| *existing != name);
+            self.pragmas.push((name, value));
+        }
+        Ok(())
+    }
+
     pub fn set_disk_path(&mut self, path: impl AsRef<Path>) {
         let path = path.as_ref().to_path_buf();
         self.config.disk_path = Some(path.clone());
         self.data.disk_path = Some(path);
     }
 
+    /// Reopens the backends for `mode`, re-writing the stored candles, session state and
+    /// watchlist into them so a live switch (e.g. `Disk` to `Memory` to stop persisting)
+    /// keeps what was already loaded. Indicator values are not carried over. On failure the
+    /// previous mode is restored with the same contents and the error is returned.
     pub fn set_mode(&mut self, mode: StorageMode) -> Result<(), StoreError> {
-        self.reconfigure(mode)?;
+        let previous = self.mode;
+        let carried = CarriedState {
+            session: self.session_rows()?,
+            watchlist: self.get_watchlist()?,
+            candles: self.candle_series()?,
+        };
+        let switched = self
+            .reconfigure(mode)
+            .and_then(|()| self.write_carried(&carried));
+        if let Err(err) = switched {
+            // Memory backends went away with the old connections; refill them too.
+            self.reconfigure(previous)?;
+            self.write_carried(&carried)?;
+            return Err(err);
+        }
         Ok(())
     }
 
+    fn write_carried(&self, carried: &CarriedState) -> Result<(), StoreError> {
+        for conn in self.config_connections() {
+            for (key, value) in &carried.session {
+                conn.execute(
+                    "INSERT INTO session_state(key, value) VALUES (?, ?)
+                     ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                    params![key, value],
+                )?;
+            }
+        }
+        self.set_watchlist(&carried.watchlist)?;
+        for (symbol, interval, candles) in &carried.candles {
+            self.write_candles(symbol, Some(interval), candles)?;
+        }
+        Ok(())
+    }
+
+    /// Raw `(key, value)` session rows of every namespace, from the first config backend
+    /// that has any.
+    fn session_rows(&self) -> Result<Vec<(String, String)>, StoreError> {
+        let mut rows_out = Vec::new();
+        for conn in self.config_connections() {
+            let mut stmt = conn.prepare("SELECT key, value FROM session_state ORDER BY key")?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                rows_out.push((row.get(0)?, row.get(1)?));
+            }
+            if !rows_out.is_empty() {
+                break;
+            }
+        }
+        Ok(rows_out)
+    }
+
+    /// Every stored `(symbol, interval)` series, read from the first data backend that has
+    /// any candles.
+    fn candle_series(&self) -> Result<Vec<(String, String, Vec<Candle>)>, StoreError> {
+        let mut series: Vec<(String, String, Vec<Candle>)> = Vec::new();
+        for conn in self.data_connections() {
+            let mut stmt = conn.prepare(
+                "SELECT symbol, \"interval\", timestamp, open, high, low, close, volume
+                 FROM candles
+                 ORDER BY symbol, \"interval\", timestamp",
+            )?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                let symbol: String = row.get(0)?;
+                let interval: String = row.get(1)?;
+                let ts_str: String = row.get(2)?;
+                let timestamp = OffsetDateTime::parse(&ts_str, &Rfc3339).map_err(|source| {
+                    StoreError::TimeParse {
+                        value: ts_str.clone(),
+                        source,
+                    }
+                })?;
+                let candle = Candle {
+                    timestamp,
+                    open: row.get(3)?,
+                    high: row.get(4)?,
+                    low: row.get(5)?,
+                    close: row.get(6)?,
+                    volume: row.get(7)?,
+                };
+                match series.last_mut() {
+                    Some((s, i, candles)) if *s == symbol && *i == interval => candles.push(candle),
+                    _ => series.push((symbol, interval, vec![candle])),
+                }
+            }
+            if !series.is_empty() {
+                break;
+            }
+        }
+        Ok(series)
+    }
+
     fn open_config_memory(&self) -> Result<Connection, StoreError> {
         let conn = Connection::open_in_memory()?;
         init_config_schema(&conn)?;
@@ -199,11 +399,63 @@ impl DuckDbStore {
             }
         }
 
+        for conn in self.all_connections() {
+            apply_pragmas(conn, &self.pragmas)?;
+        }
         self.mode = mode;
 
         Ok(())
     }
 
+    /// Issues `CHECKPOINT` on every disk connection so committed writes land in the database
+    /// file instead of only its WAL. Does nothing in memory mode.
+    pub fn flush(&self) -> Result<(), StoreError> {
+        for conn in self
+            .config
+            .disk
+            .iter()
+            .chain(self.data.disk.iter())
+            .chain(self.shared_disk.iter())
+        {
+            conn.execute_batch("CHECKPOINT")?;
+        }
+        Ok(())
+    }
+
+    /// Flushes, then closes every connection now rather than whenever the store is dropped.
+    pub fn close(self) -> Result<(), StoreError> {
+        self.flush()?;
+        let Self {
+            shared_disk,
+            config,
+            data,
+            ..
+        } = self;
+        for conn in [
+            config.memory,
+            config.disk,
+            data.memory,
+            data.disk,
+            shared_disk,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            conn.close().map_err(|(_, err)| err)?;
+        }
+        Ok(())
+    }
+
+    fn all_connections(&self) -> impl Iterator<Item = &Connection> {
+        self.config
+            .memory
+            .iter()
+            .chain(self.config.disk.iter())
+            .chain(self.data.memory.iter())
+            .chain(self.data.disk.iter())
+            .chain(self.shared_disk.iter())
+    }
+
     fn config_connections(&self) -> impl Iterator<Item = &Connection> {
         self.config
             .memory
@@ -220,6 +472,66 @@ impl DuckDbStore {
             .chain(self.shared_disk.iter())
     }
 
+    fn labeled_data_connections(&self) -> impl Iterator<Item = (&'static str, &Connection)> {
+        self.data
+            .memory
+            .iter()
+            .map(|conn| ("memory", conn))
+            .chain(self.data.disk.iter().map(|conn| ("disk", conn)))
+            .chain(self.shared_disk.iter().map(|conn| ("disk", conn)))
+    }
+
+    /// Runs `write` inside one transaction per data backend, committing only if every
+    /// backend succeeded. Any failure rolls back all backends so `Both` mode never
+    /// diverges between memory and disk.
+    fn write_all_data(
+        &self,
+        write: impl Fn(&Connection) -> Result<(), StoreError>,
+    ) -> Result<(), StoreError> {
+        let conns: Vec<_> = self.labeled_data_connections().collect();
+        if conns.is_empty() {
+            return Err(StoreError::NoBackend);
+        }
+
+        let rollback = |conns: &[(&'static str, &Connection)]| {
+            for (_, conn) in conns {
+                let _ = conn.execute_batch("ROLLBACK");
+            }
+        };
+
+        for (idx, (backend, conn)) in conns.iter().enumerate() {
+            let result = conn
+                .execute_batch("BEGIN TRANSACTION")
+                .map_err(StoreError::from)
+                .and_then(|()| write(conn));
+            if let Err(err) = result {
+                rollback(&conns[..=idx]);
+                return Err(StoreError::WriteRolledBack {
+                    backend,
+                    source: Box::new(err),
+                });
+            }
+        }
+
+        for (idx, (backend, conn)) in conns.iter().enumerate() {
+            if let Err(err) = conn.execute_batch("COMMIT") {
+                rollback(&conns[idx..]);
+                let source = Box::new(err.into());
+                if idx == 0 {
+                    return Err(StoreError::WriteRolledBack { backend, source });
+                }
+                // Committed transactions cannot be undone; name them for the caller.
+                return Err(StoreError::PartialCommit {
+                    backend,
+                    committed: conns[..idx].iter().map(|(name, _)| *name).collect(),
+                    source,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn migrate_legacy_cache_to_split(
         legacy_cache: impl AsRef<Path>,
         config_path: impl AsRef<Path>,
@@ -259,7 +571,8 @@ impl DuckDbStore {
                 &conn,
                 legacy_cache,
                 &[
-                    "INSERT INTO candles SELECT * FROM legacy.candles",
+                    "INSERT INTO candles (symbol, timestamp, open, high, low, close, volume)
+                     SELECT symbol, timestamp, open, high, low, close, volume FROM legacy.candles",
                     "INSERT INTO indicator_values SELECT * FROM legacy.indicator_values",
                 ],
             )?;
@@ -329,56 +642,38 @@ impl DuckDbStore {
         Ok(out)
     }
 
-    pub fn write_candles(&self, symbol: &str, candles: &[Candle]) -> Result<(), StoreError> {
-        if self.data_connections().count() == 0 {
-            return Err(StoreError::NoBackend);
-        }
-
+    /// Replaces the stored candles for `symbol` at `interval` (`None` means [`RAW_INTERVAL`]).
+    pub fn write_candles(
+        &self,
+        symbol: &str,
+        interval: Option<&str>,
+        candles: &[Candle],
+    ) -> Result<(), StoreError> {
+        let interval = interval.unwrap_or(RAW_INTERVAL);
         let deduped = dedup_by_timestamp(candles);
 
-        for conn in self.data_connections() {
-            // Replace any existing rows for this symbol to avoid duplicates when reloading.
-            //
-            // Use an appender for bulk insertion; row-by-row `execute` is noticeably slower
-            // for 100k+ candles and can hurt UI load times.
-            conn.execute_batch("BEGIN TRANSACTION")?;
-            let result: Result<(), StoreError> = (|| {
-                conn.execute("DELETE FROM candles WHERE symbol = ?", params![symbol])?;
-
-                let mut app = conn.appender("candles")?;
-                for candle in &deduped {
-                    let ts = candle.timestamp.format(&Rfc3339)?;
-                    app.append_row(params![
-                        symbol,
-                        ts,
-                        candle.open,
-                        candle.high,
-                        candle.low,
-                        candle.close,
-                        candle.volume
-                    ])?;
-                }
-                app.flush()?;
-
-                Ok(())
-            })();
-
-            match result {
-                Ok(()) => conn.execute_batch("COMMIT")?,
-                Err(err) => {
-                    let _ = conn.execute_batch("ROLLBACK");
-                    return Err(err);
-                }
-            }
-        }
-
-        Ok(())
+        // Replace any existing rows for this symbol to avoid duplicates when reloading.
+        //
+        // Use an appender for bulk insertion; row-by-row `execute` is noticeably slower
+        // for 100k+ candles and can hurt UI load times.
+        self.write_all_data(|conn| {
+            conn.execute(
+                "DELETE FROM candles WHERE symbol = ? AND \"interval\" = ?",
+                params![symbol, interval],
+            )?;
+            append_candle_rows(conn, symbol, interval, &deduped)
+        })
     }
 
     /// Appends candle rows for `symbol` without clearing the full symbol history.
     ///
     /// Existing rows in the appended time span are deleted first to avoid duplicate timestamps.
-    pub fn append_candles(&self, symbol: &str, candles: &[Candle]) -> Result<(), StoreError> {
+    pub fn append_candles(
+        &self,
+        symbol: &str,
+        interval: Option<&str>,
+        candles: &[Candle],
+    ) -> Result<(), StoreError> {
         if self.data_connections().count() == 0 {
             return Err(StoreError::NoBackend);
         }
@@ -386,6 +681,7 @@ impl DuckDbStore {
             return Ok(());
         }
 
+        let interval = interval.unwrap_or(RAW_INTERVAL);
         let deduped = dedup_by_timestamp(candles);
         let (min_ts, max_ts) = deduped.iter().fold(
             (deduped[0].timestamp, deduped[0].timestamp),
@@ -394,72 +690,30 @@ impl DuckDbStore {
         let min_ts = min_ts.format(&Rfc3339)?;
         let max_ts = max_ts.format(&Rfc3339)?;
 
-        for conn in self.data_connections() {
-            conn.execute_batch("BEGIN TRANSACTION")?;
-            let result: Result<(), StoreError> = (|| {
-                conn.execute(
-                    "DELETE FROM candles WHERE symbol = ? AND timestamp >= ? AND timestamp <= ?",
-                    params![symbol, min_ts, max_ts],
-                )?;
-
-                let mut app = conn.appender("candles")?;
-                for candle in &deduped {
-                    let ts = candle.timestamp.format(&Rfc3339)?;
-                    app.append_row(params![
-                        symbol,
-                        ts,
-                        candle.open,
-                        candle.high,
-                        candle.low,
-                        candle.close,
-                        candle.volume
-                    ])?;
-                }
-                app.flush()?;
-
-                Ok(())
-            })();
-
-            match result {
-                Ok(()) => conn.execute_batch("COMMIT")?,
-                Err(err) => {
-                    let _ = conn.execute_batch("ROLLBACK");
-                    return Err(err);
-                }
-            }
-        }
-
-        Ok(())
+        self.write_all_data(|conn| {
+            conn.execute(
+                "DELETE FROM candles
+                 WHERE symbol = ? AND \"interval\" = ? AND timestamp >= ? AND timestamp <= ?",
+                params![symbol, interval, min_ts, max_ts],
+            )?;
+            append_candle_rows(conn, symbol, interval, &deduped)
+        })
     }
 
     pub fn load_candles(
         &self,
         symbol: &str,
+        interval: Option<&str>,
         range: Option<DataRange>,
     ) -> Result<Vec<Candle>, StoreError> {
+        let interval = interval.unwrap_or(RAW_INTERVAL);
         let mut result = Vec::new();
         for conn in self.data_connections() {
-            let mut conditions = vec!["symbol = ?".to_string()];
-            let mut params: Vec<String> = vec![symbol.to_string()];
+            let mut conditions = vec!["symbol = ?".to_string(), "\"interval\" = ?".to_string()];
+            let mut params: Vec<String> = vec![symbol.to_string(), interval.to_string()];
 
             if let Some(range) = &range {
-                match range {
-                    DataRange::All => {}
-                    DataRange::From(start) => {
-                        conditions.push("timestamp >= ?".to_string());
-                        params.push(start.format(&Rfc3339)?);
-                    }
-                    DataRange::Until(end) => {
-                        conditions.push("timestamp <= ?".to_string());
-                        params.push(end.format(&Rfc3339)?);
-                    }
-                    DataRange::Between { start, end } => {
-                        conditions.push("timestamp >= ?".to_string());
-                        conditions.push("timestamp <= ?".to_string());
-                        params.push(start.format(&Rfc3339)?);
-                        params.push(end.format(&Rfc3339)?);
-                    }
-                }
+                push_range_conditions(range, &mut conditions, &mut params)?;
             }
 
             let query = format!(
@@ -502,6 +756,103 @@ impl DuckDbStore {
         Ok(dedup_by_timestamp(&result))
     }
 
+    /// Summarizes stored candles for `symbol` at `interval` (`None` means [`RAW_INTERVAL`]);
+    /// `None` when nothing is stored.
+    pub fn candle_coverage(
+        &self,
+        symbol: &str,
+        interval: Option<&str>,
+    ) -> Result<Option<CandleCoverage>, StoreError> {
+        let interval = interval.unwrap_or(RAW_INTERVAL);
+        let parse = |value: String| {
+            OffsetDateTime::parse(&value, &Rfc3339)
+                .map_err(|source| StoreError::TimeParse { value, source })
+        };
+        for conn in self.data_connections() {
+            let mut stmt = conn.prepare(
+                "SELECT MIN(timestamp), MAX(timestamp), COUNT(DISTINCT timestamp)
+                 FROM candles
+                 WHERE symbol = ? AND \"interval\" = ?",
+            )?;
+            let mut rows = stmt.query(params![symbol, interval])?;
+            let Some(row) = rows.next()? else {
+                continue;
+            };
+            let count: i64 = row.get(2)?;
+            if count == 0 {
+                continue;
+            }
+            let first: String = row.get(0)?;
+            let last: String = row.get(1)?;
+            return Ok(Some(CandleCoverage {
+                first: parse(first)?,
+                last: parse(last)?,
+                count: count as usize,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Aggregates raw candles of `symbol` inside `range` in SQL, without loading them.
+    pub fn symbol_stats(&self, symbol: &str, range: DataRange) -> Result<SymbolStats, StoreError> {
+        let parse = |value: String| {
+            OffsetDateTime::parse(&value, &Rfc3339)
+                .map_err(|source| StoreError::TimeParse { value, source })
+        };
+        for conn in self.data_connections() {
+            let mut conditions = vec!["symbol = ?".to_string(), "\"interval\" = ?".to_string()];
+            let mut params: Vec<String> = vec![symbol.to_string(), RAW_INTERVAL.to_string()];
+            push_range_conditions(&range, &mut conditions, &mut params)?;
+            let query = format!(
+                "SELECT COUNT(DISTINCT timestamp), MIN(timestamp), MAX(timestamp),
+                        MIN(low), MAX(high), SUM(volume)
+                 FROM candles
+                 WHERE {}",
+                conditions.join(" AND ")
+            );
+            let mut stmt = conn.prepare(&query)?;
+            let mut rows = stmt.query(params_from_iter(params))?;
+            let Some(row) = rows.next()? else {
+                continue;
+            };
+            let count: i64 = row.get(0)?;
+            if count == 0 {
+                continue;
+            }
+            let first: String = row.get(1)?;
+            let last: String = row.get(2)?;
+            return Ok(SymbolStats {
+                count: count as usize,
+                first: Some(parse(first)?),
+                last: Some(parse(last)?),
+                min_price: row.get(3)?,
+                max_price: row.get(4)?,
+                total_volume: row.get::<_, Option<f64>>(5)?.unwrap_or(0.0),
+            });
+        }
+        Ok(SymbolStats::default())
+    }
+
+    /// Removes every cached candle and indicator value, across all symbols and intervals.
+    pub fn clear_candles(&self) -> Result<(), StoreError> {
+        self.write_all_data(|conn| {
+            conn.execute_batch("DELETE FROM candles; DELETE FROM indicator_values;")?;
+            Ok(())
+        })
+    }
+
+    /// Removes cached candles (every interval) and indicator values for `symbol`.
+    pub fn delete_symbol(&self, symbol: &str) -> Result<(), StoreError> {
+        self.write_all_data(|conn| {
+            conn.execute("DELETE FROM candles WHERE symbol = ?", params![symbol])?;
+            conn.execute(
+                "DELETE FROM indicator_values WHERE symbol = ?",
+                params![symbol],
+            )?;
+            Ok(())
+        })
+    }
+
     pub fn write_indicator_values(
         &self,
         symbol: &str,
@@ -538,23 +889,7 @@ impl DuckDbStore {
             let mut params: Vec<String> = vec![symbol.to_string(), indicator.to_string()];
 
             if let Some(range) = &range {
-                match range {
-                    DataRange::All => {}
-                    DataRange::From(start) => {
-                        conditions.push("timestamp >= ?".to_string());
-                        params.push(start.format(&Rfc3339)?);
-                    }
-                    DataRange::Until(end) => {
-                        conditions.push("timestamp <= ?".to_string());
-                        params.push(end.format(&Rfc3339)?);
-                    }
-                    DataRange::Between { start, end } => {
-                        conditions.push("timestamp >= ?".to_string());
-                        conditions.push("timestamp <= ?".to_string());
-                        params.push(start.format(&Rfc3339)?);
-                        params.push(end.format(&Rfc3339)?);
-                    }
-                }
+                push_range_conditions(range, &mut conditions, &mut params)?;
             }
 
             let query = format!(
@@ -590,6 +925,7 @@ impl DuckDbStore {
         if self.config_connections().count() == 0 {
             return Err(StoreError::NoBackend);
         }
+        let key = self.session_key(key);
         for conn in self.config_connections() {
             conn.execute(
                 "INSERT INTO session_state(key, value) VALUES (?, ?)
@@ -601,9 +937,10 @@ impl DuckDbStore {
     }
 
     pub fn get_session_value(&self, key: &str) -> Result<Option<String>, StoreError> {
+        let key = self.session_key(key);
         for conn in self.config_connections() {
             let mut stmt = conn.prepare("SELECT value FROM session_state WHERE key = ? LIMIT 1")?;
-            let mut rows = stmt.query([key])?;
+            let mut rows = stmt.query([&key])?;
             if let Some(row) = rows.next()? {
                 let v: String = row.get(0)?;
                 return Ok(Some(v));
@@ -669,6 +1006,26 @@ impl DuckDbStore {
         let chunk_rep = self.get_session_value("chunk_rep")?;
         let live_source_id = self.get_session_value("live_source_id")?;
         let live_interval = self.get_session_value("live_interval")?;
+        let fast_wicks = self.get_session_value("fast_wicks")?.map(|v| v == "true");
+        let tooltip_placement = self.get_session_value("tooltip_placement")?;
+        let cache_candles = self
+            .get_session_value("cache_candles")?
+            .map(|v| v == "true");
+        let forward_fill = self.get_session_value("forward_fill")?.map(|v| v == "true");
+        let show_volume = self.get_session_value("show_volume")?.map(|v| v == "true");
+        let theme_preset = self
+            .get_session_value("theme_preset")?
+            .filter(|v| !v.is_empty());
+        let custom_intervals = self
+            .get_session_value("custom_intervals")?
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|label| !label.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
 
         Ok(UserSession {
             active_source,
@@ -686,10 +1043,61 @@ impl DuckDbStore {
             chunk_rep,
             live_source_id,
             live_interval,
+            fast_wicks,
+            tooltip_placement,
+            cache_candles,
+            forward_fill,
+            show_volume,
+            theme_preset,
+            custom_intervals,
         })
     }
 }
 
+/// Parses `name=value` pairs separated by commas, as read from [`DUCKDB_PRAGMAS_ENV`].
+fn parse_pragmas(raw: &str) -> Result<Vec<(String, String)>, StoreError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, value) = entry
+                .split_once('=')
+                .ok_or_else(|| StoreError::InvalidPragma(entry.to_string()))?;
+            validate_pragma(name, value)
+        })
+        .collect()
+}
+
+fn validate_pragma(name: &str, value: &str) -> Result<(String, String), StoreError> {
+    let name = name.trim();
+    let value = value.trim();
+    if name.is_empty()
+        || value.is_empty()
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(StoreError::InvalidPragma(format!("{name}={value}")));
+    }
+    Ok((name.to_ascii_lowercase(), value.to_string()))
+}
+
+fn apply_pragmas(conn: &Connection, pragmas: &[(String, String)]) -> Result<(), StoreError> {
+    for (name, value) in pragmas {
+        // Numbers and booleans go in bare; anything else (e.g. `2GB`) as a string literal.
+        let literal = if value.parse::<f64>().is_ok() || matches!(value.as_str(), "true" | "false")
+        {
+            value.clone()
+        } else {
+            format!("'{}'", value.replace('\'', "''"))
+        };
+        conn.execute_batch(&format!("PRAGMA {name}={literal}"))
+            .map_err(|source| StoreError::Pragma {
+                name: name.clone(),
+                source,
+            })?;
+    }
+    Ok(())
+}
+
 fn init_config_schema(conn: &Connection) -> Result<(), StoreError> {
     conn.execute_batch(
         "
@@ -715,9 +1123,9 @@ fn init_data_schema(conn: &Connection) -> Result<(), StoreError> {
             high DOUBLE NOT NULL,
             low DOUBLE NOT NULL,
             close DOUBLE NOT NULL,
-            volume DOUBLE NOT NULL
+            volume DOUBLE NOT NULL,
+            \"interval\" TEXT NOT NULL DEFAULT 'raw'
         );
-        CREATE INDEX IF NOT EXISTS idx_candles_symbol_ts ON candles(symbol, timestamp);
 
         CREATE TABLE IF NOT EXISTS indicator_values (
             symbol TEXT NOT NULL,
@@ -737,6 +1145,41 @@ fn init_data_schema(conn: &Connection) -> Result<(), StoreError> {
         );
         ",
     )?;
+    migrate_candle_intervals(conn)?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_candles_symbol_interval_ts
+         ON candles(symbol, \"interval\", timestamp);",
+    )?;
+    Ok(())
+}
+
+/// Adds the `interval` column to candle tables created before timeframes were tracked,
+/// tagging existing rows as [`RAW_INTERVAL`]. Columns left nullable by older migrations are
+/// backfilled and tightened to `NOT NULL`.
+fn migrate_candle_intervals(conn: &Connection) -> Result<(), StoreError> {
+    let mut stmt = conn.prepare(
+        "SELECT is_nullable FROM information_schema.columns
+         WHERE table_name = 'candles' AND column_name = 'interval'",
+    )?;
+    let mut rows = stmt.query([])?;
+    let is_nullable: Option<String> = rows.next()?.and_then(|row| row.get(0).ok());
+    let add_column = match is_nullable.as_deref() {
+        Some("NO") => return Ok(()),
+        Some(_) => "",
+        None => "ALTER TABLE candles ADD COLUMN \"interval\" TEXT DEFAULT 'raw';",
+    };
+
+    // DuckDB refuses to alter a table that still has dependent indexes.
+    conn.execute_batch(&format!(
+        "
+        DROP INDEX IF EXISTS idx_candles_symbol_ts;
+        DROP INDEX IF EXISTS idx_candles_symbol_interval_ts;
+        {add_column}
+        UPDATE candles SET \"interval\" = '{RAW_INTERVAL}'
+        WHERE \"interval\" IS NULL OR \"interval\" = '';
+        ALTER TABLE candles ALTER COLUMN \"interval\" SET NOT NULL;
+        "
+    ))?;
     Ok(())
 }
 
@@ -754,6 +1197,56 @@ fn attach_and_copy(
     Ok(())
 }
 
+/// Adds the `timestamp` bounds of `range` (inclusive) to a `WHERE` clause.
+fn push_range_conditions(
+    range: &DataRange,
+    conditions: &mut Vec<String>,
+    params: &mut Vec<String>,
+) -> Result<(), StoreError> {
+    match range {
+        DataRange::All => {}
+        DataRange::From(start) => {
+            conditions.push("timestamp >= ?".to_string());
+            params.push(start.format(&Rfc3339)?);
+        }
+        DataRange::Until(end) => {
+            conditions.push("timestamp <= ?".to_string());
+            params.push(end.format(&Rfc3339)?);
+        }
+        DataRange::Between { start, end } => {
+            conditions.push("timestamp >= ?".to_string());
+            conditions.push("timestamp <= ?".to_string());
+            params.push(start.format(&Rfc3339)?);
+            params.push(end.format(&Rfc3339)?);
+        }
+    }
+    Ok(())
+}
+
+fn append_candle_rows(
+    conn: &Connection,
+    symbol: &str,
+    interval: &str,
+    candles: &[Candle],
+) -> Result<(), StoreError> {
+    let mut app = conn.appender("candles")?;
+    for candle in candles {
+        let ts = candle.timestamp.format(&Rfc3339)?;
+        app.append_row(params![
+            symbol,
+            ts,
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+            interval
+        ])?;
+    }
+    app.flush()?;
+    Ok(())
+}
+
 fn dedup_by_timestamp(candles: &[Candle]) -> Vec<Candle> {
     // Keep the last occurrence for any timestamp to favor freshest data.
     let mut seen = HashSet::new();
@@ -813,7 +1306,7 @@ mod tests {
     #[test]
     fn append_candles_keeps_existing_history() {
         let store = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
-        store.write_candles("SYM", &sample_candles()).unwrap();
+        store.write_candles("SYM", None, &sample_candles()).unwrap();
 
         let appended = vec![Candle {
             timestamp: datetime!(2024-01-01 00:03:00 UTC),
@@ -823,42 +1316,240 @@ mod tests {
             close: 3.0,
             volume: 20.0,
         }];
-        store.append_candles("SYM", &appended).unwrap();
+        store.append_candles("SYM", None, &appended).unwrap();
 
-        let loaded = store.load_candles("SYM", None).unwrap();
+        let loaded = store.load_candles("SYM", None, None).unwrap();
         assert_eq!(loaded.len(), 4);
         assert_eq!(loaded.last().unwrap().close, 3.0);
     }
 
+    #[test]
+    fn failed_disk_write_rolls_back_memory() {
+        let path = temp_path();
+        let store = DuckDbStore::new(&path, StorageMode::Both).unwrap();
+        store.write_candles("SYM", None, &sample_candles()).unwrap();
+
+        // Break the disk backend so the second write fails after memory succeeded.
+        store
+            .shared_disk
+            .as_ref()
+            .unwrap()
+            .execute_batch("ALTER TABLE candles RENAME TO candles_broken")
+            .unwrap();
+
+        let err = store
+            .write_candles("SYM", None, &sample_candles()[..1])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            StoreError::WriteRolledBack {
+                backend: "disk",
+                ..
+            }
+        ));
+
+        let memory = store.data.memory.as_ref().unwrap();
+        let count: i64 = memory
+            .query_row(
+                "SELECT COUNT(*) FROM candles WHERE symbol = 'SYM'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn namespaced_stores_keep_independent_session_values() {
+        let path = temp_path();
+
+        {
+            let a = DuckDbStore::new(&path, StorageMode::Disk)
+                .unwrap()
+                .with_namespace("a");
+            a.set_session_value("active_source", "AAPL").unwrap();
+        }
+        {
+            let b = DuckDbStore::new(&path, StorageMode::Disk)
+                .unwrap()
+                .with_namespace("b");
+            assert_eq!(b.get_session_value("active_source").unwrap(), None);
+            b.set_session_value("active_source", "MSFT").unwrap();
+        }
+
+        let a = DuckDbStore::new(&path, StorageMode::Disk)
+            .unwrap()
+            .with_namespace("a");
+        assert_eq!(
+            a.get_session_value("active_source").unwrap().as_deref(),
+            Some("AAPL")
+        );
+        let session = a.load_user_session().unwrap();
+        assert_eq!(session.active_source.as_deref(), Some("AAPL"));
+        drop(a);
+
+        let b = DuckDbStore::new(&path, StorageMode::Disk)
+            .unwrap()
+            .with_namespace("b");
+        assert_eq!(
+            b.get_session_value("active_source").unwrap().as_deref(),
+            Some("MSFT")
+        );
+        drop(b);
+
+        let _ = std::fs::remove_file(path);
+    }
+
     #[test]
     fn roundtrip_memory() {
         let store = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
         let candles = sample_candles();
-        store.write_candles("SYM", &candles).unwrap();
-        let loaded = store.load_candles("SYM", None).unwrap();
+        store.write_candles("SYM", None, &candles).unwrap();
+        let loaded = store.load_candles("SYM", None, None).unwrap();
         assert_eq!(loaded.len(), candles.len());
         assert_eq!(loaded[0].open, 1.0);
         assert_eq!(loaded[1].close, 2.0);
     }
 
+    #[test]
+    fn flushed_candles_are_visible_after_reopening() {
+        let path = temp_path();
+        let store = DuckDbStore::new(&path, StorageMode::Disk).unwrap();
+        store
+            .write_candles("AAPL", None, &sample_candles())
+            .unwrap();
+        store.flush().unwrap();
+        store.close().unwrap();
+
+        let reopened = DuckDbStore::new(&path, StorageMode::Disk).unwrap();
+        let loaded = reopened.load_candles("AAPL", None, None).unwrap();
+        assert_eq!(loaded.len(), sample_candles().len());
+        assert_eq!(loaded[0].timestamp, sample_candles()[0].timestamp);
+        drop(reopened);
+        let _ = std::fs::remove_file(&path);
+
+        let memory = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
+        memory.flush().unwrap();
+    }
+
+    #[test]
+    fn pragmas_apply_and_unknown_names_error() {
+        let path = temp_path();
+        let mut store = DuckDbStore::new(&path, StorageMode::Memory).unwrap();
+        store.configure(&[("threads", "2")]).unwrap();
+        store.set_mode(StorageMode::Both).unwrap();
+
+        for conn in store.all_connections() {
+            let threads: i64 = conn
+                .query_row("SELECT current_setting('threads')", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(threads, 2);
+        }
+        let candles = sample_candles();
+        store.write_candles("PRAGMA", None, &candles).unwrap();
+        assert_eq!(
+            store.load_candles("PRAGMA", None, None).unwrap().len(),
+            candles.len()
+        );
+
+        assert!(matches!(
+            store.configure(&[("no_such_pragma", "1")]),
+            Err(StoreError::Pragma { ref name, .. }) if name == "no_such_pragma"
+        ));
+        assert!(matches!(
+            store.configure(&[("threads; DROP TABLE candles", "1")]),
+            Err(StoreError::InvalidPragma(_))
+        ));
+        assert!(parse_pragmas("threads=4, memory_limit=1GB").is_ok());
+        assert!(parse_pragmas("threads").is_err());
+        std::fs::remove_file(path).ok();
+    }
+
     #[test]
     fn switching_modes_keeps_working() {
         let path = temp_path();
         let mut store = DuckDbStore::new(&path, StorageMode::Memory).unwrap();
         store.set_mode(StorageMode::Both).unwrap();
         let candles = sample_candles();
-        store.write_candles("SWITCH", &candles).unwrap();
-        let loaded = store.load_candles("SWITCH", None).unwrap();
+        store.write_candles("SWITCH", None, &candles).unwrap();
+        let loaded = store.load_candles("SWITCH", None, None).unwrap();
         assert_eq!(loaded.len(), candles.len());
         std::fs::remove_file(path).ok();
     }
 
+    #[test]
+    fn switching_disk_to_memory_carries_candles_over() {
+        let path = temp_path();
+        let mut store = DuckDbStore::new(&path, StorageMode::Disk).unwrap();
+        let candles = sample_candles();
+        store.write_candles("MIGRATE", None, &candles).unwrap();
+        store
+            .write_candles("MIGRATE", Some("1m"), &candles[..1])
+            .unwrap();
+
+        store.set_mode(StorageMode::Memory).unwrap();
+        assert_eq!(store.mode(), StorageMode::Memory);
+        assert!(store.data.disk.is_none() && store.shared_disk.is_none());
+        assert_eq!(store.load_candles("MIGRATE", None, None).unwrap(), candles);
+        assert_eq!(
+            store.load_candles("MIGRATE", Some("1m"), None).unwrap(),
+            candles[..1]
+        );
+
+        // Writes now stay in memory; the disk file keeps only what was there before.
+        store.write_candles("FRESH", None, &candles).unwrap();
+        drop(store);
+        let reopened = DuckDbStore::new(&path, StorageMode::Disk).unwrap();
+        assert_eq!(
+            reopened.load_candles("MIGRATE", None, None).unwrap(),
+            candles
+        );
+        assert!(
+            reopened
+                .load_candles("FRESH", None, None)
+                .unwrap()
+                .is_empty()
+        );
+        drop(reopened);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn switching_mode_carries_session_and_restores_on_failure() {
+        let path = temp_path();
+        let mut store = DuckDbStore::new(&path, StorageMode::Memory).unwrap();
+        store
+            .write_candles("KEEP", None, &sample_candles())
+            .unwrap();
+        store.set_session_value("interval", "5m").unwrap();
+        store.set_watchlist(&["KEEP".to_string()]).unwrap();
+
+        // The disk file can't be created, so the switch fails and memory comes back intact.
+        store.set_disk_path(std::env::temp_dir().join("gpui-kbar-missing-dir/store.duckdb"));
+        assert!(store.set_mode(StorageMode::Disk).is_err());
+        assert_eq!(store.mode(), StorageMode::Memory);
+        assert_eq!(store.load_candles("KEEP", None, None).unwrap().len(), 2);
+
+        store.set_disk_path(&path);
+        store.set_mode(StorageMode::Disk).unwrap();
+        assert_eq!(
+            store.get_session_value("interval").unwrap().as_deref(),
+            Some("5m")
+        );
+        assert_eq!(store.get_watchlist().unwrap(), vec!["KEEP".to_string()]);
+        assert_eq!(store.load_candles("KEEP", None, None).unwrap().len(), 2);
+        drop(store);
+        std::fs::remove_file(path).ok();
+    }
+
     #[test]
     fn roundtrip_disk_and_indicators() {
         let path = temp_path();
         let store = DuckDbStore::new(&path, StorageMode::Disk).unwrap();
         let candles = sample_candles();
-        store.write_candles("ABC", &candles).unwrap();
+        store.write_candles("ABC", None, &candles).unwrap();
 
         let indicator_points = vec![
             (datetime!(2024-01-01 00:00:00 UTC), 10.0),
@@ -869,7 +1560,7 @@ mod tests {
             .write_indicator_values("ABC", "SMA", &indicator_points)
             .unwrap();
 
-        let loaded = store.load_candles("ABC", None).unwrap();
+        let loaded = store.load_candles("ABC", None, None).unwrap();
         assert_eq!(loaded.len(), 3);
         let loaded_indicators = store.load_indicator_values("ABC", "SMA", None).unwrap();
         assert_eq!(loaded_indicators.len(), 3);
@@ -886,15 +1577,154 @@ mod tests {
         std::fs::remove_file(path).ok();
     }
 
+    #[test]
+    fn intervals_are_stored_separately() {
+        let store = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
+        let seconds = sample_candles();
+        let minutes = &sample_candles()[..1];
+        store.write_candles("IVL", Some("1s"), &seconds).unwrap();
+        store.write_candles("IVL", Some("1m"), minutes).unwrap();
+
+        let loaded_seconds = store.load_candles("IVL", Some("1s"), None).unwrap();
+        let loaded_minutes = store.load_candles("IVL", Some("1m"), None).unwrap();
+        assert_eq!(loaded_seconds.len(), seconds.len());
+        assert_eq!(loaded_minutes.len(), 1);
+        assert!(store.load_candles("IVL", None, None).unwrap().is_empty());
+
+        store.write_candles("IVL", Some("1m"), &[]).unwrap();
+        assert_eq!(
+            store.load_candles("IVL", Some("1s"), None).unwrap().len(),
+            seconds.len()
+        );
+    }
+
+    #[test]
+    fn legacy_candles_table_migrates_to_raw_interval() {
+        let path = temp_path();
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "
+                CREATE TABLE candles (
+                    symbol TEXT NOT NULL,
+                    timestamp TEXT NOT NULL,
+                    open DOUBLE NOT NULL,
+                    high DOUBLE NOT NULL,
+                    low DOUBLE NOT NULL,
+                    close DOUBLE NOT NULL,
+                    volume DOUBLE NOT NULL
+                );
+                CREATE INDEX idx_candles_symbol_ts ON candles(symbol, timestamp);
+                INSERT INTO candles VALUES ('OLD', '2024-01-01T00:00:00Z', 1, 2, 0.5, 1.5, 10);
+                ",
+            )
+            .unwrap();
+        }
+
+        let store = DuckDbStore::new(&path, StorageMode::Disk).unwrap();
+        assert_eq!(store.load_candles("OLD", None, None).unwrap().len(), 1);
+        store
+            .append_candles("OLD", Some("1m"), &sample_candles())
+            .unwrap();
+        assert_eq!(store.load_candles("OLD", None, None).unwrap().len(), 1);
+        let nullable: String = store
+            .shared_disk
+            .as_ref()
+            .unwrap()
+            .query_row(
+                "SELECT is_nullable FROM information_schema.columns
+                 WHERE table_name = 'candles' AND column_name = 'interval'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(nullable, "NO");
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn delete_symbol_and_clear_candles_remove_rows() {
+        let store = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
+        store
+            .write_candles("KEEP", None, &sample_candles())
+            .unwrap();
+        store
+            .write_candles("DROP", None, &sample_candles())
+            .unwrap();
+        store
+            .write_candles("DROP", Some("1m"), &sample_candles())
+            .unwrap();
+
+        store.delete_symbol("DROP").unwrap();
+        assert!(store.load_candles("DROP", None, None).unwrap().is_empty());
+        assert!(
+            store
+                .load_candles("DROP", Some("1m"), None)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(store.load_candles("KEEP", None, None).unwrap().len(), 2);
+
+        store.clear_candles().unwrap();
+        assert!(store.load_candles("KEEP", None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn candle_coverage_reports_stored_span() {
+        let store = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
+        assert_eq!(store.candle_coverage("COV", None).unwrap(), None);
+
+        let candles = sample_candles();
+        store.write_candles("COV", None, &candles).unwrap();
+        let coverage = store.candle_coverage("COV", None).unwrap().unwrap();
+        assert_eq!(coverage.first, candles[0].timestamp);
+        assert_eq!(coverage.last, candles.last().unwrap().timestamp);
+        assert_eq!(coverage.count, candles.len());
+        assert_eq!(store.candle_coverage("COV", Some("1m")).unwrap(), None);
+    }
+
+    #[test]
+    fn symbol_stats_aggregate_in_sql() {
+        let store = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
+        let candles = sample_candles();
+        store.write_candles("STATS", None, &candles).unwrap();
+
+        let stats = store.symbol_stats("STATS", DataRange::All).unwrap();
+        assert_eq!(
+            stats,
+            SymbolStats {
+                count: 3,
+                first: Some(candles[0].timestamp),
+                last: Some(candles[2].timestamp),
+                min_price: Some(0.5),
+                max_price: Some(3.0),
+                total_volume: 37.0,
+            }
+        );
+
+        let tail = store
+            .symbol_stats("STATS", DataRange::From(datetime!(2024-01-01 00:01:00 UTC)))
+            .unwrap();
+        assert_eq!(tail.count, 2);
+        assert_eq!(tail.first, Some(candles[1].timestamp));
+        assert_eq!(tail.min_price, Some(1.0));
+        assert_eq!(tail.total_volume, 27.0);
+
+        let missing = store.symbol_stats("NONE", DataRange::All).unwrap();
+        assert_eq!(missing, SymbolStats::default());
+    }
+
     #[test]
     fn range_filters() {
         let store = DuckDbStore::new(temp_path(), StorageMode::Memory).unwrap();
         let candles = sample_candles();
-        store.write_candles("RANGE", &candles).unwrap();
+        store.write_candles("RANGE", None, &candles).unwrap();
 
         let from = store
             .load_candles(
                 "RANGE",
+                None,
                 Some(DataRange::From(datetime!(2024-01-01 00:01:00 UTC))),
             )
             .unwrap();
@@ -903,6 +1733,7 @@ mod tests {
         let until = store
             .load_candles(
                 "RANGE",
+                None,
                 Some(DataRange::Until(datetime!(2024-01-01 00:01:00 UTC))),
             )
             .unwrap();
@@ -911,6 +1742,7 @@ mod tests {
         let between = store
             .load_candles(
                 "RANGE",
+                None,
                 Some(DataRange::Between {
                     start: datetime!(2024-01-01 00:01:00 UTC),
                     end: datetime!(2024-01-01 00:02:00 UTC),
@@ -928,10 +1760,10 @@ mod tests {
         newer.open = 9.99;
         candles.push(newer);
 
-        store.write_candles("DUP", &candles).unwrap();
-        store.write_candles("DUP", &candles).unwrap();
+        store.write_candles("DUP", None, &candles).unwrap();
+        store.write_candles("DUP", None, &candles).unwrap();
 
-        let loaded = store.load_candles("DUP", None).unwrap();
+        let loaded = store.load_candles("DUP", None, None).unwrap();
         // Duplicates for the same timestamp are removed and the newest value wins.
         assert_eq!(loaded.len(), sample_candles().len());
         assert_eq!(loaded[0].timestamp, candles[0].timestamp);
@@ -975,6 +1807,24 @@ mod tests {
         store
             .set_session_value("live_interval", "1s")
             .expect("live_interval");
+        store
+            .set_session_value("fast_wicks", "false")
+            .expect("fast_wicks");
+        store
+            .set_session_value("tooltip_placement", "corner")
+            .expect("tooltip_placement");
+        store
+            .set_session_value("cache_candles", "false")
+            .expect("cache_candles");
+        store
+            .set_session_value("show_volume", "false")
+            .expect("show_volume");
+        store
+            .set_session_value("theme_preset", "Solarized")
+            .expect("theme_preset");
+        store
+            .set_session_value("custom_intervals", "2h, 4h")
+            .expect("custom_intervals");
         store
             .set_watchlist(&["TSLA".to_string(), "AAPL".to_string()])
             .expect("watchlist");
@@ -1008,5 +1858,11 @@ mod tests {
         assert_eq!(session.chunk_rep.as_deref(), Some("tcp://127.0.0.1:5557"));
         assert_eq!(session.live_source_id.as_deref(), Some("SIM"));
         assert_eq!(session.live_interval.as_deref(), Some("1s"));
+        assert_eq!(session.fast_wicks, Some(false));
+        assert_eq!(session.tooltip_placement.as_deref(), Some("corner"));
+        assert_eq!(session.cache_candles, Some(false));
+        assert_eq!(session.show_volume, Some(false));
+        assert_eq!(session.theme_preset.as_deref(), Some("Solarized"));
+        assert_eq!(session.custom_intervals, vec!["2h", "4h"]);
     }
 }
@@ -1,5 +1,10 @@
+use std::fmt;
+use std::str::FromStr;
+
 use time::{Duration, OffsetDateTime};
 
+use crate::{IntervalParseError, InvalidTimestamp};
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Candle {
     pub timestamp: OffsetDateTime,
@@ -10,6 +15,69 @@ pub struct Candle {
     pub volume: f64,
 }
 
+impl Candle {
+    pub fn new(
+        timestamp: OffsetDateTime,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Self {
+        Self {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+
+    /// Builds a candle from a unix-millisecond timestamp, as carried on the wire.
+    pub fn from_ts_ms(
+        ts_ms: i64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Result<Self, InvalidTimestamp> {
+        let timestamp = OffsetDateTime::from_unix_timestamp_nanos(ts_ms as i128 * 1_000_000)
+            .map_err(|_| InvalidTimestamp(ts_ms))?;
+        Ok(Self::new(timestamp, open, high, low, close, volume))
+    }
+
+    /// Timestamp as unix milliseconds (sub-millisecond precision is truncated).
+    pub fn ts_ms(&self) -> i64 {
+        (self.timestamp.unix_timestamp_nanos() / 1_000_000) as i64
+    }
+
+    /// Same timestamp and every price/volume field within `epsilon` of `other`.
+    pub fn approx_eq(&self, other: &Candle, epsilon: f64) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() <= epsilon;
+        self.timestamp == other.timestamp
+            && close(self.open, other.open)
+            && close(self.high, other.high)
+            && close(self.low, other.low)
+            && close(self.close, other.close)
+            && close(self.volume, other.volume)
+    }
+}
+
+/// A candle plus the quote columns some sources carry alongside OHLCV; see
+/// [`load_csv_ext`](crate::load_csv_ext).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleExt {
+    pub candle: Candle,
+    /// `None` when the source has no bid column or the cell is null.
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+}
+
+/// Source column read for each candle field. Blank entries fall back to the canonical
+/// (default) name; a missing column is reported under the field name, e.g. `volume` for a
+/// `v` mapping.
 #[derive(Debug, Clone)]
 pub struct ColumnMapping {
     pub timestamp: String,
@@ -18,6 +86,9 @@ pub struct ColumnMapping {
     pub low: String,
     pub close: String,
     pub volume: String,
+    /// Optional quote columns, read only by the `_ext` loaders when present.
+    pub bid: String,
+    pub ask: String,
 }
 
 impl Default for ColumnMapping {
@@ -29,13 +100,105 @@ impl Default for ColumnMapping {
             low: "low".into(),
             close: "close".into(),
             volume: "volume".into(),
+            bid: "bid".into(),
+            ask: "ask".into(),
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+impl ColumnMapping {
+    /// Copy with blank entries replaced by the canonical names.
+    pub(crate) fn or_canonical(&self) -> Self {
+        let canonical = Self::default();
+        let pick = |name: &String, fallback: String| {
+            if name.trim().is_empty() {
+                fallback
+            } else {
+                name.clone()
+            }
+        };
+        Self {
+            timestamp: pick(&self.timestamp, canonical.timestamp),
+            open: pick(&self.open, canonical.open),
+            high: pick(&self.high, canonical.high),
+            low: pick(&self.low, canonical.low),
+            close: pick(&self.close, canonical.close),
+            volume: pick(&self.volume, canonical.volume),
+            bid: pick(&self.bid, canonical.bid),
+            ask: pick(&self.ask, canonical.ask),
+        }
+    }
+}
+
+/// What to do with rows whose open/high/low/close is null (or a configured null value).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullPolicy {
+    /// Fail with `LoadError::InvalidNumber`.
+    #[default]
+    Error,
+    /// Drop the row.
+    Skip,
+    /// Reuse the previous row's value for that column; leading rows that cannot be filled
+    /// are dropped.
+    ForwardFill,
+}
+
+#[derive(Debug, Clone)]
 pub struct LoadOptions {
     pub columns: ColumnMapping,
+    /// Only read the first N rows (handy for previews); `None` reads everything.
+    pub limit: Option<usize>,
+    /// CSV cell values read as null. `"NaN"` also marks float NaNs as null for any source.
+    pub null_values: Vec<String>,
+    /// Applied to price columns; null volume is always read as `0.0`.
+    pub null_policy: NullPolicy,
+    /// Row order after loading; applied after `limit`.
+    pub sort: LoadSort,
+    /// Load files without a volume column (FX, indices) with every volume `0.0` instead of
+    /// failing with [`LoadError::MissingColumn`](crate::LoadError::MissingColumn).
+    pub volume_optional: bool,
+    /// Match `columns` names exactly. Off by default, where a header matches when it equals
+    /// the mapped name after trimming whitespace and ignoring case (` Open`, `HIGH `); an
+    /// exact match still wins when both exist.
+    pub exact_column_names: bool,
+    /// Read `NaN` and `±inf` prices and volumes as null, whatever `null_values` says, so
+    /// they go through `null_policy` instead of reaching charts and indicators.
+    pub non_finite_is_null: bool,
+    /// CSV field separator, e.g. `b';'` for many European exchange exports or `b'\t'`.
+    pub delimiter: u8,
+    /// CSV quote character; `None` reads quotes as literal text.
+    pub quote: Option<u8>,
+}
+
+/// Ordering of loaded rows. Sorting is stable, so rows that tie on every key keep their
+/// file order; consumers that dedup by timestamp then see a deterministic "last" row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LoadSort {
+    /// Rows as they appear in the source.
+    #[default]
+    FileOrder,
+    /// Ascending timestamp.
+    Timestamp,
+    /// Ascending timestamp, then ascending values of the named column (e.g. a sequence
+    /// number) for rows sharing a timestamp.
+    TimestampThen(String),
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            columns: ColumnMapping::default(),
+            limit: None,
+            null_values: vec!["".into(), "NaN".into(), "null".into()],
+            null_policy: NullPolicy::default(),
+            sort: LoadSort::default(),
+            volume_optional: false,
+            exact_column_names: false,
+            non_finite_is_null: true,
+            delimiter: b',',
+            quote: Some(b'"'),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -56,3 +219,46 @@ impl Interval {
         }
     }
 }
+
+/// Formats as the parser's input form (`"15s"`, `"5m"`, ...), so `to_string` round-trips
+/// through [`FromStr`].
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Interval::Second(n) => write!(f, "{n}s"),
+            Interval::Minute(n) => write!(f, "{n}m"),
+            Interval::Hour(n) => write!(f, "{n}h"),
+            Interval::Day(n) => write!(f, "{n}d"),
+        }
+    }
+}
+
+/// Parses labels like `"15s"`, `"5m"`, `"1h"` or `"1d"`.
+impl FromStr for Interval {
+    type Err = IntervalParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some(unit) = s.chars().last() else {
+            return Err(IntervalParseError::Empty);
+        };
+        let make: fn(u32) -> Interval = match unit {
+            's' => Interval::Second,
+            'm' => Interval::Minute,
+            'h' => Interval::Hour,
+            'd' => Interval::Day,
+            _ => return Err(IntervalParseError::UnknownUnit(s.to_string())),
+        };
+        let amount = &s[..s.len() - 1];
+        if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(IntervalParseError::InvalidAmount(s.to_string()));
+        }
+        let n: u32 = amount
+            .parse()
+            .map_err(|_| IntervalParseError::Overflow(s.to_string()))?;
+        if n == 0 {
+            return Err(IntervalParseError::ZeroAmount);
+        }
+        Ok(make(n))
+    }
+}
@@ -47,6 +47,7 @@ fn main() {
     let meta = ChartMeta {
         source: perf_source(spec),
         initial_interval: None,
+        initial_visible: None,
     };
 
     launch_chart(Ok(candles), meta);
@@ -14,8 +14,9 @@ use gpui::{
     WindowBounds, WindowOptions, div, prelude::*, px, rgb, size,
 };
 use gpui_component::scroll::ScrollableElement;
-use kbar_core::{DuckDbStore, StorageMode, UniverseRow};
+use kbar_core::{DuckDbStore, Interval, StorageMode, UniverseRow};
 use tokio::sync::{RwLock, watch};
+use ui::wire::{CandleWire, encode_candles};
 use zeromq::{Socket, SocketRecv, SocketSend};
 
 use ui::application_with_assets;
@@ -50,16 +51,6 @@ impl StreamKeyOwned {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct CandleWire {
-    ts_ms: i64,
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
-    volume: f64,
-}
-
 #[derive(Debug)]
 struct StreamState {
     next_sequence: u64,
@@ -91,6 +82,10 @@ struct ServerHandle {
     shutdown_tx: watch::Sender<bool>,
     published_candles: Arc<AtomicU64>,
     stored_candles: Arc<AtomicU64>,
+    /// Candles withheld from PUB by `drop_percent`; still served by backfill.
+    dropped_candles: Arc<AtomicU64>,
+    /// Candles withheld from PUB by `gap_every`; still served by backfill.
+    gapped_candles: Arc<AtomicU64>,
 }
 
 struct DevServerView {
@@ -172,6 +167,8 @@ impl DevServerView {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
         let published_candles = Arc::new(AtomicU64::new(0));
         let stored_candles = Arc::new(AtomicU64::new(0));
+        let dropped_candles = Arc::new(AtomicU64::new(0));
+        let gapped_candles = Arc::new(AtomicU64::new(0));
 
         let streams = Arc::new(RwLock::new(HashMap::<StreamKeyOwned, StreamState>::new()));
 
@@ -180,6 +177,8 @@ impl DevServerView {
         let streams_for_pub = streams.clone();
         let published_for_tasks = published_candles.clone();
         let stored_for_tasks = stored_candles.clone();
+        let dropped_for_tasks = dropped_candles.clone();
+        let gapped_for_tasks = gapped_candles.clone();
 
         tokio_runtime().spawn(async move {
             let _ = tokio::join!(
@@ -190,6 +189,8 @@ impl DevServerView {
                     shutdown_rx,
                     published_for_tasks,
                     stored_for_tasks,
+                    dropped_for_tasks,
+                    gapped_for_tasks,
                 ),
             );
         });
@@ -198,6 +199,8 @@ impl DevServerView {
             shutdown_tx,
             published_candles,
             stored_candles,
+            dropped_candles,
+            gapped_candles,
         });
         self.running = true;
         self.status = SharedString::from(format!(
@@ -335,16 +338,18 @@ impl Render for DevServerView {
             window.refresh();
         });
 
-        let (pub_count, stored_count) = self
+        let (pub_count, stored_count, dropped_count, gapped_count) = self
             .server
             .as_ref()
             .map(|s| {
                 (
                     s.published_candles.load(Ordering::Relaxed),
                     s.stored_candles.load(Ordering::Relaxed),
+                    s.dropped_candles.load(Ordering::Relaxed),
+                    s.gapped_candles.load(Ordering::Relaxed),
                 )
             })
-            .unwrap_or((0, 0));
+            .unwrap_or((0, 0, 0, 0));
 
         let header = div()
             .flex()
@@ -401,12 +406,10 @@ impl Render for DevServerView {
                             .id("dev-server-stop"),
                         0x111827,
                     ))
-                    .child(
-                        div()
-                            .text_xs()
-                            .text_color(rgb(0x9ca3af))
-                            .child(format!("published={pub_count} stored={stored_count}")),
-                    ),
+                    .child(div().text_xs().text_color(rgb(0x9ca3af)).child(format!(
+                        "published={pub_count} stored={stored_count} \
+                                 dropped={dropped_count} gapped={gapped_count}"
+                    ))),
             );
 
         let cfg_row = |label: &'static str, value: String| {
@@ -1018,21 +1021,7 @@ fn encode_candle_batch(
     let mut fbb = flatbuffers::FlatBufferBuilder::new();
     let key = build_stream_key(&mut fbb, key);
 
-    let mut candle_offsets = Vec::with_capacity(candles.len());
-    for candle in candles {
-        candle_offsets.push(fb::Candle::create(
-            &mut fbb,
-            &fb::CandleArgs {
-                ts_ms: candle.ts_ms,
-                open: candle.open,
-                high: candle.high,
-                low: candle.low,
-                close: candle.close,
-                volume: candle.volume,
-            },
-        ));
-    }
-    let candle_vec = fbb.create_vector(&candle_offsets);
+    let candle_vec = encode_candles(&mut fbb, candles.iter().copied());
     let batch = fb::CandleBatch::create(
         &mut fbb,
         &fb::CandleBatchArgs {
@@ -1065,21 +1054,7 @@ fn encode_backfill_response(
     let mut fbb = flatbuffers::FlatBufferBuilder::new();
     let key = build_stream_key(&mut fbb, key);
 
-    let mut candle_offsets = Vec::with_capacity(candles.len());
-    for candle in candles {
-        candle_offsets.push(fb::Candle::create(
-            &mut fbb,
-            &fb::CandleArgs {
-                ts_ms: candle.ts_ms,
-                open: candle.open,
-                high: candle.high,
-                low: candle.low,
-                close: candle.close,
-                volume: candle.volume,
-            },
-        ));
-    }
-    let candle_vec = fbb.create_vector(&candle_offsets);
+    let candle_vec = encode_candles(&mut fbb, candles.iter().copied());
     let resp = fb::BackfillCandlesResponse::create(
         &mut fbb,
         &fb::BackfillCandlesResponseArgs {
@@ -1139,20 +1114,13 @@ fn encode_get_cursor_response(
     fbb.finished_data().to_vec()
 }
 
-fn parse_interval_ms(interval: &str) -> i64 {
-    if interval.len() < 2 {
-        return 1_000;
-    }
-    let (num, unit) = interval.split_at(interval.len() - 1);
-    let n: i64 = num.parse().unwrap_or(1);
-    let unit_ms = match unit {
-        "s" => 1_000,
-        "m" => 60_000,
-        "h" => 3_600_000,
-        "d" => 86_400_000,
-        _ => 1_000,
-    };
-    n.saturating_mul(unit_ms).max(1)
+fn parse_interval_ms(interval: &str) -> Option<i64> {
+    let ms = interval
+        .parse::<Interval>()
+        .ok()?
+        .as_duration()
+        .whole_milliseconds();
+    i64::try_from(ms).ok().filter(|ms| *ms > 0)
 }
 
 async fn rep_task(
@@ -1323,6 +1291,8 @@ async fn pub_task(
     mut shutdown: watch::Receiver<bool>,
     published_candles: Arc<AtomicU64>,
     stored_candles: Arc<AtomicU64>,
+    dropped_candles: Arc<AtomicU64>,
+    gapped_candles: Arc<AtomicU64>,
 ) {
     let mut pub_socket = zeromq::PubSocket::new();
     if let Err(err) = pub_socket.bind(&cfg.live_pub).await {
@@ -1331,7 +1301,10 @@ async fn pub_task(
     }
 
     let mut rng_state: u64 = 0x726f6f742d666c75;
-    let interval_ms = parse_interval_ms(&cfg.interval);
+    let Some(interval_ms) = parse_interval_ms(&cfg.interval) else {
+        eprintln!("invalid interval '{}'", cfg.interval);
+        return;
+    };
 
     let now_ms = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -1372,10 +1345,6 @@ async fn pub_task(
             .collect();
 
         for key in keys {
-            let mut runs: Vec<(u64, Vec<CandleWire>)> = Vec::new();
-            let mut run_start: Option<u64> = None;
-            let mut run_candles: Vec<CandleWire> = Vec::new();
-
             let mut guard = streams.write().await;
             let state = guard.entry(key.clone()).or_insert_with(|| StreamState {
                 next_sequence: 1,
@@ -1383,58 +1352,15 @@ async fn pub_task(
                 last_close: 100.0,
                 history: Vec::new(),
             });
-
-            for _ in 0..cfg.batch_size {
-                rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-                let r = (rng_state >> 33) as u32;
-
-                let open = state.last_close;
-                let delta = ((r as f64 / (u32::MAX as f64)) - 0.5) * 0.8;
-                let close = (open + delta).max(0.01);
-                let high = open.max(close) + ((r as f64 / (u32::MAX as f64)) * 0.4);
-                let low = open.min(close) - ((r as f64 / (u32::MAX as f64)) * 0.4);
-                let volume = (((r as f64 / (u32::MAX as f64)) * 1500.0).max(1.0)).round();
-
-                let candle = CandleWire {
-                    ts_ms: state.next_ts_ms,
-                    open,
-                    high,
-                    low,
-                    close,
-                    volume,
-                };
-                state.history.push(candle);
-                stored_candles.fetch_add(1, Ordering::Relaxed);
-
-                let seq = state.next_sequence;
-                state.next_sequence = state.next_sequence.saturating_add(1);
-                state.next_ts_ms = state.next_ts_ms.saturating_add(interval_ms);
-                state.last_close = close;
-
-                let dropped =
-                    cfg.fault.drop_percent > 0 && (r % 100) < (cfg.fault.drop_percent as u32);
-                let gapped = cfg.fault.gap_every > 0 && (seq % cfg.fault.gap_every == 0);
-
-                if dropped || gapped {
-                    if let Some(start) = run_start.take()
-                        && !run_candles.is_empty()
-                    {
-                        runs.push((start, std::mem::take(&mut run_candles)));
-                    }
-                    continue;
-                }
-
-                if run_start.is_none() {
-                    run_start = Some(seq);
-                }
-                run_candles.push(candle);
-            }
-
-            if let Some(start) = run_start.take()
-                && !run_candles.is_empty()
-            {
-                runs.push((start, run_candles));
-            }
+            let runs = generate_runs(
+                &cfg,
+                state,
+                interval_ms,
+                &mut rng_state,
+                &stored_candles,
+                &dropped_candles,
+                &gapped_candles,
+            );
             drop(guard);
 
             for (start_sequence, candles) in runs {
@@ -1448,6 +1374,79 @@ async fn pub_task(
     }
 }
 
+/// Appends `cfg.batch_size` synthetic candles to `state.history` and returns the contiguous
+/// runs to publish; candles withheld by fault injection are counted as gapped or dropped.
+fn generate_runs(
+    cfg: &RunConfig,
+    state: &mut StreamState,
+    interval_ms: i64,
+    rng_state: &mut u64,
+    stored_candles: &AtomicU64,
+    dropped_candles: &AtomicU64,
+    gapped_candles: &AtomicU64,
+) -> Vec<(u64, Vec<CandleWire>)> {
+    let mut runs: Vec<(u64, Vec<CandleWire>)> = Vec::new();
+    let mut run_start: Option<u64> = None;
+    let mut run_candles: Vec<CandleWire> = Vec::new();
+
+    for _ in 0..cfg.batch_size {
+        *rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        let r = (*rng_state >> 33) as u32;
+
+        let open = state.last_close;
+        let delta = ((r as f64 / (u32::MAX as f64)) - 0.5) * 0.8;
+        let close = (open + delta).max(0.01);
+        let high = open.max(close) + ((r as f64 / (u32::MAX as f64)) * 0.4);
+        let low = open.min(close) - ((r as f64 / (u32::MAX as f64)) * 0.4);
+        let volume = (((r as f64 / (u32::MAX as f64)) * 1500.0).max(1.0)).round();
+
+        let candle = CandleWire {
+            ts_ms: state.next_ts_ms,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        };
+        state.history.push(candle);
+        stored_candles.fetch_add(1, Ordering::Relaxed);
+
+        let seq = state.next_sequence;
+        state.next_sequence = state.next_sequence.saturating_add(1);
+        state.next_ts_ms = state.next_ts_ms.saturating_add(interval_ms);
+        state.last_close = close;
+
+        let dropped = cfg.fault.drop_percent > 0 && (r % 100) < (cfg.fault.drop_percent as u32);
+        let gapped = cfg.fault.gap_every > 0 && (seq % cfg.fault.gap_every == 0);
+
+        if gapped {
+            gapped_candles.fetch_add(1, Ordering::Relaxed);
+        } else if dropped {
+            dropped_candles.fetch_add(1, Ordering::Relaxed);
+        }
+        if dropped || gapped {
+            if let Some(start) = run_start.take()
+                && !run_candles.is_empty()
+            {
+                runs.push((start, std::mem::take(&mut run_candles)));
+            }
+            continue;
+        }
+
+        if run_start.is_none() {
+            run_start = Some(seq);
+        }
+        run_candles.push(candle);
+    }
+
+    if let Some(start) = run_start.take()
+        && !run_candles.is_empty()
+    {
+        runs.push((start, run_candles));
+    }
+    runs
+}
+
 fn main() {
     application_with_assets().run(|cx: &mut App| {
         gpui_component::theme::init(cx);
@@ -1465,3 +1464,70 @@ fn main() {
         cx.activate(true);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_config(drop_percent: u8, gap_every: u64) -> RunConfig {
+        RunConfig {
+            live_pub: String::new(),
+            chunk_rep: String::new(),
+            source_id: "SIM".to_string(),
+            interval: "1s".to_string(),
+            tick_ms: 1,
+            batch_size: 200,
+            symbols: vec!["AAA".to_string()],
+            fault: FaultConfig {
+                drop_percent,
+                gap_every,
+                jitter_ms: 0,
+            },
+        }
+    }
+
+    fn generate(cfg: &RunConfig) -> (u64, u64, u64, usize) {
+        let mut state = StreamState {
+            next_sequence: 1,
+            next_ts_ms: 0,
+            last_close: 100.0,
+            history: Vec::new(),
+        };
+        let (stored, dropped, gapped) = (AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0));
+        let runs = generate_runs(
+            cfg,
+            &mut state,
+            1_000,
+            &mut 0x726f6f742d666c75,
+            &stored,
+            &dropped,
+            &gapped,
+        );
+        let published = runs.iter().map(|(_, candles)| candles.len()).sum();
+        (
+            stored.into_inner(),
+            dropped.into_inner(),
+            gapped.into_inner(),
+            published,
+        )
+    }
+
+    #[test]
+    fn drop_percent_increments_dropped_counter() {
+        let (stored, dropped, gapped, published) = generate(&run_config(30, 0));
+        assert_eq!(stored, 200);
+        assert!(dropped > 0);
+        assert_eq!(gapped, 0);
+        assert_eq!(published as u64 + dropped, stored);
+
+        let (_, dropped, _, published) = generate(&run_config(0, 0));
+        assert_eq!((dropped, published), (0, 200));
+    }
+
+    #[test]
+    fn gap_every_counts_gapped_candles_separately() {
+        let (stored, dropped, gapped, published) = generate(&run_config(30, 10));
+        assert_eq!(gapped, 20);
+        assert_eq!(published as u64 + dropped + gapped, stored);
+    }
+}
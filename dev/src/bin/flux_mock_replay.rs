@@ -4,8 +4,8 @@ use anyhow::{Context as _, Result};
 use clap::Parser;
 use flux_schema::{WIRE_SCHEMA_VERSION, fb};
 use kbar_core::{Candle, LoadOptions, load_csv};
-use time::format_description::well_known::Rfc3339;
 use tokio::time::{Duration, interval};
+use ui::wire::{CandleWire, encode_candles};
 use zeromq::{Socket, SocketRecv, SocketSend};
 
 #[derive(Debug, Clone)]
@@ -61,68 +61,11 @@ struct Args {
 }
 
 fn load_candles_for_mock(path: &PathBuf, limit: usize) -> Result<Vec<Candle>> {
-    if limit == 0 {
-        return load_csv(path, LoadOptions::default())
-            .with_context(|| format!("load candles from {}", path.display()));
-    }
-
-    let mut reader = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .from_path(path)
-        .with_context(|| format!("open csv {}", path.display()))?;
-    let headers = reader.headers().context("read csv headers")?.clone();
-
-    let idx = |name: &str| -> Result<usize> {
-        headers
-            .iter()
-            .position(|h| h == name)
-            .with_context(|| format!("missing column {name}"))
+    let options = LoadOptions {
+        limit: (limit > 0).then_some(limit),
+        ..Default::default()
     };
-
-    let ts_idx = idx("timestamp")?;
-    let open_idx = idx("open")?;
-    let high_idx = idx("high")?;
-    let low_idx = idx("low")?;
-    let close_idx = idx("close")?;
-    let volume_idx = idx("volume")?;
-
-    let mut out = Vec::with_capacity(limit);
-    for record in reader.records().take(limit) {
-        let record = record.context("read csv record")?;
-        let ts = record.get(ts_idx).unwrap_or("");
-        let timestamp = time::OffsetDateTime::parse(ts, &Rfc3339)
-            .with_context(|| format!("parse timestamp {ts}"))?;
-        out.push(Candle {
-            timestamp,
-            open: record
-                .get(open_idx)
-                .unwrap_or("")
-                .parse()
-                .context("parse open")?,
-            high: record
-                .get(high_idx)
-                .unwrap_or("")
-                .parse()
-                .context("parse high")?,
-            low: record
-                .get(low_idx)
-                .unwrap_or("")
-                .parse()
-                .context("parse low")?,
-            close: record
-                .get(close_idx)
-                .unwrap_or("")
-                .parse()
-                .context("parse close")?,
-            volume: record
-                .get(volume_idx)
-                .unwrap_or("")
-                .parse()
-                .context("parse volume")?,
-        });
-    }
-
-    Ok(out)
+    load_csv(path, options).with_context(|| format!("load candles from {}", path.display()))
 }
 
 fn build_stream_key<'a>(
@@ -146,22 +89,7 @@ fn encode_candle_batch(key: &StreamKeyOwned, start_sequence: u64, candles: &[Can
     let mut fbb = flatbuffers::FlatBufferBuilder::new();
     let key = build_stream_key(&mut fbb, key);
 
-    let mut candle_offsets = Vec::with_capacity(candles.len());
-    for candle in candles {
-        let ts_ms = candle.timestamp.unix_timestamp_nanos() / 1_000_000;
-        candle_offsets.push(fb::Candle::create(
-            &mut fbb,
-            &fb::CandleArgs {
-                ts_ms: ts_ms as i64,
-                open: candle.open,
-                high: candle.high,
-                low: candle.low,
-                close: candle.close,
-                volume: candle.volume,
-            },
-        ));
-    }
-    let candle_vec = fbb.create_vector(&candle_offsets);
+    let candle_vec = encode_candles(&mut fbb, candles.iter().map(CandleWire::from));
     let batch = fb::CandleBatch::create(
         &mut fbb,
         &fb::CandleBatchArgs {
@@ -195,22 +123,7 @@ fn encode_backfill_response(
     let mut fbb = flatbuffers::FlatBufferBuilder::new();
     let key = build_stream_key(&mut fbb, key);
 
-    let mut candle_offsets = Vec::with_capacity(candles.len());
-    for candle in candles {
-        let ts_ms = candle.timestamp.unix_timestamp_nanos() / 1_000_000;
-        candle_offsets.push(fb::Candle::create(
-            &mut fbb,
-            &fb::CandleArgs {
-                ts_ms: ts_ms as i64,
-                open: candle.open,
-                high: candle.high,
-                low: candle.low,
-                close: candle.close,
-                volume: candle.volume,
-            },
-        ));
-    }
-    let candle_vec = fbb.create_vector(&candle_offsets);
+    let candle_vec = encode_candles(&mut fbb, candles.iter().map(CandleWire::from));
     let resp = fb::BackfillCandlesResponse::create(
         &mut fbb,
         &fb::BackfillCandlesResponseArgs {
@@ -237,10 +150,7 @@ fn encode_backfill_response(
 
 fn encode_get_cursor_response(key: &StreamKeyOwned, candles: &[Candle]) -> Vec<u8> {
     let latest_sequence = candles.len() as u64;
-    let latest_ts_ms = candles
-        .last()
-        .map(|c| (c.timestamp.unix_timestamp_nanos() / 1_000_000) as i64)
-        .unwrap_or(0);
+    let latest_ts_ms = candles.last().map(Candle::ts_ms).unwrap_or(0);
 
     let mut fbb = flatbuffers::FlatBufferBuilder::new();
     let key = build_stream_key(&mut fbb, key);
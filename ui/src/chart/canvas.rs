@@ -1,11 +1,101 @@
 use std::sync::Arc;
 
-use core::Candle;
+use core::{Candle, CrossDir, PivotLevels};
 use gpui::{
-    BorderStyle, Bounds, Canvas, PathBuilder, canvas, point, px, quad, rgb, size, transparent_black,
+    Background, BorderStyle, Bounds, Canvas, PathBuilder, Pixels, Window, canvas, point, px, quad,
+    rgb, rgba, size, transparent_black,
 };
+use serde::{Deserialize, Serialize};
 
 use super::aggregation::AggregatedCandle;
+use super::compare::CompareLine;
+use super::theme::ChartTheme;
+
+const HOVER_BAR_HEX: u32 = 0xf59e0b;
+/// Compare overlay line and its axis labels.
+pub(super) const COMPARE_LINE_HEX: u32 = 0x38bdf8;
+const PIVOT_HEX: u32 = 0x9ca3af;
+const RESISTANCE_HEX: u32 = 0xf87171;
+const SUPPORT_HEX: u32 = 0x34d399;
+/// Bid/ask band behind the candles (slate at 20% alpha).
+const SPREAD_BAND_RGBA: u32 = 0x94a3b833;
+
+/// How candles are spread along the x-axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum XAxisMode {
+    /// One equal slot per candle; missing data closes up.
+    #[default]
+    Ordinal,
+    /// Positioned by timestamp across the visible span, leaving gaps where data is missing.
+    Time,
+}
+
+/// Slot centres of `candles` across `(ox, width)` plus the slot width bodies are sized from.
+///
+/// [`XAxisMode::Time`] maps timestamps linearly, padding the span by one median step so the
+/// edge candles keep half a slot of margin. It falls back to ordinal slots when the candles
+/// don't fit one pixel column each (they are drawn grouped then) or share one timestamp.
+pub(super) fn slot_xs(candles: &[Candle], mode: XAxisMode, ox: f32, width: f32) -> (Vec<f32>, f32) {
+    let count = candles.len();
+    let ordinal = || {
+        let slot = (width / count.max(1) as f32).max(f32::EPSILON);
+        let xs: Vec<f32> = (0..count)
+            .map(|idx| ox + (idx as f32 + 0.5) / count as f32 * width)
+            .collect();
+        (xs, slot)
+    };
+    if mode == XAxisMode::Ordinal || count < 2 || count > width.floor().max(1.0) as usize {
+        return ordinal();
+    }
+    let first = candles[0].timestamp;
+    let span = (candles[count - 1].timestamp - first).as_seconds_f64();
+    let mut steps: Vec<f64> = candles
+        .windows(2)
+        .map(|pair| (pair[1].timestamp - pair[0].timestamp).as_seconds_f64())
+        .filter(|step| *step > 0.0)
+        .collect();
+    if span <= 0.0 || steps.is_empty() {
+        return ordinal();
+    }
+    steps.sort_by(f64::total_cmp);
+    let step = steps[steps.len() / 2];
+    let scale = width as f64 / (span + step);
+    let xs: Vec<f32> = candles
+        .iter()
+        .map(|c| {
+            let offset = (c.timestamp - first).as_seconds_f64() + step * 0.5;
+            ox + (offset * scale) as f32
+        })
+        .collect();
+    (xs, ((step * scale) as f32).max(f32::EPSILON))
+}
+
+/// Maps the shared hover candle (relative to the visible start) onto the bar/column that
+/// contains it when `candle_count` candles are grouped into `columns` bars.
+///
+/// Every pane that draws per-candle bars uses this so hover highlighting stays in sync.
+pub(super) fn hovered_bar(
+    hover_local: Option<usize>,
+    candle_count: usize,
+    columns: usize,
+) -> Option<usize> {
+    let local = hover_local?;
+    if candle_count == 0 || columns == 0 || local >= candle_count {
+        return None;
+    }
+    if columns >= candle_count {
+        return Some(local);
+    }
+    let group_start = |col: usize| col * candle_count / columns;
+    let mut col = (local * columns / candle_count).min(columns - 1);
+    while col + 1 < columns && group_start(col + 1) <= local {
+        col += 1;
+    }
+    while col > 0 && group_start(col) > local {
+        col -= 1;
+    }
+    Some(col)
+}
 
 #[derive(Clone)]
 pub(super) struct CandleViewport {
@@ -16,248 +106,637 @@ pub(super) struct CandleViewport {
     volume_max: Option<f64>,
 }
 
-pub(super) fn chart_canvas(
-    candles: Arc<[Candle]>,
-    start: usize,
-    end: usize,
-    price_min: f64,
-    price_max: f64,
-    hover_local: Option<usize>,
-    hover_x: Option<f32>,
-    hover_y: Option<f32>,
-    aggregated: Option<Arc<[AggregatedCandle]>>,
-) -> Canvas<CandleViewport> {
-    canvas(
-        move |_, _, _| CandleViewport {
-            candles: candles.clone(),
-            start,
-            end,
-            aggregated: aggregated.clone(),
-            volume_max: None,
-        },
-        move |bounds, viewport, window, _| {
+/// Bar spacing (px) below which wicks are painted as quads when fast wicks are enabled.
+pub(super) const WICK_QUAD_MAX_SPACING_PX: f32 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WickStyle {
+    Stroke,
+    Quad,
+}
+
+/// Calls `paint` with the pane's `(ox, oy, width, height)` only when the bounds have a
+/// positive, finite size; zero-size layouts during a window resize are skipped. Returns
+/// whether `paint` ran.
+pub(super) fn paint_guarded(
+    bounds: Bounds<Pixels>,
+    paint: impl FnOnce(f32, f32, f32, f32),
+) -> bool {
+    let width = f32::from(bounds.size.width);
+    let height = f32::from(bounds.size.height);
+    if !(width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0) {
+        return false;
+    }
+    paint(
+        f32::from(bounds.origin.x),
+        f32::from(bounds.origin.y),
+        width,
+        height,
+    );
+    true
+}
+
+/// Height of a volume bar in a pane `height` px tall where `scale` fills the pane; at least
+/// 1px so empty bars stay visible.
+pub(super) fn volume_bar_height(volume: f64, scale: f64, height: f32) -> f32 {
+    let normalized = (volume / scale.max(1e-9)).clamp(0.0, 1.0);
+    (normalized as f32 * height).max(1.0)
+}
+
+/// Y of `price` in a pane spanning `top..top + height` over `price_min..=price_max`.
+/// Non-finite prices clamp to the pane (NaN to the bottom) so one bad value cannot turn the
+/// geometry into NaN.
+pub(super) fn price_to_y(
+    price: f64,
+    (price_min, price_max): (f64, f64),
+    top: f32,
+    height: f32,
+) -> f32 {
+    let range = (price_max - price_min).max(1e-9);
+    let normalized = (price - price_min) / range;
+    let normalized = if normalized.is_nan() {
+        0.0
+    } else {
+        normalized.clamp(0.0, 1.0)
+    };
+    top + (1.0 - normalized as f32) * height
+}
+
+/// Width left for candles once `right_padding` empty candle slots are reserved after the
+/// last of `candle_count` candles.
+pub(super) fn plot_width(width: f32, candle_count: usize, right_padding: usize) -> f32 {
+    if candle_count == 0 {
+        return width;
+    }
+    width * candle_count as f32 / (candle_count + right_padding) as f32
+}
+
+/// Stroked paths are the hot spot on dense views; past the density threshold a 1px quad
+/// is visually identical and much cheaper.
+pub(super) fn wick_style(fast_wicks: bool, bars: usize, width: f32) -> WickStyle {
+    if !fast_wicks || bars == 0 {
+        return WickStyle::Stroke;
+    }
+    if width / bars as f32 <= WICK_QUAD_MAX_SPACING_PX {
+        WickStyle::Quad
+    } else {
+        WickStyle::Stroke
+    }
+}
+
+fn paint_wick(window: &mut Window, style: WickStyle, mark: &CandleMark) {
+    let (x, high_y, low_y) = (mark.x, mark.high_y, mark.low_y);
+    match style {
+        WickStyle::Stroke => {
+            let mut builder = PathBuilder::stroke(px(1.));
+            builder.move_to(point(px(x), px(high_y)));
+            builder.line_to(point(px(x), px(low_y)));
+            if let Ok(path) = builder.build() {
+                window.paint_path(path, rgb(mark.wick_color));
+            }
+        }
+        WickStyle::Quad => {
+            let top = high_y.min(low_y);
+            let wick_bounds = Bounds {
+                origin: point(px(x - 0.5), px(top)),
+                size: size(px(1.), px((high_y - low_y).abs().max(1.0))),
+            };
             window.paint_quad(quad(
-                bounds,
+                wick_bounds,
                 px(0.),
-                rgb(0x0b1220),
+                rgb(mark.wick_color),
                 px(0.),
                 transparent_black(),
                 BorderStyle::default(),
             ));
+        }
+    }
+}
 
-            let width = f32::from(bounds.size.width);
-            let height = f32::from(bounds.size.height);
-            let ox = f32::from(bounds.origin.x);
-            let oy = f32::from(bounds.origin.y);
-            if viewport.candles.is_empty() || height <= 0.0 || width <= 0.0 {
-                return;
-            }
+/// Target spacing between horizontal gridlines when the density isn't set explicitly.
+const GRID_SPACING_PX: f32 = 80.0;
 
-            let start = viewport.start.min(viewport.candles.len());
-            let end = viewport.end.min(viewport.candles.len()).max(start);
-            let candles = &viewport.candles[start..end];
-            if candles.is_empty() {
-                return;
-            }
-            let candle_count = candles.len();
+/// Gridline count for a price pane `height` px tall: one line per ~`GRID_SPACING_PX`,
+/// never fewer than min/mid/max.
+pub(super) fn default_grid_lines(height: f32) -> usize {
+    ((height.max(0.0) / GRID_SPACING_PX) as usize + 1).clamp(3, 16)
+}
 
-            let range = (price_max - price_min).max(1e-9);
-            let x_for_idx = |idx: usize| -> f32 {
-                let t = (idx as f32 + 0.5) / candle_count as f32;
-                ox + t * width
-            };
+/// Y positions of `lines` evenly spaced gridlines from `top` to `top + height`; the first and
+/// last always sit on the pane edges so they line up with the max/min price labels.
+pub(super) fn gridline_ys(top: f32, height: f32, lines: usize) -> Vec<f32> {
+    let steps = lines.max(2) - 1;
+    (0..=steps)
+        .map(|i| top + height * i as f32 / steps as f32)
+        .collect()
+}
 
-            let price_to_y = |price: f64| -> f32 {
-                let normalized = ((price - price_min) / range).clamp(0.0, 1.0);
-                oy + (1.0 - normalized as f32) * height
-            };
+/// Most gridlines a fixed tick step may produce before falling back to the automatic ones.
+const MAX_FIXED_TICKS: usize = 64;
+
+/// Multiples of `step` within `[price_min, price_max]`, top (highest) first. `None` when
+/// `step` isn't a positive finite number or would crowd the pane with more than
+/// [`MAX_FIXED_TICKS`] lines.
+pub(super) fn fixed_step_ticks(price_min: f64, price_max: f64, step: f64) -> Option<Vec<f64>> {
+    if !(step.is_finite() && step > 0.0 && price_min.is_finite() && price_max.is_finite()) {
+        return None;
+    }
+    let first = (price_min / step).ceil();
+    let last = (price_max / step).floor();
+    if last - first + 1.0 > MAX_FIXED_TICKS as f64 {
+        return None;
+    }
+    let (first, last) = (first as i64, last as i64);
+    Some((first..=last).rev().map(|n| n as f64 * step).collect())
+}
+
+const CROSS_MARKER_PX: f32 = 6.0;
+
+/// Up triangle under the low for a golden cross, down triangle over the high for a death cross.
+fn paint_cross_marker(
+    window: &mut Window,
+    x: f32,
+    dir: CrossDir,
+    high_y: f32,
+    low_y: f32,
+    theme: &ChartTheme,
+) {
+    let half = CROSS_MARKER_PX * 0.5;
+    let (tip_y, base_y, color) = match dir {
+        CrossDir::Up => (low_y + 4.0, low_y + 4.0 + CROSS_MARKER_PX, theme.up),
+        CrossDir::Down => (high_y - 4.0, high_y - 4.0 - CROSS_MARKER_PX, theme.down),
+    };
+    let mut builder = PathBuilder::fill();
+    builder.move_to(point(px(x), px(tip_y)));
+    builder.line_to(point(px(x + half), px(base_y)));
+    builder.line_to(point(px(x - half), px(base_y)));
+    builder.close();
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, rgb(color));
+    }
+}
+
+/// How a candle body is painted: fill (`None` = hollow), outline color/width, dashed or not.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct BodyStyle {
+    pub(super) fill: Option<u32>,
+    pub(super) border: u32,
+    pub(super) border_width: f32,
+    pub(super) dashed: bool,
+}
+
+/// The forming live candle gets a dashed outline instead of a filled body.
+pub(super) fn body_style(color: u32, theme: &ChartTheme, forming: bool) -> BodyStyle {
+    let border = theme.body_border.unwrap_or(color);
+    if forming {
+        BodyStyle {
+            fill: None,
+            border,
+            border_width: theme.body_border_width.max(1.0),
+            dashed: true,
+        }
+    } else {
+        BodyStyle {
+            fill: Some(color),
+            border,
+            border_width: theme.body_border_width.max(0.0),
+            dashed: false,
+        }
+    }
+}
+
+fn paint_body(
+    window: &mut Window,
+    bounds: Bounds<Pixels>,
+    color: u32,
+    theme: &ChartTheme,
+    forming: bool,
+) {
+    let style = body_style(color, theme, forming);
+    let fill: Background = match style.fill {
+        Some(fill) => rgb(fill).into(),
+        None => transparent_black().into(),
+    };
+    let border_style = if style.dashed {
+        BorderStyle::Dashed
+    } else {
+        BorderStyle::default()
+    };
+    window.paint_quad(quad(
+        bounds,
+        px(2.),
+        fill,
+        px(style.border_width),
+        rgb(style.border),
+        border_style,
+    ));
+}
+
+/// Geometry of one drawn candle (or grouped column), in the pane's coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct CandleMark {
+    pub(super) x: f32,
+    pub(super) high_y: f32,
+    pub(super) low_y: f32,
+    pub(super) body_top: f32,
+    pub(super) body_width: f32,
+    pub(super) body_height: f32,
+    pub(super) color: u32,
+    pub(super) wick_color: u32,
+    pub(super) forming: bool,
+}
+
+/// Body width in a `slot`-wide column: 60% of the slot, narrowed so `gap` px separate
+/// neighbours as long as that leaves a body of at least 1px.
+pub(super) fn body_width(slot: f32, gap: f32) -> f32 {
+    let body = slot * 0.6;
+    let gapped = slot - gap;
+    let body = if gapped >= 1.0 {
+        body.min(gapped)
+    } else {
+        body
+    };
+    body.max(f32::EPSILON)
+}
+
+/// Lays out `candles` across `(ox, oy, width, height)`: one mark per aggregated column when
+/// `aggregated` is given, per candle when they fit one pixel column each, otherwise per
+/// on-the-fly group. Per-candle marks sit at [`slot_xs`] for `x_mode`. Shared by
+/// [`chart_canvas`] and off-screen rendering.
+pub(super) fn candle_marks(
+    candles: &[Candle],
+    aggregated: Option<&[AggregatedCandle]>,
+    (ox, oy, width, height): (f32, f32, f32, f32),
+    (price_min, price_max): (f64, f64),
+    forming: bool,
+    x_mode: XAxisMode,
+    theme: &ChartTheme,
+) -> Vec<CandleMark> {
+    if candles.is_empty() {
+        return Vec::new();
+    }
+    let price_to_y = |price: f64| price_to_y(price, (price_min, price_max), oy, height);
+    let mark = |x: f32, body_width: f32, ohlc: (f64, f64, f64, f64), forming: bool| {
+        let (open, high, low, close) = ohlc;
+        let open_y = price_to_y(open);
+        let close_y = price_to_y(close);
+        CandleMark {
+            x,
+            high_y: price_to_y(high),
+            low_y: price_to_y(low),
+            body_top: open_y.min(close_y),
+            body_width,
+            body_height: (open_y - close_y).abs().max(1.0),
+            color: theme.ohlc_color(open, high, low, close),
+            wick_color: theme.wick_color(open, high, low, close),
+            forming,
+        }
+    };
+
+    if let Some(aggregated) = aggregated
+        && !aggregated.is_empty()
+    {
+        let columns = aggregated.len();
+        let column_width = (width / columns as f32).max(f32::EPSILON);
+        let body_width = body_width(column_width, theme.candle_gap_px);
+        return aggregated
+            .iter()
+            .enumerate()
+            .map(|(col, agg)| {
+                mark(
+                    ox + (col as f32 + 0.5) * column_width,
+                    body_width,
+                    (agg.open, agg.high, agg.low, agg.close),
+                    forming && col + 1 == columns,
+                )
+            })
+            .collect();
+    }
+
+    let candle_count = candles.len();
+    let columns = width.floor().max(1.0) as usize;
+    if candle_count <= columns {
+        let (xs, candle_width) = slot_xs(candles, x_mode, ox, width);
+        let body_width = body_width(candle_width, theme.candle_gap_px);
+        return candles
+            .iter()
+            .zip(xs)
+            .enumerate()
+            .map(|(idx, (candle, x))| {
+                mark(
+                    x,
+                    body_width,
+                    (candle.open, candle.high, candle.low, candle.close),
+                    forming && idx + 1 == candle_count,
+                )
+            })
+            .collect();
+    }
 
-            // gridlines (min/mid/max)
-            for frac in [0.0f32, 0.5, 1.0] {
-                let y = oy + height * (1.0 - frac);
-                let mut builder = PathBuilder::stroke(px(1.));
-                builder.move_to(point(px(ox), px(y)));
-                builder.line_to(point(px(ox + width), px(y)));
-                if let Ok(path) = builder.build() {
-                    window.paint_path(path, rgb(0x1f2937));
+    let column_width = (width / columns as f32).max(f32::EPSILON);
+    let body_width = body_width(column_width, theme.candle_gap_px);
+    (0..columns)
+        .map(|col| {
+            let g_start = col * candle_count / columns;
+            let g_end = ((col + 1) * candle_count / columns).max(g_start + 1);
+            let group = &candles[g_start..g_end];
+            let first = &group[0];
+            let last = &group[group.len() - 1];
+            let high = group.iter().map(|c| c.high).fold(first.high, f64::max);
+            let low = group.iter().map(|c| c.low).fold(first.low, f64::min);
+            mark(
+                ox + (col as f32 + 0.5) * column_width,
+                body_width,
+                (first.open, high, low, last.close),
+                forming && col + 1 == columns,
+            )
+        })
+        .collect()
+}
+
+/// Everything [`chart_canvas`] draws for one frame.
+pub(super) struct ChartCanvasParams {
+    pub(super) candles: Arc<[Candle]>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) price_min: f64,
+    pub(super) price_max: f64,
+    pub(super) hover_local: Option<usize>,
+    pub(super) hover_x: Option<f32>,
+    pub(super) hover_y: Option<f32>,
+    pub(super) aggregated: Option<Arc<[AggregatedCandle]>>,
+    pub(super) fast_wicks: bool,
+    /// The series' final candle is still forming.
+    pub(super) in_progress: bool,
+    pub(super) grid_lines: usize,
+    /// Fixed-step gridline prices; `None` spreads `grid_lines` evenly.
+    pub(super) price_ticks: Option<Vec<f64>>,
+    pub(super) right_padding: usize,
+    pub(super) compare: Option<CompareLine>,
+    pub(super) spread: Option<Vec<Option<(f64, f64)>>>,
+    pub(super) crossovers: Vec<(usize, CrossDir)>,
+    pub(super) pivots: Option<(usize, PivotLevels)>,
+    pub(super) x_mode: XAxisMode,
+    pub(super) theme: ChartTheme,
+}
+
+pub(super) fn chart_canvas(params: ChartCanvasParams) -> Canvas<CandleViewport> {
+    let ChartCanvasParams {
+        candles,
+        start,
+        end,
+        price_min,
+        price_max,
+        hover_local,
+        hover_x,
+        hover_y,
+        aggregated,
+        fast_wicks,
+        in_progress,
+        grid_lines,
+        price_ticks,
+        right_padding,
+        compare,
+        spread,
+        crossovers,
+        pivots,
+        x_mode,
+        theme,
+    } = params;
+    canvas(
+        move |_, _, _| CandleViewport {
+            candles: candles.clone(),
+            start,
+            end,
+            aggregated: aggregated.clone(),
+            volume_max: None,
+        },
+        move |bounds, viewport, window, _| {
+            paint_guarded(bounds, |ox, oy, width, height| {
+                window.paint_quad(quad(
+                    bounds,
+                    px(0.),
+                    rgb(theme.background),
+                    px(0.),
+                    transparent_black(),
+                    BorderStyle::default(),
+                ));
+
+                if viewport.candles.is_empty() {
+                    return;
                 }
-            }
 
-            if let Some(aggregated) = viewport.aggregated.as_deref()
-                && !aggregated.is_empty()
-            {
-                let columns = aggregated.len();
-                let column_width = (width / columns as f32).max(f32::EPSILON);
-                let body_width = (column_width * 0.6).max(f32::EPSILON);
-                for (col, agg) in aggregated.iter().enumerate() {
-                    let open_y = price_to_y(agg.open);
-                    let close_y = price_to_y(agg.close);
-                    let high_y = price_to_y(agg.high);
-                    let low_y = price_to_y(agg.low);
-
-                    let body_top = open_y.min(close_y);
-                    let body_height = (open_y - close_y).abs().max(1.0);
-                    let color = if agg.close >= agg.open {
-                        rgb(0x22c55e)
-                    } else {
-                        rgb(0xef4444)
-                    };
+                let start = viewport.start.min(viewport.candles.len());
+                let end = viewport.end.min(viewport.candles.len()).max(start);
+                let candles = &viewport.candles[start..end];
+                if candles.is_empty() {
+                    return;
+                }
+                let candle_count = candles.len();
+                // Candles fill the left part; `right_padding` empty slots stay clear on the right.
+                let pane_width = width;
+                let width = plot_width(width, candle_count, right_padding);
+                // Only the series' final candle can be forming.
+                let forming = in_progress && end == viewport.candles.len();
 
-                    let x = ox + (col as f32 + 0.5) * column_width;
+                // Aggregated columns are always ordinal; per-candle slots follow `x_mode`.
+                let x_mode = if viewport.aggregated.is_some() {
+                    XAxisMode::Ordinal
+                } else {
+                    x_mode
+                };
+                let (xs, slot) = slot_xs(candles, x_mode, ox, width);
+                let x_for_idx = |idx: usize| -> f32 { xs.get(idx).copied().unwrap_or(ox + width) };
 
+                let price_to_y = |price: f64| price_to_y(price, (price_min, price_max), oy, height);
+
+                let gridlines = match price_ticks.as_deref() {
+                    Some(ticks) => ticks.iter().map(|tick| price_to_y(*tick)).collect(),
+                    None => gridline_ys(oy, height, grid_lines),
+                };
+                for y in gridlines {
                     let mut builder = PathBuilder::stroke(px(1.));
-                    builder.move_to(point(px(x), px(high_y)));
-                    builder.line_to(point(px(x), px(low_y)));
+                    builder.move_to(point(px(ox), px(y)));
+                    builder.line_to(point(px(ox + pane_width), px(y)));
                     if let Ok(path) = builder.build() {
-                        window.paint_path(path, rgb(0xe5e7eb));
+                        window.paint_path(path, rgb(0x1f2937));
                     }
-
-                    let body_bounds = Bounds {
-                        origin: point(px(x - body_width * 0.5), px(body_top)),
-                        size: size(px(body_width), px(body_height)),
-                    };
-                    window.paint_quad(quad(
-                        body_bounds,
-                        px(2.),
-                        color,
-                        px(0.),
-                        color,
-                        BorderStyle::default(),
-                    ));
                 }
-            } else {
-                let columns = width.floor().max(1.0) as usize;
-                if candle_count <= columns {
-                    let candle_width = (width / candle_count as f32).max(f32::EPSILON);
-                    let body_width = (candle_width * 0.6).max(f32::EPSILON);
-                    for (idx, candle) in candles.iter().enumerate() {
-                        let x = x_for_idx(idx);
-                        let open_y = price_to_y(candle.open);
-                        let close_y = price_to_y(candle.close);
-                        let high_y = price_to_y(candle.high);
-                        let low_y = price_to_y(candle.low);
-
-                        let body_top = open_y.min(close_y);
-                        let body_height = (open_y - close_y).abs().max(1.0);
-                        let color = if candle.close >= candle.open {
-                            rgb(0x22c55e)
-                        } else {
-                            rgb(0xef4444)
-                        };
 
+                // Pivot levels run from where their session starts to the right edge.
+                if let Some((from, levels)) = pivots {
+                    let x0 = if from < candle_count {
+                        x_for_idx(from) - slot * 0.5
+                    } else {
+                        ox + width
+                    };
+                    for (price, hex) in [
+                        (levels.r2, RESISTANCE_HEX),
+                        (levels.r1, RESISTANCE_HEX),
+                        (levels.pivot, PIVOT_HEX),
+                        (levels.s1, SUPPORT_HEX),
+                        (levels.s2, SUPPORT_HEX),
+                    ] {
+                        if !(price_min..=price_max).contains(&price) {
+                            continue;
+                        }
+                        let y = price_to_y(price);
                         let mut builder = PathBuilder::stroke(px(1.));
-                        builder.move_to(point(px(x), px(high_y)));
-                        builder.line_to(point(px(x), px(low_y)));
+                        builder.move_to(point(px(x0), px(y)));
+                        builder.line_to(point(px(ox + pane_width), px(y)));
                         if let Ok(path) = builder.build() {
-                            window.paint_path(path, rgb(0xe5e7eb));
+                            window.paint_path(path, rgb(hex));
                         }
-
-                        let body_bounds = Bounds {
-                            origin: point(px(x - body_width * 0.5), px(body_top)),
-                            size: size(px(body_width), px(body_height)),
-                        };
-                        window.paint_quad(quad(
-                            body_bounds,
-                            px(2.),
-                            color,
-                            px(0.),
-                            color,
-                            BorderStyle::default(),
-                        ));
                     }
-                } else {
-                    let column_width = (width / columns as f32).max(f32::EPSILON);
-                    let body_width = (column_width * 0.6).max(f32::EPSILON);
-                    for col in 0..columns {
-                        let g_start = col * candle_count / columns;
-                        let g_end = ((col + 1) * candle_count / columns).max(g_start + 1);
-                        let group = &candles[g_start..g_end];
-                        let first = &group[0];
-                        let last = &group[group.len() - 1];
-
-                        let mut high = first.high;
-                        let mut low = first.low;
-                        for c in group {
-                            high = high.max(c.high);
-                            low = low.min(c.low);
-                        }
+                }
 
-                        let open_y = price_to_y(first.open);
-                        let close_y = price_to_y(last.close);
-                        let high_y = price_to_y(high);
-                        let low_y = price_to_y(low);
-                        let body_top = open_y.min(close_y);
-                        let body_height = (open_y - close_y).abs().max(1.0);
-                        let color = if last.close >= first.open {
-                            rgb(0x22c55e)
-                        } else {
-                            rgb(0xef4444)
+                // One filled polygon per run of candles with a quote: asks left to right,
+                // then bids back.
+                if let Some(spread) = spread.as_ref() {
+                    for run in spread
+                        .iter()
+                        .enumerate()
+                        .collect::<Vec<_>>()
+                        .split(|(_, quote)| quote.is_none())
+                        .filter(|run| !run.is_empty())
+                    {
+                        let edge = |idx: usize, price: f64| {
+                            point(px(x_for_idx(idx)), px(price_to_y(price)))
                         };
-
-                        let x = ox + (col as f32 + 0.5) * column_width;
-
-                        let mut builder = PathBuilder::stroke(px(1.));
-                        builder.move_to(point(px(x), px(high_y)));
-                        builder.line_to(point(px(x), px(low_y)));
+                        let quotes: Vec<(usize, f64, f64)> = run
+                            .iter()
+                            .filter_map(|&(idx, quote)| quote.map(|(bid, ask)| (idx, bid, ask)))
+                            .collect();
+                        let mut builder = PathBuilder::fill();
+                        for (i, &(idx, _, ask)) in quotes.iter().enumerate() {
+                            if i == 0 {
+                                builder.move_to(edge(idx, ask));
+                            } else {
+                                builder.line_to(edge(idx, ask));
+                            }
+                        }
+                        for &(idx, bid, _) in quotes.iter().rev() {
+                            builder.line_to(edge(idx, bid));
+                        }
+                        builder.close();
                         if let Ok(path) = builder.build() {
-                            window.paint_path(path, rgb(0xe5e7eb));
+                            window.paint_path(path, rgba(SPREAD_BAND_RGBA));
                         }
+                    }
+                }
 
-                        let body_bounds = Bounds {
-                            origin: point(px(x - body_width * 0.5), px(body_top)),
-                            size: size(px(body_width), px(body_height)),
+                let marks = candle_marks(
+                    candles,
+                    viewport.aggregated.as_deref(),
+                    (ox, oy, width, height),
+                    (price_min, price_max),
+                    forming,
+                    x_mode,
+                    &theme,
+                );
+                let wick_style = wick_style(fast_wicks, marks.len(), width);
+                for mark in &marks {
+                    paint_wick(window, wick_style, mark);
+                    let body_bounds = Bounds {
+                        origin: point(px(mark.x - mark.body_width * 0.5), px(mark.body_top)),
+                        size: size(px(mark.body_width), px(mark.body_height)),
+                    };
+                    paint_body(window, body_bounds, mark.color, &theme, mark.forming);
+                }
+
+                if let Some(compare) = compare.as_ref() {
+                    let mut builder = PathBuilder::stroke(px(1.5));
+                    let mut started = false;
+                    for (idx, close) in compare.closes.iter().enumerate() {
+                        let Some(close) = close else {
+                            continue;
                         };
-                        window.paint_quad(quad(
-                            body_bounds,
-                            px(2.),
-                            color,
-                            px(0.),
-                            color,
-                            BorderStyle::default(),
-                        ));
+                        let pos = point(px(x_for_idx(idx)), px(compare.y_for(*close, oy, height)));
+                        if started {
+                            builder.line_to(pos);
+                        } else {
+                            builder.move_to(pos);
+                            started = true;
+                        }
+                    }
+                    if started && let Ok(path) = builder.build() {
+                        window.paint_path(path, rgb(COMPARE_LINE_HEX));
                     }
                 }
-            }
 
-            // hover crosshair
-            if hover_local.is_some() {
-                let x = if let Some(x) = hover_x {
-                    x.clamp(ox, ox + width)
-                } else if let Some(local_idx) = hover_local {
-                    x_for_idx(local_idx.min(candle_count.saturating_sub(1)))
-                } else {
-                    return;
-                };
-                let mut builder = PathBuilder::stroke(px(1.));
-                builder.move_to(point(px(x), px(oy)));
-                builder.line_to(point(px(x), px(oy + height)));
-                if let Ok(path) = builder.build() {
-                    window.paint_path(path, rgb(0xf59e0b));
+                for &(idx, dir) in &crossovers {
+                    let Some(candle) = candles.get(idx) else {
+                        continue;
+                    };
+                    paint_cross_marker(
+                        window,
+                        x_for_idx(idx),
+                        dir,
+                        price_to_y(candle.high),
+                        price_to_y(candle.low),
+                        &theme,
+                    );
                 }
-            }
 
-            if let Some(y) = hover_y {
-                let y = y.clamp(oy, oy + height);
-                let mut builder = PathBuilder::stroke(px(1.));
-                builder.move_to(point(px(ox), px(y)));
-                builder.line_to(point(px(ox + width), px(y)));
-                if let Ok(path) = builder.build() {
-                    window.paint_path(path, rgb(0xf59e0b));
+                // hover crosshair
+                if hover_local.is_some() {
+                    let x = if let Some(x) = hover_x {
+                        x.clamp(ox, ox + pane_width)
+                    } else if let Some(local_idx) = hover_local {
+                        x_for_idx(local_idx.min(candle_count.saturating_sub(1)))
+                    } else {
+                        return;
+                    };
+                    let mut builder = PathBuilder::stroke(px(1.));
+                    builder.move_to(point(px(x), px(oy)));
+                    builder.line_to(point(px(x), px(oy + height)));
+                    if let Ok(path) = builder.build() {
+                        window.paint_path(path, rgb(0xf59e0b));
+                    }
                 }
-            }
+
+                if let Some(y) = hover_y {
+                    let y = y.clamp(oy, oy + height);
+                    let mut builder = PathBuilder::stroke(px(1.));
+                    builder.move_to(point(px(ox), px(y)));
+                    builder.line_to(point(px(ox + pane_width), px(y)));
+                    if let Ok(path) = builder.build() {
+                        window.paint_path(path, rgb(0xf59e0b));
+                    }
+                }
+            });
         },
     )
 }
 
-pub(super) fn volume_canvas(
-    candles: Arc<[Candle]>,
-    start: usize,
-    end: usize,
-    hover_local: Option<usize>,
-    hover_x: Option<f32>,
-    aggregated: Option<Arc<[AggregatedCandle]>>,
-    volume_max: Option<f64>,
-) -> Canvas<CandleViewport> {
+/// Everything [`volume_canvas`] draws for one frame.
+pub(super) struct VolumeCanvasParams {
+    pub(super) candles: Arc<[Candle]>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) hover_local: Option<usize>,
+    pub(super) hover_x: Option<f32>,
+    pub(super) aggregated: Option<Arc<[AggregatedCandle]>>,
+    /// Bar height scale; `None` fits the visible slice.
+    pub(super) volume_max: Option<f64>,
+    pub(super) right_padding: usize,
+    pub(super) x_mode: XAxisMode,
+    pub(super) theme: ChartTheme,
+}
+
+pub(super) fn volume_canvas(params: VolumeCanvasParams) -> Canvas<CandleViewport> {
+    let VolumeCanvasParams {
+        candles,
+        start,
+        end,
+        hover_local,
+        hover_x,
+        aggregated,
+        volume_max,
+        right_padding,
+        x_mode,
+        theme,
+    } = params;
     canvas(
         move |_, _, _| CandleViewport {
             candles: candles.clone(),
@@ -267,98 +746,65 @@ pub(super) fn volume_canvas(
             volume_max,
         },
         move |bounds, viewport, window, _| {
-            window.paint_quad(quad(
-                bounds,
-                px(0.),
-                rgb(0x0b1220),
-                px(0.),
-                transparent_black(),
-                BorderStyle::default(),
-            ));
-
-            let width = f32::from(bounds.size.width);
-            let height = f32::from(bounds.size.height);
-            let ox = f32::from(bounds.origin.x);
-            let oy = f32::from(bounds.origin.y);
-            if viewport.candles.is_empty() || height <= 0.0 || width <= 0.0 {
-                return;
-            }
+            paint_guarded(bounds, |ox, oy, width, height| {
+                window.paint_quad(quad(
+                    bounds,
+                    px(0.),
+                    rgb(theme.background),
+                    px(0.),
+                    transparent_black(),
+                    BorderStyle::default(),
+                ));
 
-            let start = viewport.start.min(viewport.candles.len());
-            let end = viewport.end.min(viewport.candles.len()).max(start);
-            let candles = &viewport.candles[start..end];
-            if candles.is_empty() {
-                return;
-            }
-            let candle_count = candles.len();
+                if viewport.candles.is_empty() {
+                    return;
+                }
 
-            let x_for_idx = |idx: usize| -> f32 {
-                let t = (idx as f32 + 0.5) / candle_count as f32;
-                ox + t * width
-            };
+                let start = viewport.start.min(viewport.candles.len());
+                let end = viewport.end.min(viewport.candles.len()).max(start);
+                let candles = &viewport.candles[start..end];
+                if candles.is_empty() {
+                    return;
+                }
+                let candle_count = candles.len();
+                // Candles fill the left part; `right_padding` empty slots stay clear on the right.
+                let pane_width = width;
+                let width = plot_width(width, candle_count, right_padding);
 
-            if let Some(aggregated) = viewport.aggregated.as_deref()
-                && !aggregated.is_empty()
-            {
-                let columns = aggregated.len();
-                let column_width = (width / columns as f32).max(f32::EPSILON);
-                let bar_width = (column_width * 0.7).max(f32::EPSILON);
-                let max_vol = viewport
-                    .volume_max
-                    .or_else(|| {
-                        aggregated
-                            .iter()
-                            .map(|c| c.volume)
-                            .fold(None, |acc, v| Some(acc.unwrap_or(0.0).max(v)))
-                    })
-                    .unwrap_or(0.0)
-                    .max(1e-9);
-
-                for (col, agg) in aggregated.iter().enumerate() {
-                    let x = ox + (col as f32 + 0.5) * column_width;
-                    let normalized = (agg.volume / max_vol).clamp(0.0, 1.0);
-                    let bar_h = (normalized as f32 * height).max(1.0);
-                    let y = oy + height - bar_h;
-                    let color = if agg.close >= agg.open {
-                        rgb(0x22c55e)
-                    } else {
-                        rgb(0xef4444)
-                    };
+                let x_mode = if viewport.aggregated.is_some() {
+                    XAxisMode::Ordinal
+                } else {
+                    x_mode
+                };
+                let (xs, slot) = slot_xs(candles, x_mode, ox, width);
+                let x_for_idx = |idx: usize| -> f32 { xs.get(idx).copied().unwrap_or(ox + width) };
 
-                    let bar_bounds = Bounds {
-                        origin: point(px(x - bar_width * 0.5), px(y)),
-                        size: size(px(bar_width), px(bar_h)),
-                    };
-                    window.paint_quad(quad(
-                        bar_bounds,
-                        px(1.),
-                        color,
-                        px(0.),
-                        color,
-                        BorderStyle::default(),
-                    ));
-                }
-            } else {
-                let columns = width.floor().max(1.0) as usize;
-                if candle_count <= columns {
-                    let max_vol = candles
-                        .iter()
-                        .map(|c| c.volume)
-                        .fold(0.0_f64, f64::max)
+                if let Some(aggregated) = viewport.aggregated.as_deref()
+                    && !aggregated.is_empty()
+                {
+                    let columns = aggregated.len();
+                    let column_width = (width / columns as f32).max(f32::EPSILON);
+                    let bar_width = (column_width * 0.7).max(f32::EPSILON);
+                    let max_vol = viewport
+                        .volume_max
+                        .or_else(|| {
+                            aggregated
+                                .iter()
+                                .map(|c| c.volume)
+                                .fold(None, |acc, v| Some(acc.unwrap_or(0.0).max(v)))
+                        })
+                        .unwrap_or(0.0)
                         .max(1e-9);
 
-                    let candle_width = (width / candle_count as f32).max(f32::EPSILON);
-                    let bar_width = (candle_width * 0.7).max(f32::EPSILON);
-
-                    for (idx, candle) in candles.iter().enumerate() {
-                        let x = x_for_idx(idx);
-                        let normalized = (candle.volume / max_vol).clamp(0.0, 1.0);
-                        let bar_h = (normalized as f32 * height).max(1.0);
+                    let highlighted = hovered_bar(hover_local, candle_count, columns);
+                    for (col, agg) in aggregated.iter().enumerate() {
+                        let x = ox + (col as f32 + 0.5) * column_width;
+                        let bar_h = volume_bar_height(agg.volume, max_vol, height);
                         let y = oy + height - bar_h;
-                        let color = if candle.close >= candle.open {
-                            rgb(0x22c55e)
+                        let color = if highlighted == Some(col) {
+                            rgb(HOVER_BAR_HEX)
                         } else {
-                            rgb(0xef4444)
+                            rgb(theme.candle_color(agg.close >= agg.open))
                         };
 
                         let bar_bounds = Bounds {
@@ -375,71 +821,432 @@ pub(super) fn volume_canvas(
                         ));
                     }
                 } else {
-                    let mut max_group_vol = 0.0_f64;
-                    for col in 0..columns {
-                        let g_start = col * candle_count / columns;
-                        let g_end = ((col + 1) * candle_count / columns).max(g_start + 1);
-                        let mut vol_sum = 0.0_f64;
-                        for c in &candles[g_start..g_end] {
-                            vol_sum += c.volume;
-                        }
-                        max_group_vol = max_group_vol.max(vol_sum);
-                    }
-                    let max_group_vol = max_group_vol.max(1e-9);
+                    let columns = width.floor().max(1.0) as usize;
+                    if candle_count <= columns {
+                        let max_vol = viewport
+                            .volume_max
+                            .unwrap_or_else(|| {
+                                candles.iter().map(|c| c.volume).fold(0.0_f64, f64::max)
+                            })
+                            .max(1e-9);
 
-                    let column_width = (width / columns as f32).max(f32::EPSILON);
-                    let bar_width = (column_width * 0.7).max(f32::EPSILON);
+                        let bar_width = (slot * 0.7).max(f32::EPSILON);
 
-                    for col in 0..columns {
-                        let g_start = col * candle_count / columns;
-                        let g_end = ((col + 1) * candle_count / columns).max(g_start + 1);
-                        let group = &candles[g_start..g_end];
-                        let first = &group[0];
-                        let last = &group[group.len() - 1];
-                        let mut vol_sum = 0.0_f64;
-                        for c in group {
-                            vol_sum += c.volume;
+                        let highlighted = hovered_bar(hover_local, candle_count, candle_count);
+                        for (idx, candle) in candles.iter().enumerate() {
+                            let x = x_for_idx(idx);
+                            let bar_h = volume_bar_height(candle.volume, max_vol, height);
+                            let y = oy + height - bar_h;
+                            let color = if highlighted == Some(idx) {
+                                rgb(HOVER_BAR_HEX)
+                            } else {
+                                rgb(theme.candle_color(candle.close >= candle.open))
+                            };
+
+                            let bar_bounds = Bounds {
+                                origin: point(px(x - bar_width * 0.5), px(y)),
+                                size: size(px(bar_width), px(bar_h)),
+                            };
+                            window.paint_quad(quad(
+                                bar_bounds,
+                                px(1.),
+                                color,
+                                px(0.),
+                                color,
+                                BorderStyle::default(),
+                            ));
+                        }
+                    } else {
+                        let mut max_group_vol = 0.0_f64;
+                        for col in 0..columns {
+                            let g_start = col * candle_count / columns;
+                            let g_end = ((col + 1) * candle_count / columns).max(g_start + 1);
+                            let mut vol_sum = 0.0_f64;
+                            for c in &candles[g_start..g_end] {
+                                vol_sum += c.volume;
+                            }
+                            max_group_vol = max_group_vol.max(vol_sum);
                         }
+                        let max_group_vol = viewport.volume_max.unwrap_or(max_group_vol).max(1e-9);
 
-                        let x = ox + (col as f32 + 0.5) * column_width;
-                        let normalized = (vol_sum / max_group_vol).clamp(0.0, 1.0);
-                        let bar_h = (normalized as f32 * height).max(1.0);
-                        let y = oy + height - bar_h;
-                        let color = if last.close >= first.open {
-                            rgb(0x22c55e)
-                        } else {
-                            rgb(0xef4444)
-                        };
+                        let column_width = (width / columns as f32).max(f32::EPSILON);
+                        let bar_width = (column_width * 0.7).max(f32::EPSILON);
+                        let highlighted = hovered_bar(hover_local, candle_count, columns);
 
-                        let bar_bounds = Bounds {
-                            origin: point(px(x - bar_width * 0.5), px(y)),
-                            size: size(px(bar_width), px(bar_h)),
-                        };
-                        window.paint_quad(quad(
-                            bar_bounds,
-                            px(1.),
-                            color,
-                            px(0.),
-                            color,
-                            BorderStyle::default(),
-                        ));
+                        for col in 0..columns {
+                            let g_start = col * candle_count / columns;
+                            let g_end = ((col + 1) * candle_count / columns).max(g_start + 1);
+                            let group = &candles[g_start..g_end];
+                            let first = &group[0];
+                            let last = &group[group.len() - 1];
+                            let mut vol_sum = 0.0_f64;
+                            for c in group {
+                                vol_sum += c.volume;
+                            }
+
+                            let x = ox + (col as f32 + 0.5) * column_width;
+                            let bar_h = volume_bar_height(vol_sum, max_group_vol, height);
+                            let y = oy + height - bar_h;
+                            let color = if highlighted == Some(col) {
+                                rgb(HOVER_BAR_HEX)
+                            } else {
+                                rgb(theme.candle_color(last.close >= first.open))
+                            };
+
+                            let bar_bounds = Bounds {
+                                origin: point(px(x - bar_width * 0.5), px(y)),
+                                size: size(px(bar_width), px(bar_h)),
+                            };
+                            window.paint_quad(quad(
+                                bar_bounds,
+                                px(1.),
+                                color,
+                                px(0.),
+                                color,
+                                BorderStyle::default(),
+                            ));
+                        }
                     }
                 }
-            }
 
-            if let Some(local_idx) = hover_local {
-                let x = if let Some(x) = hover_x {
-                    x.clamp(ox, ox + width)
-                } else {
-                    x_for_idx(local_idx.min(candle_count.saturating_sub(1)))
-                };
-                let mut builder = PathBuilder::stroke(px(1.));
-                builder.move_to(point(px(x), px(oy)));
-                builder.line_to(point(px(x), px(oy + height)));
-                if let Ok(path) = builder.build() {
-                    window.paint_path(path, rgb(0xf59e0b));
+                if let Some(local_idx) = hover_local {
+                    let x = if let Some(x) = hover_x {
+                        x.clamp(ox, ox + pane_width)
+                    } else {
+                        x_for_idx(local_idx.min(candle_count.saturating_sub(1)))
+                    };
+                    let mut builder = PathBuilder::stroke(px(1.));
+                    builder.move_to(point(px(x), px(oy)));
+                    builder.line_to(point(px(x), px(oy + height)));
+                    if let Ok(path) = builder.build() {
+                        window.paint_path(path, rgb(0xf59e0b));
+                    }
                 }
-            }
+            });
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gridlines_span_the_pane_at_the_requested_density() {
+        assert_eq!(gridline_ys(10.0, 100.0, 3), vec![10.0, 60.0, 110.0]);
+        let ys = gridline_ys(0.0, 400.0, 9);
+        assert_eq!(ys.len(), 9);
+        assert!(ys.windows(2).all(|w| (w[1] - w[0] - 50.0).abs() < 1e-4));
+        assert_eq!(gridline_ys(0.0, 100.0, 0).len(), 2);
+
+        assert_eq!(default_grid_lines(0.0), 3);
+        assert_eq!(default_grid_lines(400.0), 6);
+        assert_eq!(default_grid_lines(10_000.0), 16);
+    }
+
+    #[test]
+    fn non_finite_prices_lay_out_finite_geometry() {
+        let at = |minute: i64, high: f64, close: f64| {
+            Candle::new(
+                time::macros::datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(minute),
+                10.0,
+                high,
+                9.0,
+                close,
+                1.0,
+            )
+        };
+        let candles = [
+            at(0, 11.0, 10.5),
+            at(1, 11.0, f64::NAN),
+            at(2, f64::INFINITY, 10.0),
+        ];
+        let domain = core::bounds(&candles).expect("finite prices remain");
+        assert_eq!(domain, (9.0, 11.0));
+
+        let marks = candle_marks(
+            &candles,
+            None,
+            (0.0, 0.0, 300.0, 200.0),
+            domain,
+            false,
+            XAxisMode::Ordinal,
+            &ChartTheme::default(),
+        );
+        assert_eq!(marks.len(), candles.len());
+        for mark in &marks {
+            for y in [mark.high_y, mark.low_y, mark.body_top] {
+                assert!((0.0..=200.0).contains(&y), "{mark:?}");
+            }
+            assert!(mark.x.is_finite() && mark.body_width.is_finite());
+            assert!(mark.body_height.is_finite(), "{mark:?}");
+        }
+        assert_eq!(price_to_y(f64::NAN, domain, 0.0, 200.0), 200.0);
+        assert_eq!(price_to_y(f64::INFINITY, domain, 0.0, 200.0), 0.0);
+        assert_eq!(price_to_y(10.0, (f64::NAN, f64::NAN), 0.0, 200.0), 200.0);
+    }
+
+    #[test]
+    fn wick_color_follows_direction_under_a_configured_theme() {
+        let at = |open: f64, close: f64| {
+            Candle::new(
+                time::macros::datetime!(2024-01-01 00:00:00 UTC),
+                open,
+                110.0,
+                90.0,
+                close,
+                1.0,
+            )
+        };
+        let candles = [at(95.0, 105.0), at(105.0, 95.0), at(100.0, 100.2)];
+        let wicks = |theme: &ChartTheme| -> Vec<(u32, u32)> {
+            candle_marks(
+                &candles,
+                None,
+                (0.0, 0.0, 300.0, 100.0),
+                (90.0, 110.0),
+                false,
+                XAxisMode::Ordinal,
+                theme,
+            )
+            .iter()
+            .map(|mark| (mark.color, mark.wick_color))
+            .collect()
+        };
+
+        let default = ChartTheme::default();
+        assert!(
+            wicks(&default)
+                .iter()
+                .all(|&(_, wick)| wick == default.wick_up)
+        );
+
+        let theme = ChartTheme {
+            doji_threshold: 0.05,
+            wick_up: 0x111111,
+            wick_down: 0x222222,
+            wick_neutral: 0x333333,
+            ..ChartTheme::default()
+        };
+        assert_eq!(
+            wicks(&theme),
+            vec![
+                (theme.up, 0x111111),
+                (theme.down, 0x222222),
+                (theme.neutral, 0x333333),
+            ]
+        );
+    }
+
+    #[test]
+    fn candle_gap_narrows_bodies_of_sparse_candles() {
+        let candles: Vec<Candle> = (0..4)
+            .map(|i| {
+                Candle::new(
+                    time::macros::datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                    100.0,
+                    110.0,
+                    90.0,
+                    105.0,
+                    1.0,
+                )
+            })
+            .collect();
+        // Four candles across 20px: 5px slots, 3px bodies by ratio alone.
+        let layout = |theme: &ChartTheme| {
+            candle_marks(
+                &candles,
+                None,
+                (0.0, 0.0, 20.0, 100.0),
+                (90.0, 110.0),
+                false,
+                XAxisMode::Ordinal,
+                theme,
+            )
+        };
+        let gap_between = |marks: &[CandleMark]| -> f32 {
+            marks
+                .windows(2)
+                .map(|w| (w[1].x - w[1].body_width * 0.5) - (w[0].x + w[0].body_width * 0.5))
+                .fold(f32::MAX, f32::min)
+        };
+
+        let plain = layout(&ChartTheme::default());
+        assert!((plain[0].body_width - 3.0).abs() < 1e-4);
+
+        let theme = ChartTheme {
+            candle_gap_px: 3.0,
+            ..ChartTheme::default()
+        };
+        let gapped = layout(&theme);
+        assert!((gapped[0].body_width - 2.0).abs() < 1e-4);
+        assert!(gap_between(&gapped) >= 3.0 - 1e-4);
+
+        // A gap that would leave under 1px of body falls back to the ratio.
+        assert!((body_width(5.0, 4.5) - 3.0).abs() < 1e-4);
+        assert!((body_width(100.0, 3.0) - 60.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn configured_body_border_differs_from_fill() {
+        let default = body_style(0x22c55e, &ChartTheme::default(), false);
+        assert_eq!(default.fill, Some(0x22c55e));
+        assert_eq!((default.border, default.border_width), (0x22c55e, 0.0));
+
+        let theme = ChartTheme {
+            body_border: Some(0xf8fafc),
+            body_border_width: 1.5,
+            ..ChartTheme::default()
+        };
+        let style = body_style(theme.candle_color(false), &theme, false);
+        assert_eq!(style.fill, Some(0xef4444));
+        assert_eq!(style.border, 0xf8fafc);
+        assert_eq!(style.border_width, 1.5);
+        assert!(!style.dashed);
+
+        let forming = body_style(theme.candle_color(true), &theme, true);
+        assert_eq!(forming.fill, None);
+        assert!(forming.dashed && forming.border_width >= 1.0);
+    }
+
+    #[test]
+    fn doji_candles_use_neutral_color_under_threshold() {
+        let at = |open: f64, close: f64| {
+            Candle::new(
+                time::macros::datetime!(2024-01-01 00:00:00 UTC),
+                open,
+                110.0,
+                90.0,
+                close,
+                1.0,
+            )
+        };
+        // Bodies of 0.5 and 5.0 against a 20.0 range: 2.5% and 25%.
+        let candles = [at(100.0, 100.5), at(100.0, 95.0)];
+        let colors = |theme: &ChartTheme| -> Vec<u32> {
+            candle_marks(
+                &candles,
+                None,
+                (0.0, 0.0, 200.0, 100.0),
+                (80.0, 120.0),
+                false,
+                XAxisMode::Ordinal,
+                theme,
+            )
+            .iter()
+            .map(|mark| mark.color)
+            .collect()
+        };
+
+        let plain = ChartTheme::default();
+        assert_eq!(colors(&plain), vec![plain.up, plain.down]);
+
+        let doji = ChartTheme {
+            doji_threshold: 0.05,
+            ..ChartTheme::default()
+        };
+        assert_eq!(colors(&doji), vec![doji.neutral, doji.down]);
+        // A flat candle has no range at all and still counts as a doji.
+        assert_eq!(doji.ohlc_color(100.0, 100.0, 100.0, 100.0), doji.neutral);
+    }
+
+    #[test]
+    fn guarded_paint_skips_degenerate_bounds() {
+        let mut calls = 0;
+        for (w, h) in [(0.0, 100.0), (100.0, 0.0), (-5.0, 10.0), (f32::NAN, 10.0)] {
+            let bounds = Bounds {
+                origin: point(px(10.), px(20.)),
+                size: size(px(w), px(h)),
+            };
+            assert!(!paint_guarded(bounds, |_, _, _, _| calls += 1));
+        }
+        assert_eq!(calls, 0);
+
+        let bounds = Bounds {
+            origin: point(px(10.), px(20.)),
+            size: size(px(300.), px(200.)),
+        };
+        let mut seen = None;
+        assert!(paint_guarded(bounds, |ox, oy, w, h| seen = Some((ox, oy, w, h))));
+        assert_eq!(seen, Some((10.0, 20.0, 300.0, 200.0)));
+    }
+
+    #[test]
+    fn right_padding_leaves_empty_slots_after_last_candle() {
+        let (ox, width, count) = (10.0, 300.0, 10);
+        let last_x = |padding: usize| {
+            let plot = plot_width(width, count, padding);
+            ox + (count as f32 - 0.5) / count as f32 * plot
+        };
+        let slot = width / 15.0;
+        assert!((ox + width - last_x(5) - 5.5 * slot).abs() < 1e-3);
+        assert!((ox + width - last_x(0) - 0.5 * width / count as f32).abs() < 1e-3);
+        assert_eq!(plot_width(width, 0, 5), width);
+    }
+
+    #[test]
+    fn dense_views_use_quad_wicks() {
+        assert_eq!(wick_style(true, 1_000, 1_000.0), WickStyle::Quad);
+        assert_eq!(wick_style(true, 100, 1_000.0), WickStyle::Stroke);
+        assert_eq!(wick_style(false, 1_000, 1_000.0), WickStyle::Stroke);
+        assert_eq!(wick_style(true, 0, 1_000.0), WickStyle::Stroke);
+    }
+
+    #[test]
+    fn hovered_bar_matches_candle_grouping() {
+        assert_eq!(hovered_bar(Some(3), 10, 10), Some(3));
+        assert_eq!(hovered_bar(Some(3), 10, 40), Some(3));
+        assert_eq!(hovered_bar(None, 10, 10), None);
+        assert_eq!(hovered_bar(Some(10), 10, 10), None);
+
+        let (candle_count, columns) = (1_000, 37);
+        for local in 0..candle_count {
+            let col = hovered_bar(Some(local), candle_count, columns).unwrap();
+            let g_start = col * candle_count / columns;
+            let g_end = ((col + 1) * candle_count / columns).max(g_start + 1);
+            assert!(
+                g_start <= local && local < g_end,
+                "candle {local} not in bar {col}"
+            );
+        }
+    }
+
+    #[test]
+    fn time_axis_spaces_candles_by_timestamp() {
+        let at = |minute: i64| {
+            Candle::new(
+                time::macros::datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(minute),
+                10.0,
+                11.0,
+                9.0,
+                10.5,
+                1.0,
+            )
+        };
+        // A one-hour hole between the third and fourth candle.
+        let candles = [at(0), at(1), at(2), at(62), at(63)];
+        let xs = |mode| -> Vec<f32> {
+            candle_marks(
+                &candles,
+                None,
+                (0.0, 0.0, 300.0, 100.0),
+                (8.0, 12.0),
+                false,
+                mode,
+                &ChartTheme::default(),
+            )
+            .iter()
+            .map(|mark| mark.x)
+            .collect()
+        };
+
+        let time = xs(XAxisMode::Time);
+        let adjacent = time[1] - time[0];
+        let across_gap = time[3] - time[2];
+        assert!(across_gap > 10.0 * adjacent, "{time:?}");
+        assert!(time.iter().all(|x| (0.0..=300.0).contains(x)), "{time:?}");
+
+        let ordinal = xs(XAxisMode::Ordinal);
+        assert!((ordinal[3] - ordinal[2] - (ordinal[1] - ordinal[0])).abs() < 1e-3);
+    }
+}
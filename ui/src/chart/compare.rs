@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use core::Candle;
+
+use super::canvas::price_to_y;
+
+/// How a compare series is scaled against the primary price pane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AxisMode {
+    /// Drawn on the primary's price axis.
+    #[default]
+    Shared,
+    /// Mapped through its own min/max and labelled on a second, right-side axis.
+    Independent,
+}
+
+/// Second symbol overlaid on the price pane for spread/ratio analysis.
+#[derive(Debug, Clone)]
+pub(crate) struct CompareSeries {
+    pub(crate) source: String,
+    pub(crate) candles: Arc<[Candle]>,
+    pub(crate) axis_mode: AxisMode,
+}
+
+/// Compare closes aligned to `primary`: the latest `secondary` close at or before each
+/// primary timestamp, `None` before the compare series starts.
+pub(crate) fn aligned_closes(primary: &[Candle], secondary: &[Candle]) -> Vec<Option<f64>> {
+    primary
+        .iter()
+        .map(|candle| {
+            let idx = secondary.partition_point(|c| c.timestamp <= candle.timestamp);
+            idx.checked_sub(1).map(|i| secondary[i].close)
+        })
+        .collect()
+}
+
+/// Everything the price canvas needs to draw the compare line.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CompareLine {
+    pub(crate) closes: Vec<Option<f64>>,
+    pub(crate) axis_mode: AxisMode,
+    pub(crate) price_min: f64,
+    pub(crate) price_max: f64,
+}
+
+impl CompareLine {
+    /// `Shared` reuses the primary pane's bounds; `Independent` pads the compare closes' own
+    /// min/max the same way the price pane does.
+    pub(crate) fn new(
+        closes: Vec<Option<f64>>,
+        axis_mode: AxisMode,
+        primary_bounds: (f64, f64),
+    ) -> Self {
+        let (price_min, price_max) = match axis_mode {
+            AxisMode::Shared => primary_bounds,
+            AxisMode::Independent => {
+                let (min, mut max) = closes
+                    .iter()
+                    .flatten()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &close| {
+                        (min.min(close), max.max(close))
+                    });
+                if !min.is_finite() || !max.is_finite() {
+                    (0.0, 1.0)
+                } else {
+                    if min == max {
+                        max = min + 1.0;
+                    }
+                    let pad = (max - min) * 0.01;
+                    (min - pad, max + pad)
+                }
+            }
+        };
+        Self {
+            closes,
+            axis_mode,
+            price_min,
+            price_max,
+        }
+    }
+
+    /// Y position of `price` in a pane spanning `top..top + height`.
+    pub(crate) fn y_for(&self, price: f64, top: f32, height: f32) -> f32 {
+        price_to_y(price, (self.price_min, self.price_max), top, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use time::{Duration, macros::datetime};
+
+    fn series(closes: &[f64], offset_secs: i64) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| {
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC)
+                        + Duration::minutes(i as i64)
+                        + Duration::seconds(offset_secs),
+                    close,
+                    close,
+                    close,
+                    close,
+                    1.0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn closes_align_to_latest_compare_candle_at_or_before_each_primary() {
+        let primary = series(&[100.0, 101.0, 102.0], 0);
+        let secondary = series(&[10.0, 11.0], 30);
+        assert_eq!(
+            aligned_closes(&primary, &secondary),
+            vec![None, Some(10.0), Some(11.0)]
+        );
+    }
+
+    #[test]
+    fn independent_axis_maps_through_compare_bounds() {
+        let closes = vec![Some(10.0), Some(15.0), Some(20.0)];
+        let primary_bounds = (100.0, 200.0);
+
+        let independent = CompareLine::new(closes.clone(), AxisMode::Independent, primary_bounds);
+        assert!(independent.price_min < 10.0 && independent.price_min > 9.8);
+        assert!(independent.price_max > 20.0 && independent.price_max < 20.2);
+        assert!((independent.y_for(15.0, 0.0, 100.0) - 50.0).abs() < 1e-3);
+        assert!(independent.y_for(20.0, 0.0, 100.0) < 1.0);
+
+        // On the shared axis the same closes sit below the primary's range.
+        let shared = CompareLine::new(closes, AxisMode::Shared, primary_bounds);
+        assert_eq!((shared.price_min, shared.price_max), primary_bounds);
+        assert_eq!(shared.y_for(15.0, 0.0, 100.0), 100.0);
+    }
+}
@@ -4,19 +4,28 @@ use gpui::{App, Bounds, WindowBounds, WindowOptions, prelude::*, px, size};
 
 mod aggregation;
 mod canvas;
+mod compare;
 mod error_view;
 mod footer;
 mod header;
+mod raster;
+mod spread;
+mod theme;
 mod view;
 
 use error_view::ErrorView;
 
-pub use view::ChartView;
+pub use canvas::XAxisMode;
+pub use compare::AxisMode;
+pub use theme::ChartTheme;
+pub use view::{ChartView, TooltipPlacement};
 
 #[derive(Clone)]
 pub struct ChartMeta {
     pub source: String,
     pub initial_interval: Option<Interval>,
+    /// Show only the last N candles on first load; `None` shows the whole dataset.
+    pub initial_visible: Option<usize>,
 }
 
 pub fn launch_chart(candles: Result<Vec<Candle>, String>, meta: ChartMeta) {
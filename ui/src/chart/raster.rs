@@ -0,0 +1,61 @@
+//! Software rasterizer for exporting the price pane without a window.
+
+use image::{Rgba, RgbaImage};
+
+use super::canvas::CandleMark;
+use super::theme::ChartTheme;
+
+const GRIDLINE_HEX: u32 = 0x1f2937;
+
+pub(super) fn pixel(hex: u32) -> Rgba<u8> {
+    let [_, r, g, b] = hex.to_be_bytes();
+    Rgba([r, g, b, 0xff])
+}
+
+/// Fills the pixels covered by `[x0, x1) × [y0, y1)`, clipped to the image.
+fn fill_rect(image: &mut RgbaImage, (x0, y0, x1, y1): (f32, f32, f32, f32), color: Rgba<u8>) {
+    let clamp_x = |v: f32| v.round().clamp(0.0, image.width() as f32) as u32;
+    let clamp_y = |v: f32| v.round().clamp(0.0, image.height() as f32) as u32;
+    let (x0, x1) = (clamp_x(x0), clamp_x(x1).max(clamp_x(x0) + 1));
+    let (y0, y1) = (clamp_y(y0), clamp_y(y1).max(clamp_y(y0) + 1));
+    for y in y0..y1.min(image.height()) {
+        for x in x0..x1.min(image.width()) {
+            image.put_pixel(x, y, color);
+        }
+    }
+}
+
+/// Paints gridlines, wicks and bodies the way `chart_canvas` does, minus hover and
+/// overlays. Forming candles get an outline instead of a fill.
+pub(super) fn paint_price_pane(
+    image: &mut RgbaImage,
+    marks: &[CandleMark],
+    gridlines: &[f32],
+    theme: &ChartTheme,
+) {
+    let width = image.width() as f32;
+    for &y in gridlines {
+        fill_rect(image, (0.0, y - 0.5, width, y + 0.5), pixel(GRIDLINE_HEX));
+    }
+    for mark in marks {
+        let color = pixel(mark.color);
+        fill_rect(
+            image,
+            (mark.x - 0.5, mark.high_y, mark.x + 0.5, mark.low_y),
+            pixel(mark.wick_color),
+        );
+        let left = mark.x - mark.body_width * 0.5;
+        let right = mark.x + mark.body_width * 0.5;
+        let top = mark.body_top;
+        let bottom = mark.body_top + mark.body_height;
+        if mark.forming {
+            let border = pixel(theme.body_border.unwrap_or(mark.color));
+            fill_rect(image, (left, top, right, top + 1.0), border);
+            fill_rect(image, (left, bottom - 1.0, right, bottom), border);
+            fill_rect(image, (left, top, left + 1.0, bottom), border);
+            fill_rect(image, (right - 1.0, top, right, bottom), border);
+        } else {
+            fill_rect(image, (left, top, right, bottom), color);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Bid/ask quotes drawn as a faint band around the close.
+
+use core::{Candle, CandleExt};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SpreadQuote {
+    pub(crate) timestamp: OffsetDateTime,
+    pub(crate) bid: f64,
+    pub(crate) ask: f64,
+}
+
+/// Loaded rows that carry both a bid and an ask, in timestamp order.
+pub(crate) fn spread_quotes(rows: &[CandleExt]) -> Vec<SpreadQuote> {
+    let mut quotes: Vec<SpreadQuote> = rows
+        .iter()
+        .filter_map(|row| {
+            Some(SpreadQuote {
+                timestamp: row.candle.timestamp,
+                bid: row.bid?,
+                ask: row.ask?,
+            })
+        })
+        .collect();
+    quotes.sort_by_key(|quote| quote.timestamp);
+    quotes
+}
+
+/// `(bid, ask)` for each of `candles[start..end]`: the latest quote before the next candle
+/// opens, so a resampled bucket shows the spread at its close. `None` before the first quote.
+pub(crate) fn aligned_spreads(
+    candles: &[Candle],
+    start: usize,
+    end: usize,
+    quotes: &[SpreadQuote],
+) -> Vec<Option<(f64, f64)>> {
+    (start..end.min(candles.len()))
+        .map(|idx| {
+            let upto = match candles.get(idx + 1) {
+                Some(next) => quotes.partition_point(|q| q.timestamp < next.timestamp),
+                None => quotes.len(),
+            };
+            upto.checked_sub(1).map(|i| (quotes[i].bid, quotes[i].ask))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn spreads_align_to_each_bucket_close() {
+        let at = |minute: i64| datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(minute);
+        let row = |minute: i64, bid: Option<f64>, ask: Option<f64>| CandleExt {
+            candle: Candle::new(at(minute), 1.0, 1.0, 1.0, 1.0, 1.0),
+            bid,
+            ask,
+        };
+        let quotes = spread_quotes(&[
+            row(3, Some(1.3), Some(1.4)),
+            row(1, Some(1.1), Some(1.2)),
+            row(2, None, Some(9.0)),
+            row(7, Some(1.7), Some(1.8)),
+        ]);
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[0].timestamp, at(1));
+
+        // Five-minute buckets at 0, 5 and 10.
+        let buckets: Vec<Candle> = [0, 5, 10]
+            .into_iter()
+            .map(|minute| Candle::new(at(minute), 1.0, 1.0, 1.0, 1.0, 1.0))
+            .collect();
+        assert_eq!(
+            aligned_spreads(&buckets, 0, 3, &quotes),
+            vec![Some((1.3, 1.4)), Some((1.7, 1.8)), Some((1.7, 1.8))]
+        );
+        assert_eq!(aligned_spreads(&buckets, 1, 2, &[]), vec![None]);
+    }
+}
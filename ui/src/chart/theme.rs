@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+/// Colors and stroke widths used when painting candles.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChartTheme {
+    /// Fill behind the price and volume panes.
+    #[serde(default = "default_background")]
+    pub background: u32,
+    pub up: u32,
+    pub down: u32,
+    /// Body fill for doji candles; see [`doji_threshold`](Self::doji_threshold).
+    #[serde(default = "default_neutral")]
+    pub neutral: u32,
+    /// Candles whose body is at most this fraction of their high-low range are drawn in
+    /// [`neutral`](Self::neutral); `0.0` colors every candle up or down.
+    #[serde(default)]
+    pub doji_threshold: f64,
+    /// Body outline color; `None` outlines each body in its own fill color.
+    pub body_border: Option<u32>,
+    /// Body outline width in px; `0.0` draws no outline.
+    pub body_border_width: f32,
+    /// Wick colors, chosen by direction like the body fill.
+    #[serde(default = "default_wick")]
+    pub wick_up: u32,
+    #[serde(default = "default_wick")]
+    pub wick_down: u32,
+    #[serde(default = "default_wick")]
+    pub wick_neutral: u32,
+    /// Minimum px between neighbouring bodies; bodies narrow to keep it while at least 1px
+    /// of body remains. `0.0` keeps the plain 60%-of-slot bodies.
+    #[serde(default)]
+    pub candle_gap_px: f32,
+}
+
+impl Default for ChartTheme {
+    fn default() -> Self {
+        Self {
+            background: default_background(),
+            up: 0x22c55e,
+            down: 0xef4444,
+            neutral: default_neutral(),
+            doji_threshold: 0.0,
+            body_border: None,
+            body_border_width: 0.0,
+            wick_up: default_wick(),
+            wick_down: default_wick(),
+            wick_neutral: default_wick(),
+            candle_gap_px: 0.0,
+        }
+    }
+}
+
+fn default_background() -> u32 {
+    0x0b1220
+}
+
+fn default_neutral() -> u32 {
+    0x9ca3af
+}
+
+fn default_wick() -> u32 {
+    0xe5e7eb
+}
+
+impl ChartTheme {
+    /// Names accepted by [`preset`](Self::preset), in settings order.
+    pub const PRESETS: [&'static str; 5] =
+        ["Dark", "Light", "Solarized", "Monochrome", "Accessible"];
+
+    /// Named color scheme; unknown names (case-insensitive) fall back to `Dark`.
+    pub fn preset(name: &str) -> ChartTheme {
+        let dark = ChartTheme::default();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "light" => ChartTheme {
+                background: 0xf8fafc,
+                up: 0x16a34a,
+                down: 0xdc2626,
+                ..dark
+            },
+            "solarized" => ChartTheme {
+                background: 0x002b36,
+                up: 0x859900,
+                down: 0xdc322f,
+                ..dark
+            },
+            "monochrome" => ChartTheme {
+                background: 0x111111,
+                up: 0xe5e5e5,
+                down: 0x525252,
+                neutral: 0x8a8a8a,
+                body_border: Some(0xe5e5e5),
+                body_border_width: 1.0,
+                ..dark
+            },
+            // Okabe-Ito blue/orange stay distinct under red-green color blindness.
+            "accessible" => ChartTheme {
+                up: 0x0072b2,
+                down: 0xe69f00,
+                ..dark
+            },
+            _ => dark,
+        }
+    }
+
+    /// Body fill for a candle that closed at or above its open (`rising`) or below it.
+    pub fn candle_color(&self, rising: bool) -> u32 {
+        if rising { self.up } else { self.down }
+    }
+
+    /// Body fill for a candle: [`neutral`](Self::neutral) for dojis under
+    /// [`doji_threshold`](Self::doji_threshold), otherwise [`candle_color`](Self::candle_color).
+    pub fn ohlc_color(&self, open: f64, high: f64, low: f64, close: f64) -> u32 {
+        if self.is_doji(open, high, low, close) {
+            self.neutral
+        } else {
+            self.candle_color(close >= open)
+        }
+    }
+
+    /// Wick color for a candle, picked the same way as [`ohlc_color`](Self::ohlc_color).
+    pub fn wick_color(&self, open: f64, high: f64, low: f64, close: f64) -> u32 {
+        if self.is_doji(open, high, low, close) {
+            self.wick_neutral
+        } else if close >= open {
+            self.wick_up
+        } else {
+            self.wick_down
+        }
+    }
+
+    fn is_doji(&self, open: f64, high: f64, low: f64, close: f64) -> bool {
+        self.doji_threshold > 0.0
+            && (close - open).abs() <= (high - low).max(0.0) * self.doji_threshold
+    }
+}
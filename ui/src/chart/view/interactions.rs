@@ -1,24 +1,64 @@
-use gpui::{MouseButton, MouseMoveEvent, ScrollWheelEvent, Window, px};
+use std::time::{Duration, Instant};
 
+use gpui::{Bounds, MouseButton, MouseMoveEvent, Pixels, ScrollWheelEvent, Window, px};
+
+use super::super::canvas::{XAxisMode, slot_xs};
 use super::ChartView;
 
+/// Scroll events this close to the first one of a burst share one zoom budget.
+const ZOOM_WINDOW: Duration = Duration::from_millis(120);
+/// Largest zoom change (either way) a burst of scroll events can make.
+pub(super) const MAX_ZOOM_PER_WINDOW: f32 = 1.5;
+
 impl ChartView {
     pub(super) fn handle_scroll(&mut self, event: &ScrollWheelEvent, window: &mut Window) {
         if self.settings_open || self.symbol_search_open || self.candles.is_empty() {
             return;
         }
         let delta = event.delta.pixel_delta(px(16.0));
+        let scroll_x = f32::from(delta.x);
         let scroll_y = f32::from(delta.y);
+        if scroll_x.abs() > scroll_y.abs() {
+            // Horizontal wheel pans, which matters once `min_candle_px` makes content scroll.
+            let Some(width) = self.chart_bounds.map(|b| f32::from(b.size.width)) else {
+                return;
+            };
+            let visible = self.visible_len();
+            let visible_count = visible.round().max(1.0) as usize;
+            let candle_px = (width / visible).max(f32::EPSILON);
+            self.view_offset =
+                self.clamp_offset(self.view_offset - scroll_x / candle_px, visible_count);
+            let _ = self.persist_viewport();
+            window.refresh();
+            return;
+        }
         if scroll_y.abs() < f32::EPSILON {
             return;
         }
         let center = self.view_offset + self.visible_len() * 0.5;
+        // Trackpads fire dozens of events per flick; cap the change per window of events.
+        let now = Instant::now();
+        let base_zoom = match self.zoom_window {
+            Some((started, base)) if now.duration_since(started) < ZOOM_WINDOW => base,
+            _ => {
+                self.zoom_window = Some((now, self.zoom));
+                self.zoom
+            }
+        };
         let zoom_factor = if scroll_y < 0.0 { 1.1 } else { 0.9 };
-        self.zoom = (self.zoom * zoom_factor).clamp(1.0, self.candles.len() as f32);
+        let min_zoom = self.min_zoom();
+        let max_zoom = (self.candles.len() as f32).max(min_zoom);
+        self.zoom = (self.zoom * zoom_factor)
+            .clamp(
+                base_zoom / MAX_ZOOM_PER_WINDOW,
+                base_zoom * MAX_ZOOM_PER_WINDOW,
+            )
+            .clamp(min_zoom, max_zoom);
         let new_visible = self.visible_len();
         let new_offset = center - new_visible * 0.5;
         let visible_count = new_visible.round().max(1.0) as usize;
         self.view_offset = self.clamp_offset(new_offset, visible_count);
+        self.refresh_auto_interval();
         let _ = self.persist_viewport();
         window.refresh();
     }
@@ -27,27 +67,58 @@ impl ChartView {
         if self.settings_open || self.symbol_search_open {
             return;
         }
-        if let (Some(bounds), true) = (
-            self.chart_bounds,
-            !self.candles.is_empty() && candle_count > 0,
-        ) {
+        if self.candles.is_empty() || candle_count == 0 {
+            return;
+        }
+        self.measure_held = event.modifiers.shift;
+        let px = f32::from(event.position.x);
+        let py = f32::from(event.position.y);
+        let contains = |bounds: Bounds<Pixels>| {
             let bx = f32::from(bounds.origin.x);
             let by = f32::from(bounds.origin.y);
             let bw = f32::from(bounds.size.width);
             let bh = f32::from(bounds.size.height);
-            let px = f32::from(event.position.x);
-            let py = f32::from(event.position.y);
-            if px >= bx && px <= bx + bw && py >= by && py <= by + bh {
-                let candle_width = (bw / candle_count as f32).max(1.0);
-                let local_x = (px - bx).max(0.0);
-                let local_idx = (local_x / candle_width).floor() as usize;
+            px >= bx && px <= bx + bw && py >= by && py <= by + bh
+        };
+
+        // Price and volume panes share one hover index so both highlight the same candle.
+        let pane = match (self.chart_bounds, self.volume_bounds) {
+            (Some(bounds), _) if contains(bounds) => Some((bounds, false)),
+            (_, Some(bounds)) if contains(bounds) => Some((bounds, true)),
+            (None, None) => return,
+            _ => None,
+        };
+        match pane {
+            Some((bounds, in_volume)) => {
+                let bx = f32::from(bounds.origin.x);
+                let bw = f32::from(bounds.size.width);
+                let plot = self.candle_area_width(bw, candle_count);
                 let start_idx = self.visible_range().0;
+                let local_idx = match self.x_axis_mode() {
+                    XAxisMode::Ordinal => {
+                        let candle_width = (plot / candle_count as f32).max(1.0);
+                        let local_x = (px - bx).max(0.0);
+                        ((local_x / candle_width).floor() as usize).min(candle_count - 1)
+                    }
+                    // Nearest slot centre, since gaps make slots uneven.
+                    XAxisMode::Time => {
+                        let end = (start_idx + candle_count).min(self.candles.len());
+                        let visible = &self.candles[start_idx.min(end)..end];
+                        let (xs, _) = slot_xs(visible, XAxisMode::Time, bx, plot);
+                        xs.windows(2)
+                            .take_while(|pair| (pair[0] + pair[1]) * 0.5 < px)
+                            .count()
+                    }
+                };
                 let idx = (start_idx + local_idx).min(self.candles.len().saturating_sub(1));
                 self.hover_index = Some(idx);
                 self.hover_position = Some((px, py));
-            } else {
+                self.hover_in_volume = in_volume;
+            }
+            None => {
                 self.hover_index = None;
                 self.hover_position = None;
+                self.hover_in_volume = false;
             }
         }
     }
@@ -7,8 +7,1289 @@ mod sections;
 mod state;
 mod widgets;
 
-pub use state::{ChartView, padded_bounds};
+pub use overlay::TooltipPlacement;
+pub use state::ChartView;
 pub const SIDEBAR_WIDTH: f32 = 320.0;
 pub const TOOLBAR_WIDTH: f32 = 56.0;
 pub const OVERLAY_GAP: f32 = 8.0;
 pub const INTERVAL_TRIGGER_WIDTH: f32 = 64.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use core::{Candle, DuckDbStore, Interval, StorageMode};
+    use gpui::{
+        Context, Entity, IntoElement, Render, TestAppContext, VisualTestContext, Window, div,
+        prelude::*,
+    };
+    use time::macros::{datetime, offset};
+
+    use crate::test_support::{minute_candle, minute_candles};
+    use crate::{ChartMeta, ChartTheme};
+
+    /// Windowed chart over `candles` with default meta and no store.
+    pub(super) fn test_view(
+        cx: &mut TestAppContext,
+        candles: Vec<Candle>,
+    ) -> (Entity<ChartView>, &mut VisualTestContext) {
+        cx.add_window_view(|_, cx| {
+            ChartView::new(
+                candles,
+                ChartMeta {
+                    source: "TEST".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                None,
+                cx,
+            )
+        })
+    }
+
+    struct HostView {
+        chart: Entity<ChartView>,
+    }
+
+    impl Render for HostView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .flex()
+                .size_full()
+                .child(div().w_1_4().child("host sidebar"))
+                .child(div().flex_1().child(self.chart.clone()))
+        }
+    }
+
+    #[gpui::test]
+    async fn embedded_chart_renders_inside_host_view(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = vec![
+            Candle {
+                timestamp: datetime!(2024-01-01 00:00:00 UTC),
+                open: 1.0,
+                high: 2.0,
+                low: 0.5,
+                close: 1.5,
+                volume: 10.0,
+            },
+            Candle {
+                timestamp: datetime!(2024-01-01 00:01:00 UTC),
+                open: 1.5,
+                high: 2.5,
+                low: 1.0,
+                close: 2.0,
+                volume: 15.0,
+            },
+        ];
+        let (host, cx) = cx.add_window_view(|_, cx| HostView {
+            chart: ChartView::embed(
+                candles,
+                ChartMeta {
+                    source: "EMBED".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                None,
+                cx,
+            ),
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+
+        let chart = host.update(cx, |host, _| host.chart.clone());
+        let (source, len) =
+            chart.update(cx, |chart, _| (chart.current_source(), chart.candles.len()));
+        assert_eq!(source, "EMBED");
+        assert_eq!(len, 2);
+    }
+
+    async fn render_with(cx: &TestAppContext, candles: Vec<Candle>) -> (usize, (usize, usize)) {
+        let mut cx = cx.clone();
+        let (chart, cx) = test_view(&mut cx, candles);
+        chart.update(cx, |chart, cx| {
+            chart.hover_index = Some(0);
+            chart.hover_position = Some((10.0, 10.0));
+            chart.set_replay_mode(true);
+            cx.notify();
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        chart.update(cx, |chart, _| (chart.candles.len(), chart.visible_range()))
+    }
+
+    #[gpui::test]
+    async fn empty_and_single_candle_datasets_render(cx: &TestAppContext) {
+        let (len, range) = render_with(cx, Vec::new()).await;
+        assert_eq!((len, range), (0, (0, 0)));
+
+        let one = vec![Candle {
+            timestamp: datetime!(2024-01-01 00:00:00 UTC),
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 0.0,
+        }];
+        let (len, range) = render_with(cx, one).await;
+        assert_eq!((len, range), (1, (0, 1)));
+    }
+
+    #[gpui::test]
+    async fn live_append_past_cap_trims_head_and_keeps_window(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(
+                minute_candles(100),
+                ChartMeta {
+                    source: "LIVE".to_string(),
+                    initial_interval: None,
+                    initial_visible: Some(20),
+                },
+                None,
+                cx,
+            )
+        });
+
+        let (before, after, len) = chart.update(cx, |chart, cx| {
+            chart.set_max_candles(Some(100));
+            chart.view_offset = 50.0;
+            let (start, end) = chart.visible_range();
+            let before = (chart.candles[start].timestamp, end - start);
+            chart.append_live_batch(1, (100..110).map(minute_candle).collect(), cx);
+            let (start, end) = chart.visible_range();
+            let after = (chart.candles[start].timestamp, end - start);
+            assert_eq!(
+                chart.in_progress().map(|c| c.timestamp),
+                Some(minute_candle(109).timestamp)
+            );
+            (before, after, chart.candles.len())
+        });
+
+        assert_eq!(len, 100);
+        assert_eq!(after, before);
+    }
+
+    #[gpui::test]
+    async fn in_progress_candle_merges_updates_until_next_interval(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let at = |minute: i64, open: f64, high: f64, low: f64, close: f64| Candle {
+            timestamp: datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(minute),
+            open,
+            high,
+            low,
+            close,
+            volume: 5.0,
+        };
+        let (chart, cx) = test_view(&mut cx, vec![at(0, 1.0, 2.0, 0.5, 1.5)]);
+
+        let (merged, finalized, len) = chart.update(cx, |chart, cx| {
+            chart.update_in_progress(at(1, 1.5, 1.8, 1.2, 1.6), cx);
+            chart.update_in_progress(at(1, 1.6, 2.4, 1.0, 2.2), cx);
+            let merged = chart.in_progress().cloned();
+            chart.update_in_progress(at(2, 2.2, 2.3, 2.1, 2.2), cx);
+            let finalized = chart.candles[1].clone();
+            (merged, finalized, chart.candles.len())
+        });
+
+        let merged = merged.expect("forming candle");
+        assert_eq!(merged.open, 1.5);
+        assert_eq!(merged.high, 2.4);
+        assert_eq!(merged.low, 1.0);
+        assert_eq!(merged.close, 2.2);
+        assert_eq!(merged.volume, 10.0);
+        assert_eq!(finalized, merged);
+        assert_eq!(len, 3);
+        chart.update(cx, |chart, _| {
+            assert_eq!(chart.in_progress().map(|c| c.close), Some(2.2));
+            assert_eq!(chart.in_progress().map(|c| c.volume), Some(5.0));
+        });
+    }
+
+    #[gpui::test]
+    async fn append_candles_matches_full_replace(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let all: Vec<Candle> = (0..19)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.7).sin() * 5.0;
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                    close - 0.5,
+                    close + 1.0 + i as f64 * 0.1,
+                    close - 1.0,
+                    close,
+                    10.0 + i as f64,
+                )
+            })
+            .collect();
+        let view = |candles: Vec<Candle>, cx: &mut Context<ChartView>| {
+            let mut chart = ChartView::new(
+                candles,
+                ChartMeta {
+                    source: "SYN".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                None,
+                cx,
+            );
+            chart.apply_interval(Some(Interval::Minute(5)), false);
+            chart
+        };
+        let head = all[..12].to_vec();
+        let (incremental, cx) = cx.add_window_view(|_, cx| view(head, cx));
+        let full = cx.new(|cx| view(Vec::new(), cx));
+
+        incremental.update(cx, |chart, _| chart.append_candles(all[12..].to_vec()));
+        full.update(cx, |chart, _| {
+            chart.replace_data(all.clone(), "SYN".to_string(), false, false)
+        });
+
+        for interval in [Some(Interval::Minute(5)), None, Some(Interval::Minute(15))] {
+            let snapshot = |chart: &mut ChartView| {
+                chart.apply_interval(interval, false);
+                let len = chart.candles.len();
+                (chart.candles.to_vec(), chart.visible_padded_bounds(0, len))
+            };
+            let a = incremental.update(cx, |chart, _| snapshot(chart));
+            let b = full.update(cx, |chart, _| snapshot(chart));
+            assert_eq!(a, b, "interval {interval:?}");
+        }
+        incremental.update(cx, |chart, _| assert_eq!(chart.base_candles.len(), 19));
+    }
+
+    #[gpui::test]
+    async fn interval_change_reframes_range_or_keeps_time_window(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        // Three days of one-minute candles.
+        let candles: Vec<Candle> = (0..3 * 1_440)
+            .map(|i| {
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                    1.0,
+                    2.0,
+                    0.5,
+                    1.5,
+                    1.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            assert!(chart.reset_view_on_interval());
+            chart.apply_range_index(0, false);
+            chart.zoom = 1.0;
+            chart.view_offset = 0.0;
+
+            // On: "1D" is carried over and framed on the 5m series (288 buckets + the edge).
+            chart.apply_interval(Some(Interval::Minute(5)), false);
+            assert_eq!(chart.quick_ranges()[chart.active_range_index].0, "1D");
+            assert_eq!(chart.visible_range(), (575, 864));
+
+            // Off: the first hour of day two stays in view on the 15m series.
+            chart.set_reset_view_on_interval(false);
+            chart.apply_interval(None, false);
+            chart.zoom = 4_320.0 / 60.0;
+            chart.view_offset = 1_440.0;
+            assert_eq!(chart.visible_range(), (1_440, 1_500));
+            chart.apply_interval(Some(Interval::Minute(15)), false);
+            assert_eq!(chart.visible_range(), (96, 100));
+        });
+    }
+
+    #[gpui::test]
+    async fn rapid_scroll_events_zoom_at_most_one_step_budget(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = minute_candles(1_000);
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        let scroll = |dy: f32| gpui::ScrollWheelEvent {
+            delta: gpui::ScrollDelta::Pixels(gpui::point(gpui::px(0.), gpui::px(dy))),
+            ..Default::default()
+        };
+        chart.update_in(cx, |chart, window, _| {
+            chart.zoom = 1.0;
+            for _ in 0..10 {
+                chart.handle_scroll(&scroll(-4.0), window);
+            }
+            // Unthrottled this would be 1.1^10 ≈ 2.59.
+            assert!(chart.zoom > 1.0);
+            assert!(
+                chart.zoom <= interactions::MAX_ZOOM_PER_WINDOW + 1e-4,
+                "{}",
+                chart.zoom
+            );
+
+            let zoomed = chart.zoom;
+            for _ in 0..10 {
+                chart.handle_scroll(&scroll(4.0), window);
+            }
+            assert!(chart.zoom >= 1.0 / interactions::MAX_ZOOM_PER_WINDOW - 1e-4);
+            assert!(chart.zoom < zoomed);
+        });
+    }
+
+    #[gpui::test]
+    async fn auto_interval_coarsens_when_zooming_out(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        // Two days of one-minute candles.
+        let candles: Vec<Candle> = (0..2 * 1_440)
+            .map(|i| {
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                    1.0,
+                    2.0,
+                    0.5,
+                    1.5,
+                    1.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            // 100 raw candles fit the fallback 150-candle target, so no aggregation.
+            chart.zoom = 2_880.0 / 100.0;
+            chart.view_offset = 1_000.0;
+            chart.set_auto_interval(true);
+            assert_eq!(chart.current_interval(), None);
+            assert_eq!(chart.visible_range(), (1_000, 1_100));
+
+            // Zoomed all the way out, 48h needs 30m candles to stay within 150.
+            chart.zoom = 1.0;
+            chart.view_offset = 0.0;
+            chart.refresh_auto_interval();
+            assert_eq!(chart.current_interval(), Some(Interval::Minute(30)));
+            assert_eq!(chart.visible_range(), (0, 96));
+
+            // A manual pick leaves auto mode.
+            chart.set_auto_interval(false);
+            chart.apply_interval(Some(Interval::Minute(5)), false);
+            chart.zoom = 1.0;
+            chart.refresh_auto_interval();
+            assert_eq!(chart.current_interval(), Some(Interval::Minute(5)));
+        });
+    }
+
+    #[gpui::test]
+    async fn min_candle_px_makes_content_wider_than_viewport(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = minute_candles(1_000);
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        let (unbounded, bounded, range) = chart.update(cx, |chart, _| {
+            chart.chart_bounds = Some(gpui::Bounds {
+                origin: gpui::point(gpui::px(0.0), gpui::px(0.0)),
+                size: gpui::size(gpui::px(200.0), gpui::px(100.0)),
+            });
+            chart.zoom = 1.0;
+            let unbounded = chart.content_width();
+            chart.set_min_candle_px(Some(4.0));
+            (unbounded, chart.content_width(), chart.visible_range())
+        });
+
+        assert_eq!(unbounded, Some(200.0));
+        let bounded = bounded.expect("content width");
+        assert!(bounded > 200.0);
+        assert_eq!(bounded, 4.0 * 1_000.0);
+        assert_eq!(range.1 - range.0, 50);
+    }
+
+    #[gpui::test]
+    async fn visible_csv_round_trips_through_load_csv_reader(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..10)
+            .map(|i| Candle {
+                timestamp: datetime!(2024-01-01 09:30:00 UTC) + time::Duration::minutes(i),
+                open: 100.0 + i as f64 * 0.1,
+                high: 101.25 + i as f64,
+                low: 99.5 - i as f64 * 0.01,
+                close: 100.3 + i as f64 / 3.0,
+                volume: 1_000.0 + i as f64,
+            })
+            .collect();
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(
+                candles,
+                ChartMeta {
+                    source: "COPY".to_string(),
+                    initial_interval: None,
+                    initial_visible: Some(4),
+                },
+                None,
+                cx,
+            )
+        });
+
+        let (csv, visible) = chart.update(cx, |chart, _| {
+            (chart.visible_csv(), chart.visible_candles().to_vec())
+        });
+        let parsed = core::load_csv_reader(csv.as_bytes(), core::LoadOptions::default())
+            .expect("clipboard csv parses");
+
+        assert!(csv.starts_with(core::CSV_HEADER));
+        assert_eq!(visible.len(), 4);
+        assert_eq!(parsed, visible);
+    }
+
+    #[gpui::test]
+    async fn hiding_volume_removes_volume_pane_and_grows_price_pane(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = minute_candles(10);
+        let (chart, cx) = test_view(&mut cx, candles);
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("volume-pane").is_some());
+        let price_height = |cx: &mut gpui::VisualTestContext| {
+            chart.update(cx, |chart, _| {
+                chart
+                    .chart_bounds
+                    .map(|b| f32::from(b.size.height))
+                    .expect("chart bounds")
+            })
+        };
+        let with_volume = price_height(cx);
+
+        chart.update(cx, |chart, cx| {
+            chart.set_show_volume(false);
+            cx.notify();
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+
+        assert!(cx.debug_bounds("volume-pane").is_none());
+        assert!(price_height(cx) > with_volume);
+        assert!(chart.update(cx, |chart, _| chart.volume_bounds.is_none()));
+    }
+
+    #[gpui::test]
+    async fn zero_volume_data_hides_volume_pane(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..10)
+            .map(|i| Candle {
+                timestamp: datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                open: 1.1,
+                high: 1.2,
+                low: 1.0,
+                close: 1.15,
+                volume: 0.0,
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+
+        assert!(chart.update(cx, |chart, _| chart.show_volume_enabled()
+            && !chart.has_volume()));
+        assert!(cx.debug_bounds("volume-pane").is_none());
+    }
+
+    #[test]
+    fn quick_ranges_differ_for_second_and_day_intervals() {
+        use core::Interval;
+
+        let labels = |interval| {
+            state::quick_ranges_for(interval)
+                .iter()
+                .map(|(label, _)| *label)
+                .collect::<Vec<_>>()
+        };
+        let seconds = labels(Some(Interval::Second(1)));
+        let days = labels(Some(Interval::Day(1)));
+
+        assert_eq!(seconds, vec!["15m", "1H", "4H", "1D", "ALL"]);
+        assert_eq!(days, vec!["1M", "6M", "1Y", "5Y", "ALL"]);
+        assert!(!seconds.contains(&"5Y"));
+        assert!(!days.contains(&"1H"));
+    }
+
+    #[gpui::test]
+    async fn index_of_time_finds_exact_nearest_and_out_of_range(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = minute_candles(5);
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            assert_eq!(
+                chart.index_of_time(datetime!(2024-01-01 00:00:00 UTC)),
+                Some(0)
+            );
+            assert_eq!(
+                chart.index_of_time(datetime!(2024-01-01 00:03:00 UTC)),
+                Some(3)
+            );
+            assert_eq!(
+                chart.index_of_time(datetime!(2024-01-01 00:01:20 UTC)),
+                Some(1)
+            );
+            assert_eq!(
+                chart.index_of_time(datetime!(2024-01-01 00:01:40 UTC)),
+                Some(2)
+            );
+            assert_eq!(
+                chart.index_of_time(datetime!(2024-01-01 00:01:30 UTC)),
+                Some(1)
+            );
+            assert_eq!(
+                chart.index_of_time(datetime!(2023-12-31 23:59:00 UTC)),
+                None
+            );
+            assert_eq!(
+                chart.index_of_time(datetime!(2024-01-01 00:05:00 UTC)),
+                None
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn initial_visible_shows_the_last_candles(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = minute_candles(1_000);
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(
+                candles,
+                ChartMeta {
+                    source: "VISIBLE".to_string(),
+                    initial_interval: None,
+                    initial_visible: Some(100),
+                },
+                None,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        let range = chart.update(cx, |chart, _| chart.visible_range());
+        assert_eq!(range, (900, 1_000));
+    }
+
+    fn render_state_for(
+        cx: &TestAppContext,
+        source: &str,
+        candles: Vec<Candle>,
+    ) -> (String, u32, String, String, String, Vec<String>, usize) {
+        let mut cx = cx.clone();
+        let source = source.to_string();
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(
+                candles,
+                ChartMeta {
+                    source,
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                None,
+                cx,
+            )
+        });
+        chart.update(cx, |chart, _| {
+            let state = render::RenderState::compute(chart);
+            (
+                state.change_display,
+                state.change_color,
+                state.price_display,
+                state.symbol_label,
+                state.timezone_label.to_string(),
+                state.price_labels,
+                state.candle_count,
+            )
+        })
+    }
+
+    #[gpui::test]
+    async fn render_state_for_empty_view_uses_placeholders(cx: &TestAppContext) {
+        let (change, color, price, symbol, tz, _, count) =
+            render_state_for(cx, "data/candles/AAPL.csv", Vec::new());
+
+        assert_eq!(change, "--");
+        assert_eq!(color, 0x9ca3af);
+        assert_eq!(price, "--");
+        assert_eq!(symbol, "AAPL.csv");
+        assert_eq!(tz, "UTC");
+        assert_eq!(count, 0);
+    }
+
+    #[gpui::test]
+    async fn render_state_for_single_candle_has_price_but_no_change(cx: &TestAppContext) {
+        let candle = Candle {
+            timestamp: datetime!(2024-01-02 14:30:00 UTC),
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close: 1.5,
+            volume: 10.0,
+        };
+        let (change, color, price, symbol, tz, _, count) =
+            render_state_for(cx, "MSFT", vec![candle]);
+
+        assert_eq!(change, "--");
+        assert_eq!(color, 0x9ca3af);
+        assert_eq!(price, "1.50");
+        assert_eq!(symbol, "MSFT");
+        assert!(tz.starts_with("UTC+00"));
+        assert_eq!(count, 1);
+    }
+
+    #[gpui::test]
+    async fn display_tz_shifts_labels_but_not_stored_candles(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..3)
+            .map(|i| {
+                Candle::new(
+                    datetime!(2024-01-02 14:30:00 UTC) + time::Duration::minutes(i),
+                    1.0,
+                    2.0,
+                    0.5,
+                    1.5,
+                    10.0,
+                )
+            })
+            .collect();
+        let stored = candles.clone();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            let utc = render::RenderState::compute(chart);
+            assert_eq!(utc.start_label, "2024-01-02 14:30");
+            assert!(utc.timezone_label.starts_with("UTC+00"));
+
+            chart.set_display_tz(offset!(-5));
+            let ny = render::RenderState::compute(chart);
+            assert_eq!(ny.start_label, "2024-01-02 09:30");
+            assert_eq!(ny.end_label, "2024-01-02 09:32");
+            assert!(ny.timezone_label.starts_with("UTC-05"));
+            assert_eq!(chart.display_tz(), offset!(-5));
+            assert_eq!(chart.candles, stored);
+        });
+    }
+
+    #[gpui::test]
+    async fn grid_lines_setting_controls_price_label_count(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..4)
+            .map(|i| {
+                let close = 100.0 + i as f64;
+                Candle::new(
+                    datetime!(2024-01-02 14:30:00 UTC) + time::Duration::minutes(i),
+                    close,
+                    close + 1.0,
+                    close - 1.0,
+                    close,
+                    1.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            chart.set_grid_lines(Some(7));
+            let state = render::RenderState::compute(chart);
+            assert_eq!(chart.grid_lines(), 7);
+            assert_eq!(state.price_labels.len(), 7);
+
+            chart.set_grid_lines(Some(1));
+            assert_eq!(chart.grid_lines(), 2);
+        });
+    }
+
+    #[gpui::test]
+    async fn price_tick_step_pins_gridlines_to_multiples(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..40)
+            .map(|i| {
+                let close = 101.3 + (i as f64 * 0.7).sin() * 4.2;
+                Candle::new(
+                    datetime!(2024-01-02 14:30:00 UTC) + time::Duration::minutes(i),
+                    close,
+                    close + 0.6,
+                    close - 0.6,
+                    close,
+                    1.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            assert_eq!(render::RenderState::compute(chart).price_ticks, None);
+
+            chart.set_price_tick_step(Some(1.0));
+            let state = render::RenderState::compute(chart);
+            let ticks = state.price_ticks.expect("fixed ticks");
+            let (min, max) = (state.price_min, state.price_max);
+            assert!(ticks.iter().all(|tick| tick.fract() == 0.0), "{ticks:?}");
+            assert!(ticks.windows(2).all(|pair| pair[0] - pair[1] == 1.0));
+            assert!(ticks.iter().all(|tick| (min..=max).contains(tick)));
+            assert!(ticks[0] > max - 1.0 && ticks[ticks.len() - 1] < min + 1.0);
+            assert_eq!(state.price_labels.len(), ticks.len());
+
+            chart.set_price_tick_step(Some(0.0));
+            assert_eq!(chart.price_tick_step(), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn serialized_view_state_round_trips(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..600)
+            .map(|i| {
+                let close = 100.0 + (i % 17) as f64;
+                Candle::new(
+                    datetime!(2024-01-02 00:00:00 UTC) + time::Duration::minutes(i),
+                    close,
+                    close + 1.0,
+                    close - 1.0,
+                    close,
+                    1.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            let theme = ChartTheme {
+                up: 0x3b82f6,
+                down: 0xf97316,
+                body_border: Some(0x111827),
+                body_border_width: 1.5,
+                ..ChartTheme::default()
+            };
+            chart.apply_interval(Some(Interval::Minute(5)), false);
+            chart.zoom = 3.0;
+            chart.view_offset = 10.0;
+            chart.set_theme(theme);
+            chart.set_crossover_periods(Some((5, 20)));
+            chart.set_display_tz(offset!(+8));
+            chart.set_grid_lines(Some(6));
+            chart.set_tooltip_placement(TooltipPlacement::Corner);
+            chart.set_show_volume(false);
+            let saved = chart.serialize_state();
+
+            chart.apply_interval(None, false);
+            chart.set_theme(ChartTheme::default());
+            chart.set_crossover_periods(None);
+            chart.set_display_tz(time::UtcOffset::UTC);
+            chart.set_grid_lines(None);
+            chart.set_tooltip_placement(TooltipPlacement::Follow);
+            chart.set_show_volume(true);
+            assert_ne!(chart.serialize_state(), saved);
+
+            chart.restore_state(&saved).expect("restore");
+            assert_eq!(chart.serialize_state(), saved);
+            assert_eq!(chart.current_interval(), Some(Interval::Minute(5)));
+            assert_eq!((chart.zoom, chart.view_offset), (3.0, 10.0));
+            assert_eq!(chart.theme(), theme);
+            assert_eq!(chart.crossover_periods(), Some((5, 20)));
+            assert_eq!(chart.display_tz(), offset!(+8));
+            assert_eq!(chart.tooltip_placement(), TooltipPlacement::Corner);
+            assert!(!chart.show_volume_enabled());
+
+            assert!(chart.restore_state("not json").is_err());
+            assert_eq!(chart.serialize_state(), saved);
+        });
+    }
+
+    #[gpui::test]
+    async fn accessible_preset_drives_direction_colors(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = vec![
+            Candle::new(datetime!(2024-01-01 00:00:00 UTC), 1.0, 2.0, 0.5, 1.5, 1.0),
+            Candle::new(datetime!(2024-01-01 00:01:00 UTC), 1.5, 1.6, 1.0, 1.2, 1.0),
+        ];
+        let (chart, cx) = test_view(&mut cx, candles);
+        chart.update(cx, |chart, _| {
+            chart.apply_theme_preset("Accessible");
+            let theme = chart.theme();
+            assert_eq!((theme.up, theme.down), (0x0072b2, 0xe69f00));
+            assert_eq!(theme.candle_color(true), 0x0072b2);
+
+            let state = render::RenderState::compute(chart);
+            assert_eq!(
+                state.change_color, 0xe69f00,
+                "falling close uses theme down"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn theme_preset_applies_colors_and_round_trips_through_session(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let path = std::env::temp_dir().join("gpui-kbar-theme-preset.duckdb");
+        let store = Arc::new(Mutex::new(
+            DuckDbStore::new(path, StorageMode::Memory).expect("memory store"),
+        ));
+        let meta = || ChartMeta {
+            source: "SPY".to_string(),
+            initial_interval: None,
+            initial_visible: None,
+        };
+
+        let first_store = store.clone();
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(Vec::<Candle>::new(), meta(), Some(first_store), cx)
+        });
+        chart.update(cx, |chart, _| {
+            chart.apply_theme_preset("Light");
+            let theme = chart.theme();
+            assert_eq!(
+                (theme.background, theme.up, theme.down),
+                (0xf8fafc, 0x16a34a, 0xdc2626)
+            );
+            assert_eq!(chart.theme_preset(), Some("Light"));
+        });
+        assert_eq!(
+            store
+                .lock()
+                .unwrap()
+                .get_session_value("theme_preset")
+                .unwrap()
+                .as_deref(),
+            Some("Light")
+        );
+
+        let restored = cx.new(|cx| ChartView::new(Vec::<Candle>::new(), meta(), Some(store), cx));
+        restored.update(cx, |chart, _| {
+            chart.hydrate_from_store();
+            assert_eq!(chart.theme_preset(), Some("Light"));
+            assert_eq!(chart.theme(), ChartTheme::preset("Light"));
+
+            chart.set_theme(ChartTheme::default());
+            assert_eq!(chart.theme_preset(), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn per_key_settings_survive_the_view_state_blob(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let path = std::env::temp_dir().join("gpui-kbar-per-key-settings.duckdb");
+        let store = Arc::new(Mutex::new(
+            DuckDbStore::new(path, StorageMode::Memory).expect("memory store"),
+        ));
+        let meta = || ChartMeta {
+            source: "SPY".to_string(),
+            initial_interval: None,
+            initial_visible: None,
+        };
+
+        let first_store = store.clone();
+        let chart =
+            cx.new(|cx| ChartView::new(Vec::<Candle>::new(), meta(), Some(first_store), cx));
+        chart.update(&mut cx, |chart, _| {
+            // Writes the blob first, so the setters below must refresh it.
+            chart.set_global_volume_scale(true);
+            chart.set_show_volume(false);
+            chart.set_fast_wicks(true);
+            chart.set_tooltip_placement(TooltipPlacement::Corner);
+        });
+
+        let restored = cx.new(|cx| ChartView::new(Vec::<Candle>::new(), meta(), Some(store), cx));
+        restored.update(&mut cx, |chart, _| {
+            chart.hydrate_from_store();
+            assert!(chart.global_volume_scale());
+            assert!(!chart.show_volume_enabled());
+            assert!(chart.fast_wicks_enabled());
+            assert_eq!(chart.tooltip_placement(), TooltipPlacement::Corner);
+        });
+    }
+
+    #[gpui::test]
+    async fn custom_interval_is_listed_persisted_and_resamples(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let path = std::env::temp_dir().join("gpui-kbar-custom-interval.duckdb");
+        let store = Arc::new(Mutex::new(
+            DuckDbStore::new(path, StorageMode::Memory).expect("memory store"),
+        ));
+        // Eight hours of one-minute candles; close climbs by one each minute.
+        let candles: Vec<Candle> = (0..480)
+            .map(|i| {
+                let close = 100.0 + i as f64;
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                    close - 0.5,
+                    close + 1.0,
+                    close - 1.0,
+                    close,
+                    1.0,
+                )
+            })
+            .collect();
+        let chart_store = store.clone();
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(
+                candles,
+                ChartMeta {
+                    source: "SPY".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                Some(chart_store),
+                cx,
+            )
+        });
+
+        chart.update(cx, |chart, _| {
+            assert!(chart.add_custom_interval("nope").is_err());
+            assert_eq!(chart.add_custom_interval("4h"), Ok(Interval::Hour(4)));
+            // Same duration as a built-in entry: not listed twice.
+            chart.add_custom_interval("60m").expect("parses");
+
+            let labels: Vec<String> = chart
+                .interval_options()
+                .iter()
+                .map(|(_, label)| label.to_string())
+                .collect();
+            let pos = |label: &str| labels.iter().position(|l| l == label).unwrap();
+            assert!(pos("1h") < pos("4h") && pos("4h") < pos("1d"));
+            assert_eq!(labels.iter().filter(|l| l.as_str() == "60m").count(), 0);
+
+            chart.apply_interval(Some(Interval::Hour(4)), false);
+            assert_eq!(chart.candles.len(), 2);
+            assert_eq!(chart.candles[0].open, 99.5);
+            assert_eq!(chart.candles[0].close, 339.0);
+            assert_eq!(chart.candles[1].high, 580.0);
+            assert_eq!(chart.candles[1].volume, 240.0);
+        });
+        assert_eq!(
+            store
+                .lock()
+                .unwrap()
+                .get_session_value("custom_intervals")
+                .unwrap()
+                .as_deref(),
+            Some("4h")
+        );
+    }
+
+    #[gpui::test]
+    async fn active_interval_round_trips_through_session(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        for (interval, key) in [
+            (None, "raw"),
+            (Some(Interval::Second(3)), "3s"),
+            (Some(Interval::Day(1)), "1d"),
+        ] {
+            let path = std::env::temp_dir().join("gpui-kbar-interval-session.duckdb");
+            let store = Arc::new(Mutex::new(
+                DuckDbStore::new(path, StorageMode::Memory).expect("memory store"),
+            ));
+            // Start somewhere else so restoring has to change the interval.
+            let meta = || ChartMeta {
+                source: "SPY".to_string(),
+                initial_interval: Some(Interval::Minute(5)),
+                initial_visible: None,
+            };
+
+            let first_store = store.clone();
+            let chart =
+                cx.new(|cx| ChartView::new(Vec::<Candle>::new(), meta(), Some(first_store), cx));
+            chart.update(&mut cx, |chart, _| chart.apply_interval(interval, true));
+            assert_eq!(
+                store
+                    .lock()
+                    .unwrap()
+                    .get_session_value("interval")
+                    .unwrap()
+                    .as_deref(),
+                Some(key)
+            );
+
+            let restored =
+                cx.new(|cx| ChartView::new(Vec::<Candle>::new(), meta(), Some(store), cx));
+            restored.update(&mut cx, |chart, _| {
+                assert_eq!(chart.current_interval(), Some(Interval::Minute(5)));
+                chart.hydrate_from_store();
+                assert_eq!(chart.current_interval(), interval);
+            });
+            assert_eq!(ChartView::parse_interval_key(key), Some(interval));
+        }
+        assert_eq!(ChartView::parse_interval_key("1 week"), None);
+    }
+
+    #[gpui::test]
+    async fn pivots_use_previous_session_for_the_latest_visible_one(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        // Two days of hourly candles; day one spans low 90 .. high 123 and closes at 113.
+        let candles: Vec<Candle> = (0..48)
+            .map(|i| {
+                let close = 90.0 + i as f64;
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::hours(i),
+                    close,
+                    close + 10.0,
+                    close,
+                    close,
+                    1.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            assert_eq!(chart.visible_pivots(0, 48), None);
+            chart.set_show_pivots(true);
+
+            let expected = core::pivot(&Candle::new(
+                datetime!(2024-01-01 00:00:00 UTC),
+                90.0,
+                123.0,
+                90.0,
+                113.0,
+                24.0,
+            ));
+            assert_eq!(chart.visible_pivots(0, 48), Some((24, expected)));
+            // The session start is clamped to the visible window.
+            assert_eq!(chart.visible_pivots(30, 48), Some((0, expected)));
+            // Only the first session visible: nothing to derive pivots from.
+            assert_eq!(chart.visible_pivots(0, 12), None);
+
+            // Sessions follow the display timezone, so UTC-6 shifts the day boundary.
+            chart.set_display_tz(offset!(-6));
+            let (from, _) = chart.visible_pivots(0, 48).expect("pivots");
+            assert_eq!(from, 30);
+        });
+    }
+
+    #[gpui::test]
+    async fn visible_ohlc_merges_the_visible_window(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..100)
+            .map(|i| {
+                let close = 100.0 + i as f64;
+                // One spike inside the window so the high isn't simply the last candle's.
+                let high = if i == 35 { 500.0 } else { close + 1.0 };
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                    close - 0.5,
+                    high,
+                    close - 2.0,
+                    close,
+                    i as f64,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, Vec::<Candle>::new());
+
+        chart.update(cx, |chart, _| {
+            assert_eq!(chart.visible_ohlc(), None);
+
+            chart.replace_data(candles, "OHLC".to_string(), false, false);
+            chart.zoom = 5.0;
+            chart.view_offset = 30.0;
+            assert_eq!(chart.visible_range(), (30, 50));
+
+            let summary = chart.visible_ohlc().expect("visible candles");
+            assert_eq!(summary.timestamp, datetime!(2024-01-01 00:30:00 UTC));
+            assert_eq!(summary.open, 129.5);
+            assert_eq!(summary.high, 500.0);
+            assert_eq!(summary.low, 128.0);
+            assert_eq!(summary.close, 149.0);
+            assert_eq!(summary.volume, (30..50).sum::<i32>() as f64);
+        });
+    }
+
+    #[gpui::test]
+    async fn fit_clears_price_lock_and_reframes_active_range(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        // Three days of hourly candles, so "1D" frames the last 25.
+        let candles: Vec<Candle> = (0..72)
+            .map(|i| {
+                let close = 100.0 + i as f64;
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::hours(i),
+                    close,
+                    close + 1.0,
+                    close - 1.0,
+                    close,
+                    1.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        let framed = chart.update(cx, |chart, _| {
+            chart.apply_range_index(0, false);
+            let framed = (chart.zoom, chart.view_offset);
+            assert_eq!(chart.visible_range(), (47, 72));
+
+            chart.set_price_lock(Some((1.0, 2.0)));
+            chart.zoom = 10.0;
+            chart.view_offset = 5.0;
+            framed
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert_eq!(
+            chart.update(cx, |chart, _| (chart.price_min, chart.price_max)),
+            (1.0, 2.0)
+        );
+
+        chart.update(cx, |chart, _| {
+            chart.fit_to_data();
+            assert_eq!(chart.price_lock(), None);
+            assert_eq!((chart.zoom, chart.view_offset), framed);
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        let (min, max) = chart.update(cx, |chart, _| (chart.price_min, chart.price_max));
+        // Auto-scaled around the framed candles' lows (146) and highs (172).
+        assert!(min < 146.0 && min > 100.0, "{min}");
+        assert!(max > 172.0 && max < 200.0, "{max}");
+
+        chart.update(cx, |chart, _| {
+            chart.toggle_price_lock();
+            assert_eq!(chart.price_lock(), Some((min, max)));
+            chart.toggle_price_lock();
+            assert_eq!(chart.price_lock(), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn renders_off_screen_at_a_chosen_size(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..50)
+            .map(|i| {
+                let open = 100.0 + i as f64;
+                let close = if i % 2 == 0 { open + 2.0 } else { open - 2.0 };
+                Candle::new(
+                    datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                    open,
+                    open.max(close) + 1.0,
+                    open.min(close) - 1.0,
+                    close,
+                    10.0,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            let image = chart.render_to_image(800, 600);
+            assert_eq!(image.dimensions(), (800, 600));
+
+            let theme = chart.theme();
+            let rgb = |hex: u32| {
+                let [_, r, g, b] = hex.to_be_bytes();
+                [r, g, b, 0xff]
+            };
+            let count = |hex: u32| image.pixels().filter(|p| p.0 == rgb(hex)).count();
+            assert!(count(theme.up) > 0 && count(theme.down) > 0);
+            assert!(count(theme.background) < (800 * 600) as usize);
+        });
+    }
+
+    #[gpui::test]
+    async fn global_volume_scale_ignores_the_visible_slice(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        // A 100-volume spike early on, then ten quiet candles of volume 1..=10.
+        let candles: Vec<Candle> = (0..20)
+            .map(|i| {
+                let volume = if i < 10 { 100.0 } else { (i - 9) as f64 };
+                Candle::new(
+                    datetime!(2024-01-02 00:00:00 UTC) + time::Duration::minutes(i),
+                    1.0,
+                    2.0,
+                    0.5,
+                    1.5,
+                    volume,
+                )
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            chart.show_last_candles(10);
+            let visible_scale = render::RenderState::compute(chart)
+                .volume_max
+                .unwrap_or(10.0);
+            assert_eq!(visible_scale, 10.0);
+
+            chart.set_global_volume_scale(true);
+            let global_scale = render::RenderState::compute(chart)
+                .volume_max
+                .expect("global scale");
+            assert_eq!(global_scale, 100.0);
+
+            let bar = |scale| crate::chart::canvas::volume_bar_height(5.0, scale, 200.0);
+            assert_eq!(bar(visible_scale), 100.0);
+            assert_eq!(bar(global_scale), 10.0);
+
+            chart.show_last_candles(5);
+            let panned = render::RenderState::compute(chart).volume_max;
+            assert_eq!(
+                panned,
+                Some(global_scale),
+                "global scale is stable while panning"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn independent_compare_axis_labels_use_compare_bounds(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let series = |base: f64| -> Vec<Candle> {
+            (0..5)
+                .map(|i| {
+                    let close = base + i as f64;
+                    Candle::new(
+                        datetime!(2024-01-02 14:30:00 UTC) + time::Duration::minutes(i),
+                        close,
+                        close + 0.5,
+                        close - 0.5,
+                        close,
+                        1.0,
+                    )
+                })
+                .collect()
+        };
+        let (chart, cx) = test_view(&mut cx, series(1_000.0));
+
+        chart.update(cx, |chart, _| {
+            chart.set_compare(
+                "QQQ".to_string(),
+                series(10.0),
+                crate::AxisMode::Independent,
+            );
+            let state = render::RenderState::compute(chart);
+            let line = state.compare.expect("compare line");
+            assert!(line.price_min > 9.0 && line.price_max < 15.0);
+            assert!(state.price_labels[0].parse::<f64>().unwrap() > 1_000.0);
+            let labels = state.compare_labels.expect("secondary axis labels");
+            assert!(labels[0].parse::<f64>().unwrap() < 15.0);
+
+            chart.set_compare_axis_mode(crate::AxisMode::Shared);
+            let state = render::RenderState::compute(chart);
+            assert!(state.compare_labels.is_none());
+            assert_eq!(state.compare.map(|l| l.price_min), Some(state.price_min));
+        });
+    }
+
+    #[gpui::test]
+    async fn render_state_across_sessions_reports_last_change(cx: &TestAppContext) {
+        let close_at = |ts, close: f64| Candle {
+            timestamp: ts,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 1.0,
+        };
+        let candles = vec![
+            close_at(datetime!(2024-01-02 20:59:00 UTC), 100.0),
+            close_at(datetime!(2024-01-02 21:00:00 UTC), 100.0),
+            close_at(datetime!(2024-01-03 14:30:00 UTC), 98.0),
+        ];
+        let (change, color, price, _, _, labels, count) = render_state_for(cx, "SPY", candles);
+
+        assert_eq!(change, "-2.00 (-2.00%)");
+        assert_eq!(color, 0xef4444);
+        assert_eq!(price, "98.00");
+        assert_eq!(count, 3);
+        let labels: Vec<f64> = labels.iter().map(|l| l.parse().unwrap()).collect();
+        assert!(labels[0] >= 101.0 && labels[labels.len() - 1] <= 97.0);
+        let step = labels[0] - labels[1];
+        assert!(labels.windows(2).all(|w| (w[0] - w[1] - step).abs() < 1e-3));
+    }
+}
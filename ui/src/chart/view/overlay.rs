@@ -1,37 +1,214 @@
-use gpui::{Div, div, prelude::*, px, rgb};
+use gpui::{Bounds, Div, Pixels, div, prelude::*, px, rgb};
+use time::Duration;
 
+use super::super::canvas::slot_xs;
 use super::ChartView;
+use super::sections::health::format_span;
 
-impl ChartView {
-    pub(super) fn tooltip_overlay(&self, start: usize, end: usize) -> Option<Div> {
-        let (idx, (mx, my), bounds) = (self.hover_index?, self.hover_position?, self.chart_bounds?);
-        let candle = self.candles.get(idx)?;
-        if idx < start || idx >= end {
-            return None;
+const TOOLTIP_WIDTH: f32 = 180.0;
+const TOOLTIP_HEIGHT: f32 = 88.0;
+const TOOLTIP_CURSOR_OFFSET: f32 = 12.0;
+const TOOLTIP_INSET: f32 = 8.0;
+const EVENT_MARKER_SIZE: f32 = 8.0;
+
+/// Where the hover tooltip is drawn relative to the chart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TooltipPlacement {
+    /// Next to the cursor, clamped inside the chart.
+    #[default]
+    Follow,
+    /// Pinned to the top-left corner of the chart.
+    Corner,
+    /// Top corner on the opposite half of the chart from the cursor.
+    Opposite,
+}
+
+impl TooltipPlacement {
+    pub const ALL: [TooltipPlacement; 3] = [Self::Follow, Self::Corner, Self::Opposite];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Follow => "follow",
+            Self::Corner => "corner",
+            Self::Opposite => "opposite",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Follow => "Follow",
+            Self::Corner => "Corner",
+            Self::Opposite => "Opposite",
         }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str() == value)
+    }
+}
 
+/// Close-to-close difference from the pinned candle to the hovered one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct CandleDelta {
+    pub price: f64,
+    /// `None` when the pinned close is zero.
+    pub percent: Option<f64>,
+    pub span: Duration,
+    /// Candles from the pinned one to the hovered one; negative when hovering earlier.
+    pub bars: isize,
+}
+
+impl ChartView {
+    /// Pins the hovered candle as the measure origin, or unpins it if it already is.
+    pub(super) fn toggle_pin_hovered(&mut self) {
+        self.pinned_index = match (self.pinned_index, self.hover_index) {
+            (Some(pinned), Some(hovered)) if pinned == hovered => None,
+            (_, hovered) => hovered,
+        };
+    }
+
+    pub(super) fn candle_delta(&self) -> Option<CandleDelta> {
+        let (from, to) = (self.pinned_index?, self.hover_index?);
+        let (pinned, hovered) = (self.candles.get(from)?, self.candles.get(to)?);
+        let price = hovered.close - pinned.close;
+        Some(CandleDelta {
+            price,
+            percent: (pinned.close != 0.0).then(|| price / pinned.close * 100.0),
+            span: hovered.timestamp - pinned.timestamp,
+            bars: to as isize - from as isize,
+        })
+    }
+
+    /// Top-left position of the tooltip for a cursor at `(mx, my)` inside `bounds`.
+    pub(super) fn tooltip_origin(
+        &self,
+        (mx, my): (f32, f32),
+        bounds: Bounds<Pixels>,
+    ) -> (f32, f32) {
         let origin_x = f32::from(bounds.origin.x);
         let origin_y = f32::from(bounds.origin.y);
         let max_x = origin_x + f32::from(bounds.size.width);
         let max_y = origin_y + f32::from(bounds.size.height);
-        let mut x = mx + 12.0;
-        let mut y = my + 12.0;
-        let tip_width = 180.0;
-        let tip_height = 88.0;
-        if x + tip_width > max_x {
-            x = (max_x - tip_width).max(origin_x);
+        let left = origin_x + TOOLTIP_INSET;
+        let right = (max_x - TOOLTIP_WIDTH - TOOLTIP_INSET).max(origin_x);
+        let top = origin_y + TOOLTIP_INSET;
+
+        match self.tooltip_placement() {
+            TooltipPlacement::Follow => {
+                let mut x = mx + TOOLTIP_CURSOR_OFFSET;
+                let mut y = my + TOOLTIP_CURSOR_OFFSET;
+                if x + TOOLTIP_WIDTH > max_x {
+                    x = (max_x - TOOLTIP_WIDTH).max(origin_x);
+                }
+                if y + TOOLTIP_HEIGHT > max_y {
+                    y = (max_y - TOOLTIP_HEIGHT).max(origin_y);
+                }
+                (x, y)
+            }
+            TooltipPlacement::Corner => (left, top),
+            TooltipPlacement::Opposite => {
+                let mid_x = origin_x + f32::from(bounds.size.width) * 0.5;
+                if mx < mid_x {
+                    (right, top)
+                } else {
+                    (left, top)
+                }
+            }
         }
-        if y + tip_height > max_y {
-            y = (max_y - tip_height).max(origin_y);
+    }
+
+    /// Events inside `start..end` as `(candle index, label)`, in event order.
+    pub(super) fn visible_events(&self, start: usize, end: usize) -> Vec<(usize, &str)> {
+        self.events
+            .iter()
+            .filter_map(|(ts, label)| {
+                let idx = self.index_of_time(*ts)?;
+                (start <= idx && idx < end).then_some((idx, label.as_str()))
+            })
+            .collect()
+    }
+
+    /// Dots along the bottom of the price pane under each event's candle, at the same slot
+    /// centres the candles use; the label of the event under the hovered candle is shown
+    /// above its dot.
+    pub(super) fn event_markers_overlay(&self, start: usize, end: usize) -> Option<Div> {
+        let bounds = self.chart_bounds?;
+        let events = self.visible_events(start, end);
+        if events.is_empty() || start >= end {
+            return None;
         }
 
-        let ts = candle.timestamp;
+        let origin_x = f32::from(bounds.origin.x);
+        let width = self.candle_area_width(f32::from(bounds.size.width), end - start);
+        let bottom = f32::from(bounds.origin.y) + f32::from(bounds.size.height);
+        let visible = &self.candles[start..end.min(self.candles.len())];
+        let (xs, _) = slot_xs(visible, self.x_axis_mode(), origin_x, width);
+
+        let mut layer = div().absolute().left(px(0.)).top(px(0.)).size_full();
+        for (idx, label) in events {
+            let Some(&x) = xs.get(idx - start) else {
+                continue;
+            };
+            let marker_top = bottom - EVENT_MARKER_SIZE - 4.0;
+            layer = layer.child(
+                div()
+                    .absolute()
+                    .left(px(x - EVENT_MARKER_SIZE * 0.5))
+                    .top(px(marker_top))
+                    .size(px(EVENT_MARKER_SIZE))
+                    .rounded_full()
+                    .bg(rgb(0xf59e0b))
+                    .debug_selector(move || format!("event-marker-{idx}")),
+            );
+            if self.hover_index == Some(idx) {
+                layer = layer.child(
+                    div()
+                        .absolute()
+                        .left(px(x - EVENT_MARKER_SIZE))
+                        .top(px(marker_top - 24.0))
+                        .px_2()
+                        .py_1()
+                        .rounded_md()
+                        .bg(rgb(0x111827))
+                        .border_1()
+                        .border_color(rgb(0xf59e0b))
+                        .text_xs()
+                        .text_color(gpui::white())
+                        .child(label.to_string()),
+                );
+            }
+        }
+        Some(layer)
+    }
+
+    pub(super) fn tooltip_overlay(&self, start: usize, end: usize) -> Option<Div> {
+        let (idx, (mx, my), bounds) = (self.hover_index?, self.hover_position?, self.chart_bounds?);
+        let candle = self.candles.get(idx)?;
+        if idx < start || idx >= end {
+            return None;
+        }
+
+        let (x, y) = self.tooltip_origin((mx, my), bounds);
+
+        let ts = candle.timestamp.to_offset(self.display_tz());
         let idx_line = format!("#{idx}");
         let o_line = format!("O: {:.4}", candle.open);
         let h_line = format!("H: {:.4}", candle.high);
         let l_line = format!("L: {:.4}", candle.low);
         let c_line = format!("C: {:.4}", candle.close);
         let v_line = format!("V: {:.2}", candle.volume);
+        let delta_lines = self
+            .candle_delta()
+            .filter(|_| self.measure_held)
+            .map(|delta| {
+                let percent = delta
+                    .percent
+                    .map_or_else(|| "--".to_string(), |p| format!("{p:+.2}%"));
+                [
+                    format!("Δ {:+.4} ({percent})", delta.price),
+                    format!("{:+} bars, {}", delta.bars, format_span(delta.span.abs())),
+                ]
+            });
 
         Some(
             div()
@@ -55,7 +232,176 @@ impl ChartView {
                 .child(h_line)
                 .child(l_line)
                 .child(c_line)
-                .child(v_line),
+                .child(v_line)
+                .children(delta_lines.into_iter().flatten()),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::Candle;
+    use gpui::{TestAppContext, point, size};
+    use time::macros::datetime;
+
+    use crate::chart::view::tests::test_view;
+    use crate::test_support::{minute_candle, minute_candles};
+    use crate::{ChartMeta, XAxisMode};
+
+    #[gpui::test]
+    async fn tooltip_origin_follows_placement_setting(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let (chart, cx) = test_view(&mut cx, Vec::new());
+        let bounds = Bounds::new(point(px(0.), px(0.)), size(px(800.), px(400.)));
+        let cursor = (100.0, 100.0);
+
+        let origins = chart.update(cx, |chart, _| {
+            TooltipPlacement::ALL.map(|placement| {
+                chart.set_tooltip_placement(placement);
+                chart.tooltip_origin(cursor, bounds)
+            })
+        });
+        assert_eq!(origins[0], (112.0, 112.0));
+        assert_eq!(origins[1], (TOOLTIP_INSET, TOOLTIP_INSET));
+        assert_eq!(
+            origins[2],
+            (800.0 - TOOLTIP_WIDTH - TOOLTIP_INSET, TOOLTIP_INSET)
+        );
+
+        let far_right = chart.update(cx, |chart, _| chart.tooltip_origin((700.0, 100.0), bounds));
+        assert_eq!(far_right, (TOOLTIP_INSET, TOOLTIP_INSET));
+    }
+
+    #[gpui::test]
+    async fn pinned_delta_measures_to_hovered_candle(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles: Vec<Candle> = (0..10)
+            .map(|i| Candle {
+                timestamp: datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+                open: 100.0,
+                high: 120.0,
+                low: 90.0,
+                close: 100.0 + i as f64 * 2.0,
+                volume: 10.0,
+            })
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+
+        chart.update(cx, |chart, _| {
+            assert_eq!(chart.candle_delta(), None);
+
+            chart.hover_index = Some(2);
+            chart.toggle_pin_hovered();
+            assert_eq!(chart.pinned_index, Some(2));
+
+            chart.hover_index = Some(7);
+            let delta = chart.candle_delta().expect("delta");
+            assert_eq!(delta.price, 10.0);
+            assert!((delta.percent.unwrap() - 10.0 / 104.0 * 100.0).abs() < 1e-9);
+            assert_eq!(delta.span, Duration::minutes(5));
+            assert_eq!(delta.bars, 5);
+
+            chart.hover_index = Some(0);
+            let back = chart.candle_delta().expect("delta");
+            assert_eq!((back.price, back.bars), (-4.0, -2));
+            assert_eq!(back.span, Duration::minutes(-2));
+
+            // Clicking the pinned candle again unpins it.
+            chart.hover_index = Some(2);
+            chart.toggle_pin_hovered();
+            assert_eq!(chart.pinned_index, None);
+
+            // Trimming the head keeps the pin on the same candle, or drops it with the candle.
+            chart.set_max_candles(Some(10));
+            chart.hover_index = Some(5);
+            chart.toggle_pin_hovered();
+            chart.append_candles((10..13).map(minute_candle).collect());
+            assert_eq!(chart.pinned_index, Some(2));
+            chart.append_candles((13..16).map(minute_candle).collect());
+            assert_eq!(chart.pinned_index, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn event_marker_sits_on_matching_candle(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = minute_candles(10);
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            let mut chart = ChartView::new(
+                candles,
+                ChartMeta {
+                    source: "EVENTS".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                None,
+                cx,
+            );
+            chart.set_events(vec![
+                (datetime!(2024-01-01 00:03:00 UTC), "Earnings".to_string()),
+                (
+                    datetime!(2025-01-01 00:00:00 UTC),
+                    "Out of range".to_string(),
+                ),
+            ]);
+            chart
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+
+        let (events, bounds) = chart.update(cx, |chart, _| {
+            let events: Vec<(usize, String)> = chart
+                .visible_events(0, 10)
+                .into_iter()
+                .map(|(idx, label)| (idx, label.to_string()))
+                .collect();
+            (events, chart.chart_bounds.expect("chart bounds"))
+        });
+        assert_eq!(events, vec![(3, "Earnings".to_string())]);
+
+        let marker = cx
+            .debug_bounds("event-marker-3")
+            .expect("event marker bounds");
+        let expected_x = f32::from(bounds.origin.x) + 3.5 / 10.0 * f32::from(bounds.size.width);
+        assert!((f32::from(marker.center().x) - expected_x).abs() < 1.0);
+    }
+
+    #[gpui::test]
+    async fn event_marker_follows_time_axis_slots(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        // A gap after the third candle makes time slots diverge from ordinal ones.
+        let candles: Vec<Candle> = [0, 1, 2, 10, 11, 12, 13, 14, 15, 16]
+            .into_iter()
+            .map(minute_candle)
+            .collect();
+        let (chart, cx) = test_view(&mut cx, candles);
+        chart.update(cx, |chart, cx| {
+            chart.set_x_axis_mode(XAxisMode::Time);
+            chart.set_events(vec![(
+                datetime!(2024-01-01 00:11:00 UTC),
+                "Earnings".to_string(),
+            )]);
+            cx.notify();
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+
+        let (expected_x, ordinal_x) = chart.update(cx, |chart, _| {
+            let (start, end) = chart.visible_range();
+            assert_eq!((start, end), (0, 10));
+            let bounds = chart.chart_bounds.expect("chart bounds");
+            let origin_x = f32::from(bounds.origin.x);
+            let width = chart.candle_area_width(f32::from(bounds.size.width), end - start);
+            let (xs, _) = slot_xs(&chart.candles[start..end], XAxisMode::Time, origin_x, width);
+            (xs[4], origin_x + 4.5 / 10.0 * width)
+        });
+        assert!((expected_x - ordinal_x).abs() > 10.0);
+
+        let marker = cx
+            .debug_bounds("event-marker-4")
+            .expect("event marker bounds");
+        assert!((f32::from(marker.center().x) - expected_x).abs() < 1.0);
+    }
+}
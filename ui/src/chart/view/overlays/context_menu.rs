@@ -0,0 +1,44 @@
+use gpui::{ClipboardItem, Div, MouseButton, div, prelude::*, px, rgb};
+
+use crate::chart::view::ChartView;
+use crate::components::button_effect;
+
+/// Right-click menu over the chart; opened at the cursor position.
+pub fn context_menu(view: &ChartView, cx: &mut gpui::Context<ChartView>) -> Option<Div> {
+    let (x, y) = view.context_menu?;
+    let (left, top) = (x - view.root_origin.0, y - view.root_origin.1);
+
+    let copy_csv = cx.listener(
+        |this: &mut ChartView, _: &gpui::MouseDownEvent, window, cx| {
+            cx.write_to_clipboard(ClipboardItem::new_string(this.visible_csv()));
+            this.context_menu = None;
+            cx.stop_propagation();
+            window.refresh();
+        },
+    );
+
+    Some(
+        div()
+            .absolute()
+            .left(px(left))
+            .top(px(top))
+            .flex()
+            .flex_col()
+            .bg(rgb(0x0f172a))
+            .border_1()
+            .border_color(rgb(0x1f2937))
+            .rounded_md()
+            .child(button_effect::apply(
+                div()
+                    .px_3()
+                    .py_2()
+                    .bg(rgb(0x0f172a))
+                    .text_sm()
+                    .text_color(gpui::white())
+                    .on_mouse_down(MouseButton::Left, copy_csv)
+                    .child("Copy visible as CSV")
+                    .id("context-menu-copy-csv"),
+                0x0f172a,
+            )),
+    )
+}
@@ -0,0 +1,94 @@
+use gpui::{Div, div, prelude::*, px, rgb};
+
+use crate::chart::view::ChartView;
+
+/// Frame-time/candle-count readout pinned to the top-right corner.
+///
+/// Frame time is measured around `ChartView::render`, so the value shown is from the
+/// previous frame.
+pub fn debug_overlay(view: &ChartView, visible_candles: usize) -> Option<Div> {
+    if !view.debug_overlay_enabled() {
+        return None;
+    }
+
+    let frame = view
+        .last_frame_time
+        .map(|d| format!("{:.2}ms", d.as_secs_f64() * 1_000.0))
+        .unwrap_or_else(|| "--".to_string());
+    let paint = if view.last_render_heavy {
+        "rebuild"
+    } else {
+        "cached"
+    };
+    let line = |label: &str, value: String| {
+        div()
+            .flex()
+            .justify_between()
+            .gap_3()
+            .child(div().text_color(rgb(0x9ca3af)).child(label.to_string()))
+            .child(div().text_color(rgb(0xe5e7eb)).child(value))
+    };
+
+    Some(
+        div()
+            .absolute()
+            .top(px(64.))
+            .right(px(12.))
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_3()
+            .py_2()
+            .rounded_md()
+            .bg(rgb(0x0f172a))
+            .border_1()
+            .border_color(rgb(0x1f2937))
+            .text_xs()
+            .child(line("frame", frame))
+            .child(line("visible", visible_candles.to_string()))
+            .child(line("paint", paint.to_string()))
+            .debug_selector(|| "debug-overlay".to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use gpui::TestAppContext;
+
+    use crate::ChartMeta;
+    use crate::test_support::minute_candles;
+
+    #[gpui::test]
+    async fn toggling_debug_flag_adds_overlay(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let candles = minute_candles(10);
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            let mut view = ChartView::new(
+                candles,
+                ChartMeta {
+                    source: "DEBUG".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                None,
+                cx,
+            );
+            view.set_debug_overlay(false);
+            view
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("debug-overlay").is_none());
+
+        chart.update(cx, |chart, cx| {
+            chart.set_debug_overlay(true);
+            cx.notify();
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("debug-overlay").is_some());
+        assert!(chart.update(cx, |chart, _| chart.last_frame_time.is_some()));
+    }
+}
@@ -1,4 +1,3 @@
-use core::Interval;
 use gpui::{Div, MouseButton, SharedString, div, prelude::*, px, rgb};
 
 use crate::chart::view::{ChartView, OVERLAY_GAP};
@@ -7,7 +6,6 @@ use crate::components::button_effect;
 pub fn interval_menu(
     view: &mut ChartView,
     cx: &mut gpui::Context<ChartView>,
-    options: &[(Option<Interval>, &str)],
     origin: (f32, f32),
     trigger_height: f32,
     trigger_width: f32,
@@ -31,16 +29,43 @@ pub fn interval_menu(
         .border_color(rgb(0x1f2937))
         .rounded_md();
 
-    for (option, label) in options.iter().cloned() {
-        let is_active = view.current_interval() == option;
+    let auto = view.auto_interval_enabled();
+    let toggle_auto = cx.listener(
+        move |this: &mut ChartView, _: &gpui::MouseDownEvent, window, _| {
+            this.set_auto_interval(!auto);
+            window.refresh();
+        },
+    );
+    let auto_bg_hex = if auto { 0x1f2937 } else { 0x0f172a };
+    menu = menu.child(button_effect::apply(
+        div()
+            .px_3()
+            .py_2()
+            .w(px(menu_width))
+            .bg(rgb(auto_bg_hex))
+            .border_b_1()
+            .border_color(rgb(0x1f2937))
+            .text_sm()
+            .text_color(gpui::white())
+            .on_mouse_down(MouseButton::Left, toggle_auto)
+            .child("Auto")
+            .id("interval-menu-auto"),
+        auto_bg_hex,
+    ));
+
+    for (option, label) in view.interval_options().to_vec() {
+        let is_active = !auto && view.current_interval() == option;
         let handler = cx.listener(
             move |this: &mut ChartView, _: &gpui::MouseDownEvent, window, _| {
+                if this.auto_interval_enabled() {
+                    this.set_auto_interval(false);
+                }
                 this.apply_interval(option, true);
                 window.refresh();
             },
         );
         let bg_hex = if is_active { 0x1f2937 } else { 0x0f172a };
-        let text = SharedString::from(label.to_string());
+        let text = label.clone();
         let option_id: SharedString = format!("interval-menu-option-{label}").into();
 
         menu = menu.child(button_effect::apply(
@@ -58,5 +83,63 @@ pub fn interval_menu(
         ));
     }
 
-    Some(menu)
+    let custom_row = match view.custom_interval_input.as_deref() {
+        Some(input) => {
+            let hint = match view.custom_interval_error.as_deref() {
+                Some(err) => div()
+                    .text_xs()
+                    .text_color(rgb(0xef4444))
+                    .child(err.to_string()),
+                None => div()
+                    .text_xs()
+                    .text_color(rgb(0x9ca3af))
+                    .child("e.g. 2h, 4h; Enter to add"),
+            };
+            div()
+                .px_3()
+                .py_2()
+                .w(px(menu_width))
+                .flex()
+                .flex_col()
+                .gap_1()
+                .border_t_1()
+                .border_color(rgb(0x1f2937))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(gpui::white())
+                        .child(SharedString::from(format!("{input}|"))),
+                )
+                .child(hint)
+                .id("interval-menu-custom-input")
+        }
+        None => {
+            let start_custom = cx.listener(
+                |this: &mut ChartView, _: &gpui::MouseDownEvent, window, cx| {
+                    this.custom_interval_input = Some(String::new());
+                    this.custom_interval_error = None;
+                    this.focus_handle.focus(window);
+                    cx.stop_propagation();
+                    window.refresh();
+                },
+            );
+            button_effect::apply(
+                div()
+                    .px_3()
+                    .py_2()
+                    .w(px(menu_width))
+                    .bg(rgb(0x0f172a))
+                    .border_t_1()
+                    .border_color(rgb(0x1f2937))
+                    .text_sm()
+                    .text_color(rgb(0x9ca3af))
+                    .on_mouse_down(MouseButton::Left, start_custom)
+                    .child("+ custom")
+                    .id("interval-menu-custom"),
+                0x0f172a,
+            )
+        }
+    };
+
+    Some(menu.child(custom_row))
 }
@@ -0,0 +1,154 @@
+use gpui::{Context, Div, MouseButton, MouseDownEvent, div, prelude::*, px, rgb};
+use time::macros::format_description;
+
+use crate::chart::view::ChartView;
+use crate::chart::view::widgets::header_chip;
+use crate::logging::{LogSource, clear_logs, recent_logs};
+
+/// Recent loading/live log lines above the footer, newest last, toggled by the "Log" chip.
+pub fn log_panel(view: &ChartView, cx: &mut Context<ChartView>) -> Option<Div> {
+    if !view.log_panel_open() {
+        return None;
+    }
+
+    let clear = cx.listener(|_: &mut ChartView, _: &MouseDownEvent, window, cx| {
+        clear_logs();
+        cx.stop_propagation();
+        window.refresh();
+    });
+    let close = cx.listener(|this: &mut ChartView, _: &MouseDownEvent, window, cx| {
+        this.set_log_panel_open(false);
+        cx.stop_propagation();
+        window.refresh();
+    });
+
+    let format = format_description!("[hour]:[minute]:[second]");
+    let entries = recent_logs();
+    let rows = entries.iter().map(|entry| {
+        let at = entry
+            .at
+            .to_offset(view.display_tz())
+            .format(&format)
+            .unwrap_or_default();
+        let source_hex = match entry.source {
+            LogSource::Loading => 0x38bdf8,
+            LogSource::Live => 0xf59e0b,
+        };
+        let seq = entry.seq;
+        div()
+            .flex()
+            .gap_2()
+            .child(div().text_color(rgb(0x6b7280)).child(at))
+            .child(
+                div()
+                    .w(px(52.))
+                    .text_color(rgb(source_hex))
+                    .child(entry.source.as_str()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_color(rgb(0xe5e7eb))
+                    .child(entry.message.clone()),
+            )
+            .debug_selector(move || format!("log-entry-{seq}"))
+    });
+
+    let list = div()
+        .id("log-panel-list")
+        .flex()
+        .flex_col()
+        .gap_1()
+        .max_h(px(260.))
+        .overflow_y_scroll()
+        .text_xs()
+        .children(rows)
+        .when(entries.is_empty(), |list| {
+            list.child(div().text_color(rgb(0x6b7280)).child("No log entries"))
+        });
+
+    Some(
+        div()
+            .absolute()
+            .bottom(px(48.))
+            .right(px(12.))
+            .w(px(480.))
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_3()
+            .rounded_md()
+            .bg(rgb(0x0f172a))
+            .border_1()
+            .border_color(rgb(0x1f2937))
+            .shadow_lg()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(0x9ca3af))
+                            .child(format!("Log ({})", entries.len())),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(header_chip("Clear").on_mouse_down(MouseButton::Left, clear))
+                            .child(header_chip("Close").on_mouse_down(MouseButton::Left, close)),
+                    ),
+            )
+            .child(list)
+            .debug_selector(|| "log-panel".to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use gpui::TestAppContext;
+
+    use crate::chart::view::tests::test_view;
+    use crate::logging::{log_live, log_loading};
+
+    #[gpui::test]
+    async fn logged_entries_are_listed_in_the_panel(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let (chart, cx) = test_view(&mut cx, Vec::new());
+        let messages = ["loading LOGPANEL", "error: LOGPANEL connection refused"];
+        log_loading(messages[0]);
+        log_live(messages[1]);
+
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("log-panel").is_none());
+
+        chart.update(cx, |chart, cx| {
+            chart.set_log_panel_open(true);
+            cx.notify();
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("log-panel").is_some());
+
+        let entries = recent_logs();
+        for message in messages {
+            let entry = entries
+                .iter()
+                .find(|entry| entry.message == message)
+                .expect("entry logged");
+            let selector = format!("log-entry-{}", entry.seq).leak();
+            assert!(cx.debug_bounds(selector).is_some(), "{message} not listed");
+        }
+        let sources: Vec<LogSource> = entries
+            .iter()
+            .filter(|entry| entry.message.contains("LOGPANEL"))
+            .map(|entry| entry.source)
+            .collect();
+        assert_eq!(sources, vec![LogSource::Loading, LogSource::Live]);
+    }
+}
@@ -1,3 +1,6 @@
+pub mod context_menu;
+pub mod debug;
 pub mod interval_menu;
+pub mod log_panel;
 pub mod settings;
 pub mod symbol_search;
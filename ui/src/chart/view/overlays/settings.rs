@@ -1,10 +1,14 @@
+use core::StorageMode;
 use gpui::{
-    Context, Div, MouseButton, MouseDownEvent, MouseMoveEvent, ScrollWheelEvent, Stateful, Window,
-    div, prelude::*, px, rgb, rgba, svg,
+    Context, Div, MouseButton, MouseDownEvent, MouseMoveEvent, ScrollWheelEvent, SharedString,
+    Stateful, Window, div, prelude::*, px, rgb, rgba, svg,
 };
 
-use crate::chart::view::ChartView;
+use time::UtcOffset;
+
 use crate::chart::view::widgets::header_chip;
+use crate::chart::view::{ChartView, TooltipPlacement};
+use crate::chart::{AxisMode, ChartTheme, XAxisMode};
 use crate::components::button_effect;
 
 fn section(title: &str, content: impl IntoElement) -> Div {
@@ -37,7 +41,7 @@ fn row(label: &str, content: impl IntoElement) -> Div {
 }
 
 fn chip_button(
-    label: &'static str,
+    label: impl Into<SharedString>,
     active: bool,
     handle: impl Fn(&mut ChartView, &MouseDownEvent, &mut Window, &mut Context<ChartView>) + 'static,
     cx: &mut Context<ChartView>,
@@ -201,6 +205,372 @@ pub fn settings_overlay(view: &mut ChartView, cx: &mut Context<ChartView>) -> Op
         )
     };
 
+    let fast_wicks_row = {
+        let active = view.fast_wicks_enabled();
+        row(
+            "Fast wicks",
+            chip_button(
+                if active { "On" } else { "Off" },
+                active,
+                |this, _, window, _| {
+                    let next = !this.fast_wicks_enabled();
+                    this.set_fast_wicks(next);
+                    window.refresh();
+                },
+                cx,
+            ),
+        )
+    };
+
+    let volume_row = {
+        let active = view.show_volume_enabled();
+        row(
+            "Volume",
+            chip_button(
+                if active { "On" } else { "Off" },
+                active,
+                |this, _, window, _| {
+                    let next = !this.show_volume_enabled();
+                    this.set_show_volume(next);
+                    window.refresh();
+                },
+                cx,
+            ),
+        )
+    };
+
+    let volume_scale_row = {
+        let active = view.global_volume_scale();
+        row(
+            "Volume scale",
+            chip_button(
+                if active { "Global" } else { "Visible" },
+                active,
+                |this, _, window, _| {
+                    let next = !this.global_volume_scale();
+                    this.set_global_volume_scale(next);
+                    window.refresh();
+                },
+                cx,
+            ),
+        )
+    };
+
+    let pivots_row = {
+        let active = view.show_pivots_enabled();
+        row(
+            "Pivots",
+            chip_button(
+                if active { "On" } else { "Off" },
+                active,
+                |this, _, window, _| {
+                    let next = !this.show_pivots_enabled();
+                    this.set_show_pivots(next);
+                    window.refresh();
+                },
+                cx,
+            ),
+        )
+    };
+
+    let time_axis_row = {
+        let active = view.x_axis_mode() == XAxisMode::Time;
+        row(
+            "Time axis",
+            chip_button(
+                if active { "On" } else { "Off" },
+                active,
+                |this, _, window, _| {
+                    let next = if this.x_axis_mode() == XAxisMode::Time {
+                        XAxisMode::Ordinal
+                    } else {
+                        XAxisMode::Time
+                    };
+                    this.set_x_axis_mode(next);
+                    window.refresh();
+                },
+                cx,
+            ),
+        )
+    };
+
+    let interval_reset_row = {
+        let active = view.reset_view_on_interval();
+        row(
+            "Interval reset",
+            chip_button(
+                if active { "On" } else { "Off" },
+                active,
+                |this, _, window, _| {
+                    let next = !this.reset_view_on_interval();
+                    this.set_reset_view_on_interval(next);
+                    window.refresh();
+                },
+                cx,
+            ),
+        )
+    };
+
+    let timezone_row = {
+        let current = view.display_tz();
+        let mut chips = div().flex().items_center().gap_1();
+        for (label, hours) in [("UTC", 0), ("UTC-5", -5), ("UTC+1", 1), ("UTC+8", 8)] {
+            let offset = UtcOffset::from_hms(hours, 0, 0).expect("whole-hour offset");
+            chips = chips.child(chip_button(
+                label,
+                current == offset,
+                move |this, _, window, _| {
+                    this.set_display_tz(offset);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Timezone", chips)
+    };
+
+    let grid_row = {
+        let current = view.grid_lines_override();
+        let mut chips = div().flex().items_center().gap_1();
+        for (label, lines) in [
+            ("Auto", None),
+            ("4", Some(4)),
+            ("8", Some(8)),
+            ("12", Some(12)),
+        ] {
+            chips = chips.child(chip_button(
+                label,
+                current == lines,
+                move |this, _, window, _| {
+                    this.set_grid_lines(lines);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Gridlines", chips)
+    };
+
+    let crossover_row = {
+        let current = view.crossover_periods();
+        let mut chips = div().flex().items_center().gap_1();
+        for (label, periods) in [
+            ("Off", None),
+            ("9/21", Some((9, 21))),
+            ("20/50", Some((20, 50))),
+            ("50/200", Some((50, 200))),
+        ] {
+            chips = chips.child(chip_button(
+                label,
+                current == periods,
+                move |this, _, window, _| {
+                    this.set_crossover_periods(periods);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Crossovers", chips)
+    };
+
+    let right_padding_row = {
+        let current = view.right_padding_candles();
+        let mut chips = div().flex().items_center().gap_1();
+        for (label, candles) in [("Off", 0), ("5", 5), ("10", 10), ("20", 20)] {
+            chips = chips.child(chip_button(
+                label,
+                current == candles,
+                move |this, _, window, _| {
+                    this.set_right_padding_candles(candles);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Right space", chips)
+    };
+
+    let compare_row = {
+        let current = view.compare_source().map(str::to_string);
+        let mut chips = div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .child(chip_button(
+                "Off",
+                current.is_none(),
+                |this, _, window, _| {
+                    this.clear_compare();
+                    window.refresh();
+                },
+                cx,
+            ));
+        let candidates = view
+            .watchlist
+            .iter()
+            .filter(|symbol| **symbol != view.source)
+            .take(4)
+            .cloned()
+            .collect::<Vec<_>>();
+        for symbol in candidates {
+            let active = current.as_deref() == Some(symbol.as_str());
+            chips = chips.child(chip_button(
+                symbol.clone(),
+                active,
+                move |this, _, window, cx| this.start_compare_load(symbol.clone(), window, cx),
+                cx,
+            ));
+        }
+        row("Compare", chips)
+    };
+
+    let compare_axis_row = view.compare_axis_mode().map(|current| {
+        let mut chips = div().flex().items_center().gap_1();
+        for (label, mode) in [
+            ("Shared", AxisMode::Shared),
+            ("Independent", AxisMode::Independent),
+        ] {
+            chips = chips.child(chip_button(
+                label,
+                current == mode,
+                move |this, _, window, _| {
+                    this.set_compare_axis_mode(mode);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Compare axis", chips)
+    });
+
+    let theme_row = {
+        let current = view.theme_preset().map(str::to_string);
+        let mut chips = div().flex().items_center().gap_1();
+        for name in ChartTheme::PRESETS {
+            chips = chips.child(chip_button(
+                name,
+                current.as_deref() == Some(name),
+                move |this, _, window, _| {
+                    this.apply_theme_preset(name);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Theme", chips)
+    };
+
+    let doji_row = {
+        let current = view.theme().doji_threshold;
+        let mut chips = div().flex().items_center().gap_1();
+        for (label, threshold) in [("Off", 0.0), ("5%", 0.05), ("10%", 0.1)] {
+            chips = chips.child(chip_button(
+                label,
+                current == threshold,
+                move |this, _, window, _| {
+                    this.set_doji_threshold(threshold);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Doji", chips)
+    };
+
+    let tooltip_row = {
+        let current = view.tooltip_placement();
+        let mut chips = div().flex().items_center().gap_2();
+        for placement in TooltipPlacement::ALL {
+            chips = chips.child(chip_button(
+                placement.label(),
+                current == placement,
+                move |this, _, window, _| {
+                    this.set_tooltip_placement(placement);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Tooltip", chips)
+    };
+
+    let cache_row = {
+        let active = view.cache_candles_enabled();
+        row(
+            "Cache",
+            div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .child(chip_button(
+                    if active { "On" } else { "Off" },
+                    active,
+                    |this, _, window, _| {
+                        let next = !this.cache_candles_enabled();
+                        this.set_cache_candles(next);
+                        window.refresh();
+                    },
+                    cx,
+                ))
+                .child(chip_button(
+                    "Clear symbol",
+                    false,
+                    |this, _, window, _| {
+                        this.clear_cached_symbol();
+                        window.refresh();
+                    },
+                    cx,
+                ))
+                .child(chip_button(
+                    "Clear all",
+                    false,
+                    |this, _, window, _| {
+                        this.clear_candle_cache();
+                        window.refresh();
+                    },
+                    cx,
+                )),
+        )
+    };
+
+    let forward_fill_row = {
+        let active = view.forward_fill_enabled();
+        row(
+            "Fill bad prices",
+            chip_button(
+                if active { "On" } else { "Off" },
+                active,
+                |this, _, window, _| {
+                    let next = !this.forward_fill_enabled();
+                    this.set_forward_fill(next);
+                    window.refresh();
+                },
+                cx,
+            ),
+        )
+    };
+
+    let storage_row = view.storage_mode().map(|current| {
+        let mut chips = div().flex().items_center().gap_1();
+        for (label, mode) in [
+            ("Memory", StorageMode::Memory),
+            ("Disk", StorageMode::Disk),
+            ("Both", StorageMode::Both),
+        ] {
+            chips = chips.child(chip_button(
+                label,
+                current == mode,
+                move |this, _, window, cx| {
+                    this.set_storage_mode(mode, cx);
+                    window.refresh();
+                },
+                cx,
+            ));
+        }
+        row("Storage", chips)
+    });
+
     let reset_row = row(
         "Defaults",
         chip_button(
@@ -226,7 +596,14 @@ pub fn settings_overlay(view: &mut ChartView, cx: &mut Context<ChartView>) -> Op
         ),
     );
 
-    let mut data_section = div().flex().flex_col().gap_3().child(source_row);
+    let mut data_section = div()
+        .flex()
+        .flex_col()
+        .gap_3()
+        .child(source_row)
+        .child(cache_row)
+        .child(forward_fill_row)
+        .children(storage_row);
     if perf_mode {
         data_section = data_section.child(perf_dataset_row).child(perf_step_row);
     }
@@ -274,7 +651,29 @@ pub fn settings_overlay(view: &mut ChartView, cx: &mut Context<ChartView>) -> Op
         .child(section("Data", data_section))
         .child(section(
             "Chart",
-            div().flex().flex_col().gap_3().child(replay_row),
+            div()
+                .flex()
+                .flex_col()
+                .gap_3()
+                .child(replay_row)
+                .child(volume_row)
+                .child(volume_scale_row)
+                .child(pivots_row)
+                .child(crossover_row)
+                .child(time_axis_row)
+                .child(interval_reset_row)
+                .child(timezone_row)
+                .child(grid_row)
+                .child(right_padding_row)
+                .child(compare_row)
+                .children(compare_axis_row)
+                .child(theme_row)
+                .child(doji_row)
+                .child(tooltip_row),
+        ))
+        .child(section(
+            "Performance",
+            div().flex().flex_col().gap_3().child(fast_wicks_row),
         ))
         .child(section(
             "Actions",
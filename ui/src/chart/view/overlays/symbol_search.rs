@@ -50,17 +50,9 @@ pub fn symbol_search_overlay(view: &mut ChartView, cx: &mut Context<ChartView>)
         ));
     }
 
-    let filtered: Vec<&_> = view
-        .symbol_universe()
-        .iter()
-        .filter(|entry| {
-            active_filter == "All"
-                || entry
-                    .filters
-                    .iter()
-                    .any(|f| f.eq_ignore_ascii_case(&active_filter))
-        })
-        .collect();
+    let filtered = view.filtered_symbol_universe();
+    let selected = view.symbol_search_selected();
+    let query = view.symbol_search_query().to_string();
 
     let mut results_list = div()
         .flex()
@@ -79,11 +71,11 @@ pub fn symbol_search_overlay(view: &mut ChartView, cx: &mut Context<ChartView>)
                 .p_4()
                 .text_sm()
                 .text_color(rgb(0x9ca3af))
-                .child("No symbols match this filter."),
+                .child("No symbols match this search."),
         );
     }
     for (idx, entry) in filtered.into_iter().enumerate() {
-        let active = idx == 0;
+        let active = idx == selected;
         let row_bg_hex = if active { 0x0f172a } else { 0x0b1220 };
         let border_color = if active { rgb(0x2563eb) } else { rgb(0x1f2937) };
         let symbol = entry.symbol.clone();
@@ -256,7 +248,18 @@ pub fn symbol_search_overlay(view: &mut ChartView, cx: &mut Context<ChartView>)
                                     .child(
                                         div().text_sm().text_color(rgb(0x9ca3af)).child("Search"),
                                     )
-                                    .child(div().text_sm().text_color(gpui::white()).child("NDQ")),
+                                    .child(if query.is_empty() {
+                                        div()
+                                            .text_sm()
+                                            .text_color(rgb(0x6b7280))
+                                            .child("Type a symbol or name")
+                                    } else {
+                                        div()
+                                            .text_sm()
+                                            .text_color(gpui::white())
+                                            .child(query)
+                                            .debug_selector(|| "symbol-search-query".to_string())
+                                    }),
                             )
                             .child(filters)
                             .child(results_list)
@@ -270,3 +273,98 @@ pub fn symbol_search_overlay(view: &mut ChartView, cx: &mut Context<ChartView>)
             ),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use core::{Candle, DuckDbStore, StorageMode};
+    use gpui::TestAppContext;
+    use time::macros::datetime;
+
+    use crate::ChartMeta;
+    use crate::chart::view::ChartView;
+    use crate::data::universe::SymbolSearchEntry;
+
+    fn entry(symbol: &str, name: &str) -> SymbolSearchEntry {
+        SymbolSearchEntry {
+            filters: vec!["Stocks".to_string()],
+            badge: symbol[..1].to_string(),
+            symbol: symbol.to_string(),
+            name: name.to_string(),
+            market: "stock".to_string(),
+            venue: "NASDAQ".to_string(),
+        }
+    }
+
+    fn memory_store() -> Arc<Mutex<DuckDbStore>> {
+        let path = std::env::temp_dir().join("gpui-kbar-symbol-search.duckdb");
+        let store = DuckDbStore::new(path, StorageMode::Memory).expect("memory store");
+        let candles = vec![Candle {
+            timestamp: datetime!(2024-01-01 00:00:00 UTC),
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close: 1.5,
+            volume: 10.0,
+        }];
+        for symbol in ["MSFT", "MSTR"] {
+            store
+                .write_candles(symbol, None, &candles)
+                .expect("seed candles");
+        }
+        Arc::new(Mutex::new(store))
+    }
+
+    #[gpui::test]
+    async fn typing_filters_symbols_and_enter_loads_selection(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let store = memory_store();
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(
+                Vec::<Candle>::new(),
+                ChartMeta {
+                    source: "AAPL".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                Some(store),
+                cx,
+            )
+        });
+
+        chart.update_in(cx, |chart, window, cx| {
+            chart.set_symbol_universe(vec![
+                entry("AAPL", "Apple Inc."),
+                entry("MSFT", "Microsoft Corp."),
+                entry("MSTR", "MicroStrategy Inc."),
+            ]);
+            chart.symbol_search_open = true;
+            chart.focus_handle.focus(window);
+            cx.notify();
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+
+        cx.simulate_input("ms");
+        let (query, matches) = chart.update(cx, |chart, _| {
+            let matches: Vec<String> = chart
+                .filtered_symbol_universe()
+                .into_iter()
+                .map(|entry| entry.symbol)
+                .collect();
+            (chart.symbol_search_query().to_string(), matches)
+        });
+        assert_eq!(query, "ms");
+        assert_eq!(matches, vec!["MSFT".to_string(), "MSTR".to_string()]);
+
+        cx.simulate_keystrokes("down enter");
+        cx.run_until_parked();
+
+        let (source, open) = chart.update(cx, |chart, _| {
+            (chart.current_source(), chart.symbol_search_open)
+        });
+        assert_eq!(source, "MSTR");
+        assert!(!open);
+    }
+}
@@ -1,10 +1,15 @@
-use std::{path::Path, sync::Arc};
+use std::{path::Path, sync::Arc, time::Instant};
 
 use super::super::{
     aggregation::AggregatedCandle,
-    canvas::{chart_canvas, volume_canvas},
+    canvas::{
+        ChartCanvasParams, VolumeCanvasParams, chart_canvas, fixed_step_ticks, volume_canvas,
+    },
+    compare::{AxisMode, CompareLine, aligned_closes},
     footer::{chart_footer, range_button},
     header::chart_header,
+    spread::aligned_spreads,
+    theme::ChartTheme,
 };
 use super::context::format_price_range;
 use super::sections::body::chart_body;
@@ -13,31 +18,21 @@ use super::sections::layout::{
     build_body_layout, build_interval_menu, build_layered_view, build_loading_overlay,
     build_sidebar_panels,
 };
-use super::state::QUICK_RANGE_WINDOWS;
+use super::sections::replay::replay_scrubber;
 use super::widgets::{header_chip, header_icon};
-use super::{ChartView, INTERVAL_TRIGGER_WIDTH, padded_bounds};
+use super::{ChartView, INTERVAL_TRIGGER_WIDTH};
+use crate::chart::view::overlays::context_menu::context_menu;
+use crate::chart::view::overlays::debug::debug_overlay;
+use crate::chart::view::overlays::log_panel::log_panel;
 use crate::chart::view::overlays::settings::settings_overlay;
 use crate::components::button_effect;
 use crate::perf::{PerfSpec, perf_label};
-use core::{Candle, Interval};
+use core::{Candle, CrossDir, PivotLevels};
 use gpui::{
     Context, Div, MouseButton, MouseDownEvent, Render, SharedString, Window, div, prelude::*, px,
     rgb,
 };
-
-const INTERVAL_OPTIONS: &[(Option<Interval>, &str)] = &[
-    (None, "raw"),
-    (Some(Interval::Second(3)), "3s"),
-    (Some(Interval::Second(10)), "10s"),
-    (Some(Interval::Second(30)), "30s"),
-    (Some(Interval::Minute(1)), "1m"),
-    (Some(Interval::Minute(5)), "5m"),
-    (Some(Interval::Minute(10)), "10m"),
-    (Some(Interval::Minute(15)), "15m"),
-    (Some(Interval::Minute(30)), "30m"),
-    (Some(Interval::Hour(1)), "1h"),
-    (Some(Interval::Day(1)), "1d"),
-];
+use time::UtcOffset;
 
 // While the blocking loading overlay is visible, avoid expensive per-frame chart rendering
 // so the spinner animation can stay smooth.
@@ -55,7 +50,9 @@ pub(crate) struct RenderState {
     pub(crate) aggregated: Option<Arc<[AggregatedCandle]>>,
     pub(crate) volume_max: Option<f64>,
     pub(crate) candle_count: usize,
-    pub(crate) price_labels: [String; 3],
+    pub(crate) price_labels: Vec<String>,
+    /// Gridline prices, top first, when a fixed tick step is set and fits the pane.
+    pub(crate) price_ticks: Option<Vec<f64>>,
     pub(crate) start_label: String,
     pub(crate) mid_label: String,
     pub(crate) end_label: String,
@@ -65,29 +62,34 @@ pub(crate) struct RenderState {
     pub(crate) hover_local: Option<usize>,
     pub(crate) hover_x: Option<f32>,
     pub(crate) hover_y: Option<f32>,
+    pub(crate) fast_wicks: bool,
+    pub(crate) in_progress: bool,
     pub(crate) change_display: String,
     pub(crate) change_color: u32,
     pub(crate) symbol_label: String,
     pub(crate) price_display: String,
     pub(crate) tooltip: Option<Div>,
+    pub(crate) event_markers: Option<Div>,
+    pub(crate) compare: Option<CompareLine>,
+    /// Bid/ask per visible candle, when the data carries quotes.
+    pub(crate) spread: Option<Vec<Option<(f64, f64)>>>,
+    pub(crate) crossovers: Vec<(usize, CrossDir)>,
+    /// Pivot levels and the visible index (relative to the start) their session begins at.
+    pub(crate) pivots: Option<(usize, PivotLevels)>,
+    /// Right-side axis labels; only for a compare series on an independent axis.
+    pub(crate) compare_labels: Option<Vec<String>>,
 }
 
 impl RenderState {
-    fn from_view(view: &mut ChartView) -> Self {
+    /// Derives everything `render` lays out from the view. Needs `&mut` only to reuse the
+    /// per-viewport render cache and record the price bounds used for hit-testing.
+    pub(crate) fn compute(view: &mut ChartView) -> Self {
         let interval_label = ChartView::interval_label(view.current_interval());
         let (playback_label, playback_detail) = view.playback_labels();
         let playback_label = SharedString::from(playback_label);
         let playback_detail = playback_detail.map(SharedString::from);
         let playback_dot_hex = view.live_dot_hex();
-        let timezone_label = SharedString::from(
-            view.candles
-                .last()
-                .map(|c| {
-                    let offset = c.timestamp.offset();
-                    format!("UTC{offset}")
-                })
-                .unwrap_or_else(|| "UTC".to_string()),
-        );
+        let timezone_label = SharedString::from(timezone_label(&view.candles, view.display_tz()));
 
         let (mut start, mut end) = view.visible_range();
         end = end.min(view.candles.len());
@@ -99,7 +101,11 @@ impl RenderState {
 
         let columns = view
             .chart_bounds
-            .map(|b| f32::from(b.size.width).floor().max(1.0) as usize)
+            .map(|b| {
+                view.candle_area_width(f32::from(b.size.width), end - start)
+                    .floor()
+                    .max(1.0) as usize
+            })
             .unwrap_or(0);
         let (cached_min, cached_max, aggregated, volume_max) = if columns > 0 {
             match view.render_cache(start, end, columns) {
@@ -115,26 +121,59 @@ impl RenderState {
             (None, None, None, None)
         };
 
-        let visible = if start < end {
-            &view.candles[start..end]
+        // Global scaling: the series' largest candle, times how many candles share a bar.
+        let volume_max = if view.global_volume_scale() {
+            let candles_per_bar = if columns > 0 {
+                ((end - start) as f64 / columns as f64).ceil().max(1.0)
+            } else {
+                1.0
+            };
+            Some(view.global_max_volume() * candles_per_bar)
+        } else {
+            volume_max
+        };
+
+        let (bounds_start, bounds_end) = if start < end {
+            (start, end)
         } else {
-            &view.candles[..]
+            (0, view.candles.len())
         };
-        let candle_count = visible.len();
+        let candle_count = bounds_end - bounds_start;
 
-        let (price_min, price_max) = match (cached_min, cached_max) {
-            (Some(min), Some(max)) => (min, max),
-            _ => padded_bounds(visible),
+        let (price_min, price_max) = match (view.price_lock(), cached_min, cached_max) {
+            (Some(lock), _, _) => lock,
+            (None, Some(min), Some(max)) => (min, max),
+            _ => view.visible_padded_bounds(bounds_start, bounds_end),
         };
         view.price_min = price_min;
         view.price_max = price_max;
         let range_text = SharedString::from(format_price_range(price_min, price_max));
         let tooltip = view.tooltip_overlay(start, end);
-        let price_labels = [
-            format!("{price_max:.4}"),
-            format!("{:.4}", (price_min + price_max) * 0.5),
-            format!("{price_min:.4}"),
-        ];
+        let event_markers = view.event_markers_overlay(start, end);
+        let grid_lines = view.grid_lines();
+        let price_ticks = view
+            .price_tick_step()
+            .and_then(|step| fixed_step_ticks(price_min, price_max, step));
+        let price_labels = match price_ticks.as_deref() {
+            Some(ticks) => ticks.iter().map(|price| format!("{price:.4}")).collect(),
+            None => price_labels(price_min, price_max, grid_lines),
+        };
+        let compare = view.compare.as_ref().map(|series| {
+            let visible = &view.candles[start..end];
+            CompareLine::new(
+                aligned_closes(visible, &series.candles),
+                series.axis_mode,
+                (price_min, price_max),
+            )
+        });
+        let spread = (!view.spread_quotes.is_empty())
+            .then(|| aligned_spreads(&view.candles, start, end, &view.spread_quotes));
+        let crossovers = view.visible_crossovers(start, end);
+        let pivots = view.visible_pivots(start, end);
+        let compare_labels = compare
+            .as_ref()
+            .filter(|line| line.axis_mode == AxisMode::Independent)
+            .map(|line| price_labels(line.price_min, line.price_max, grid_lines));
 
         let (start_label, mid_label, end_label) = view.time_axis_labels(start, end);
 
@@ -149,40 +188,19 @@ impl RenderState {
         let (hover_x, hover_y) = if hover_local.is_some() {
             (
                 view.hover_position.map(|(x, _)| x),
-                view.hover_position.map(|(_, y)| y),
+                view.hover_position
+                    .filter(|_| !view.hover_in_volume)
+                    .map(|(_, y)| y),
             )
         } else {
             (None, None)
         };
-        let last_close = view.candles.last().map(|c| c.close);
-        let prev_close = view.candles.iter().rev().nth(1).map(|c| c.close);
-        let (change_display, change_color) = match (last_close, prev_close) {
-            (Some(latest), Some(prev)) if prev.abs() > f64::EPSILON => {
-                let diff = latest - prev;
-                let pct = diff / prev * 100.0;
-                let sign = if diff >= 0.0 { "+" } else { "-" };
-                (
-                    format!("{sign}{:.2} ({sign}{:.2}%)", diff.abs(), pct.abs()),
-                    if diff >= 0.0 { 0x22c55e } else { 0xef4444 },
-                )
-            }
-            _ => ("--".to_string(), 0x9ca3af),
-        };
-        let symbol_label = if view.is_perf_mode() {
-            perf_label(PerfSpec {
-                n: view.perf_n,
-                step_secs: view.perf_step_secs,
-            })
-        } else {
-            Path::new(&view.source)
-                .file_name()
-                .and_then(|s| s.to_str())
-                .filter(|s| !s.is_empty())
-                .unwrap_or(view.source.as_str())
-                .to_string()
-        };
-        let price_display = last_close
-            .map(|v| format!("{v:.2}"))
+        let (change_display, change_color) = change_summary(&view.candles, &view.theme());
+        let symbol_label = symbol_label(view);
+        let price_display = view
+            .candles
+            .last()
+            .map(|c| format!("{:.2}", c.close))
             .unwrap_or_else(|| "--".to_string());
 
         Self {
@@ -198,6 +216,7 @@ impl RenderState {
             volume_max,
             candle_count,
             price_labels,
+            price_ticks,
             start_label,
             mid_label,
             end_label,
@@ -207,15 +226,74 @@ impl RenderState {
             hover_local,
             hover_x,
             hover_y,
+            fast_wicks: view.fast_wicks_enabled(),
+            in_progress: view.in_progress().is_some(),
             change_display,
             change_color,
             symbol_label,
             price_display,
             tooltip,
+            event_markers,
+            compare,
+            spread,
+            crossovers,
+            pivots,
+            compare_labels,
         }
     }
 }
 
+fn timezone_label(candles: &[Candle], display_tz: UtcOffset) -> String {
+    candles
+        .last()
+        .map(|_| format!("UTC{display_tz}"))
+        .unwrap_or_else(|| "UTC".to_string())
+}
+
+/// One label per gridline, top (max) to bottom (min).
+fn price_labels(price_min: f64, price_max: f64, lines: usize) -> Vec<String> {
+    let steps = lines.max(2) - 1;
+    (0..=steps)
+        .map(|i| {
+            let price = price_max - (price_max - price_min) * i as f64 / steps as f64;
+            format!("{price:.4}")
+        })
+        .collect()
+}
+
+/// Last close versus the one before it, as `"+d (+p%)"` plus the theme's up/down color.
+fn change_summary(candles: &[Candle], theme: &ChartTheme) -> (String, u32) {
+    let last_close = candles.last().map(|c| c.close);
+    let prev_close = candles.iter().rev().nth(1).map(|c| c.close);
+    match (last_close, prev_close) {
+        (Some(latest), Some(prev)) if prev.abs() > f64::EPSILON => {
+            let diff = latest - prev;
+            let pct = diff / prev * 100.0;
+            let sign = if diff >= 0.0 { "+" } else { "-" };
+            (
+                format!("{sign}{:.2} ({sign}{:.2}%)", diff.abs(), pct.abs()),
+                theme.candle_color(diff >= 0.0),
+            )
+        }
+        _ => ("--".to_string(), 0x9ca3af),
+    }
+}
+
+fn symbol_label(view: &ChartView) -> String {
+    if view.is_perf_mode() {
+        return perf_label(PerfSpec {
+            n: view.perf_n,
+            step_secs: view.perf_step_secs,
+        });
+    }
+    Path::new(&view.source)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or(view.source.as_str())
+        .to_string()
+}
+
 impl Render for ChartView {
     fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
         if self.settings_open && !self.focus_handle.is_focused(_window) {
@@ -238,63 +316,103 @@ impl Render for ChartView {
             }
         }
 
-        let state = RenderState::from_view(self);
+        let frame_start = Instant::now();
+        let state = RenderState::compute(self);
         let chart_area = build_chart_area(self, _cx, &state);
         let (header, search_overlay) = build_header_bar(self, _cx, &state);
         let footer = build_footer_bar(self, _cx, &state);
         let sidebar = build_sidebar_panels(self, _cx, &state);
         let body = build_body_layout(chart_area, sidebar);
-        let interval_menu = build_interval_menu(self, _cx, INTERVAL_OPTIONS);
+        let interval_menu = build_interval_menu(self, _cx);
         let settings_overlay = settings_overlay(self, _cx);
         let loading_overlay = build_loading_overlay(self, _cx);
+        let debug = debug_overlay(self, state.candle_count);
+        let log_panel = log_panel(self, _cx);
+        let context_menu = context_menu(self, _cx);
+        let event_markers = state.event_markers;
         let tooltip = state.tooltip;
-        build_layered_view(
+        let layered = build_layered_view(
             self,
             _cx,
             header,
             body,
             footer,
+            event_markers,
             search_overlay,
             interval_menu,
             settings_overlay,
             tooltip,
             loading_overlay,
-        )
+        );
+        self.last_frame_time = Some(frame_start.elapsed());
+        let layered = match context_menu {
+            Some(menu) => layered.child(menu),
+            None => layered,
+        };
+        let layered = match log_panel {
+            Some(panel) => layered.child(panel),
+            None => layered,
+        };
+        match debug {
+            Some(debug) => layered.child(debug),
+            None => layered,
+        }
     }
 }
 
 fn build_chart_area(view: &mut ChartView, cx: &mut Context<ChartView>, state: &RenderState) -> Div {
-    let chart = chart_canvas(
-        state.candles.clone(),
-        state.visible_start,
-        state.visible_end,
-        state.price_min,
-        state.price_max,
-        state.hover_local,
-        state.hover_x,
-        state.hover_y,
-        state.aggregated.clone(),
-    )
-    .flex_1()
-    .w_full()
-    .h_full();
-    let volume = volume_canvas(
-        state.candles.clone(),
-        state.visible_start,
-        state.visible_end,
-        state.hover_local,
-        state.hover_x,
-        state.aggregated.clone(),
-        state.volume_max,
-    )
+    let chart = chart_canvas(ChartCanvasParams {
+        candles: state.candles.clone(),
+        start: state.visible_start,
+        end: state.visible_end,
+        price_min: state.price_min,
+        price_max: state.price_max,
+        hover_local: state.hover_local,
+        hover_x: state.hover_x,
+        hover_y: state.hover_y,
+        aggregated: state.aggregated.clone(),
+        fast_wicks: state.fast_wicks,
+        in_progress: state.in_progress,
+        grid_lines: state.price_labels.len(),
+        price_ticks: state.price_ticks.clone(),
+        right_padding: view.right_padding_candles(),
+        compare: state.compare.clone(),
+        spread: state.spread.clone(),
+        crossovers: state.crossovers.clone(),
+        pivots: state.pivots,
+        x_mode: view.x_axis_mode(),
+        theme: view.theme(),
+    })
     .flex_1()
     .w_full()
     .h_full();
+    let volume = (view.show_volume_enabled() && view.has_volume()).then(|| {
+        volume_canvas(VolumeCanvasParams {
+            candles: state.candles.clone(),
+            start: state.visible_start,
+            end: state.visible_end,
+            hover_local: state.hover_local,
+            hover_x: state.hover_x,
+            aggregated: state.aggregated.clone(),
+            volume_max: state.volume_max,
+            right_padding: view.right_padding_candles(),
+            x_mode: view.x_axis_mode(),
+            theme: view.theme(),
+        })
+        .flex_1()
+        .w_full()
+        .h_full()
+    });
+    if volume.is_none() {
+        view.volume_bounds = None;
+    }
 
     chart_body(
         view,
         cx,
         state.price_labels.clone(),
+        state.price_ticks.clone(),
+        state.compare_labels.clone(),
         chart,
         volume,
         state.start_label.clone(),
@@ -313,6 +431,7 @@ fn build_header_bar(
         cx.listener(|this: &mut ChartView, _: &MouseDownEvent, window, _| {
             this.interval_select_open = !this.interval_select_open;
             this.symbol_search_open = false;
+            this.custom_interval_input = None;
             if this.interval_select_open {
                 this.focus_handle.focus(window);
             }
@@ -410,11 +529,24 @@ fn build_header_bar(
         .child(header_icon("alarm-clock.svg", "Alerts"))
         .child(replay_chip);
 
+    let toggle_log = cx.listener(|this: &mut ChartView, _: &MouseDownEvent, window, _| {
+        let next = !this.log_panel_open();
+        this.set_log_panel_open(next);
+        window.refresh();
+    });
+    let log_chip = header_chip("Log")
+        .border_color(if view.log_panel_open() {
+            rgb(0x2563eb)
+        } else {
+            rgb(0x1f2937)
+        })
+        .on_mouse_down(MouseButton::Left, toggle_log);
+
     let mut header_right = div()
         .flex()
         .items_center()
         .gap_2()
-        .child(header_chip("Log"))
+        .child(log_chip)
         .child(header_chip("Auto"));
     header_right = header_right
         .child(settings_button)
@@ -451,7 +583,7 @@ fn build_header_bar(
 
 fn build_quick_ranges(view: &mut ChartView, cx: &mut Context<ChartView>) -> Div {
     let mut quick_ranges = div().flex().items_center().gap_2();
-    for (idx, (label, _)) in QUICK_RANGE_WINDOWS.iter().enumerate() {
+    for (idx, (label, _)) in view.quick_ranges().iter().enumerate() {
         let is_active = view.current_range_index() == idx;
         let handle = cx.listener(move |this: &mut ChartView, _: &MouseDownEvent, window, _| {
             this.apply_range_index(idx, true);
@@ -460,12 +592,24 @@ fn build_quick_ranges(view: &mut ChartView, cx: &mut Context<ChartView>) -> Div
         quick_ranges = quick_ranges
             .child(range_button(*label, is_active).on_mouse_down(MouseButton::Left, handle));
     }
+    let fit = cx.listener(|this: &mut ChartView, _: &MouseDownEvent, window, _| {
+        this.fit_to_data();
+        window.refresh();
+    });
+    let lock = cx.listener(|this: &mut ChartView, _: &MouseDownEvent, window, _| {
+        this.toggle_price_lock();
+        window.refresh();
+    });
+    let locked = view.price_lock().is_some();
     quick_ranges
+        .child(range_button("Lock", locked).on_mouse_down(MouseButton::Left, lock))
+        .child(range_button("Fit", false).on_mouse_down(MouseButton::Left, fit))
 }
 
 fn build_footer_bar(view: &mut ChartView, cx: &mut Context<ChartView>, state: &RenderState) -> Div {
     let quick_ranges = build_quick_ranges(view, cx);
-    chart_footer(
+    let scrubber = replay_scrubber(view, cx);
+    let footer = chart_footer(
         quick_ranges,
         state.interval_label.clone(),
         state.candle_count,
@@ -474,5 +618,9 @@ fn build_footer_bar(view: &mut ChartView, cx: &mut Context<ChartView>, state: &R
         state.playback_detail.clone(),
         state.playback_dot_hex,
         state.timezone_label.clone(),
-    )
+    );
+    match scrubber {
+        Some(scrubber) => div().flex().flex_col().child(scrubber).child(footer),
+        None => footer,
+    }
 }
@@ -3,6 +3,7 @@ use gpui::{
     ScrollWheelEvent, div, prelude::*, px, rgb, rgba,
 };
 
+use crate::chart::canvas::COMPARE_LINE_HEX;
 use crate::chart::view::ChartView;
 
 /// Builds the main chart area (price + volume + time axis).
@@ -10,9 +11,11 @@ use crate::chart::view::ChartView;
 pub fn chart_body(
     view: &mut ChartView,
     cx: &mut Context<ChartView>,
-    price_labels: [String; 3],
+    price_labels: Vec<String>,
+    price_ticks: Option<Vec<f64>>,
+    compare_labels: Option<Vec<String>>,
     chart: impl IntoElement,
-    volume: impl IntoElement,
+    volume: Option<impl IntoElement>,
     start_label: String,
     mid_label: String,
     end_label: String,
@@ -25,6 +28,23 @@ pub fn chart_body(
             }
         });
 
+    let track_volume_bounds =
+        cx.processor(|this: &mut ChartView, bounds: Vec<Bounds<Pixels>>, _, _| {
+            if let Some(canvas_bounds) = bounds.first() {
+                this.volume_bounds = Some(*canvas_bounds);
+            }
+        });
+
+    let handle_volume_hover = cx.listener(
+        move |this: &mut ChartView, event: &MouseMoveEvent, window, _| {
+            if this.settings_open || this.dragging {
+                return;
+            }
+            this.handle_hover(event, candle_count);
+            window.refresh();
+        },
+    );
+
     let handle_scroll = cx.listener(
         |this: &mut ChartView, event: &ScrollWheelEvent, window, _| {
             this.handle_scroll(event, window);
@@ -37,6 +57,12 @@ pub fn chart_body(
                 return;
             }
             if event.button == MouseButton::Left {
+                this.context_menu = None;
+                if event.modifiers.shift {
+                    this.toggle_pin_hovered();
+                    window.refresh();
+                    return;
+                }
                 this.dragging = true;
                 this.last_drag_position =
                     Some((f32::from(event.position.x), f32::from(event.position.y)));
@@ -44,6 +70,15 @@ pub fn chart_body(
             }
         });
 
+    let open_context_menu =
+        cx.listener(|this: &mut ChartView, event: &MouseDownEvent, window, _| {
+            if this.settings_open || this.symbol_search_open {
+                return;
+            }
+            this.context_menu = Some((f32::from(event.position.x), f32::from(event.position.y)));
+            window.refresh();
+        });
+
     let handle_mouse_up = cx.listener(|this: &mut ChartView, _: &MouseUpEvent, window, _| {
         if this.settings_open {
             this.dragging = false;
@@ -86,7 +121,7 @@ pub fn chart_body(
                 let price = view.price_max - (view.price_max - view.price_min) * frac as f64;
                 let label_h = 18.0;
                 let mut top = frac * height - label_h * 0.5;
-                top = top.clamp(0.0, height - label_h);
+                top = top.clamp(0.0, (height - label_h).max(0.0));
 
                 Some(
                     div()
@@ -112,7 +147,7 @@ pub fn chart_body(
             None
         };
 
-    let mut price_axis = div()
+    let price_axis = div()
         .w(px(82.))
         .h_full()
         .flex()
@@ -125,10 +160,26 @@ pub fn chart_body(
         .border_color(rgb(0x1f2937))
         .text_xs()
         .text_color(rgb(0x9ca3af))
-        .relative()
-        .child(price_labels[0].clone())
-        .child(price_labels[1].clone())
-        .child(price_labels[2].clone());
+        .relative();
+    // Fixed-step labels sit at their price instead of being spread evenly.
+    let mut price_axis = match (price_ticks, view.chart_bounds) {
+        (Some(ticks), Some(bounds)) => {
+            let height = f32::from(bounds.size.height);
+            let span = (view.price_max - view.price_min).max(f64::EPSILON);
+            price_axis.children(ticks.iter().zip(price_labels).map(|(tick, label)| {
+                let frac = ((view.price_max - tick) / span) as f32;
+                let label_h = 14.0;
+                let top = (frac * height - label_h * 0.5).clamp(0.0, (height - label_h).max(0.0));
+                div()
+                    .absolute()
+                    .right(px(8.))
+                    .top(px(top))
+                    .h(px(label_h))
+                    .child(label)
+            }))
+        }
+        _ => price_axis.children(price_labels),
+    };
 
     price_axis = if let Some(label) = hover_price_label {
         price_axis.child(label)
@@ -143,11 +194,28 @@ pub fn chart_body(
         .h_full()
         .min_h(px(320.))
         .on_mouse_down(MouseButton::Left, handle_mouse_down)
+        .on_mouse_down(MouseButton::Right, open_context_menu)
         .on_mouse_move(handle_mouse_move)
         .on_mouse_up(MouseButton::Left, handle_mouse_up)
         .on_scroll_wheel(handle_scroll)
         .child(price_axis)
-        .child(canvas_region);
+        .child(canvas_region)
+        .children(compare_labels.map(|labels| {
+            div()
+                .w(px(82.))
+                .h_full()
+                .flex()
+                .flex_col()
+                .justify_between()
+                .px_2()
+                .bg(rgb(0x0f172a))
+                .border_l_1()
+                .border_color(rgb(0x1f2937))
+                .text_xs()
+                .text_color(rgb(COMPARE_LINE_HEX))
+                .debug_selector(|| "compare-axis".to_string())
+                .children(labels)
+        }));
 
     let time_axis = div()
         .h(px(28.))
@@ -164,6 +232,33 @@ pub fn chart_body(
         .child(mid_label)
         .child(end_label);
 
+    // Without volume the price row is the only flex child and takes the full height.
+    let volume_pane = volume.map(|volume| {
+        div()
+            .flex()
+            .w_full()
+            .h(px(120.))
+            .min_h(px(100.))
+            .debug_selector(|| "volume-pane".to_string())
+            .child(
+                div()
+                    .w(px(82.))
+                    .h_full()
+                    .bg(rgb(0x0f172a))
+                    .border_r_1()
+                    .border_color(rgb(0x1f2937)),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .w_full()
+                    .h_full()
+                    .on_children_prepainted(track_volume_bounds)
+                    .on_mouse_move(handle_volume_hover)
+                    .child(div().flex_1().w_full().h_full().child(volume)),
+            )
+    });
+
     div()
         .flex()
         .flex_col()
@@ -171,27 +266,12 @@ pub fn chart_body(
         .w_full()
         .h_full()
         .min_h(px(420.))
-        .bg(rgb(0x0b1220))
+        .bg(rgb(view.theme().background))
         .border_1()
         .border_color(rgb(0x1f2937))
         .rounded_md()
         .overflow_hidden()
         .child(chart_row)
-        .child(
-            div()
-                .flex()
-                .w_full()
-                .h(px(120.))
-                .min_h(px(100.))
-                .child(
-                    div()
-                        .w(px(82.))
-                        .h_full()
-                        .bg(rgb(0x0f172a))
-                        .border_r_1()
-                        .border_color(rgb(0x1f2937)),
-                )
-                .child(div().flex_1().w_full().h_full().child(volume)),
-        )
+        .children(volume_pane)
         .child(time_axis)
 }
@@ -20,6 +20,7 @@ pub fn header_controls(
             this.symbol_search_open = !was_open;
             this.interval_select_open = false;
             if this.symbol_search_open {
+                this.reset_symbol_search();
                 this.focus_handle.focus(window);
             }
             window.refresh();
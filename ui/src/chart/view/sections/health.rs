@@ -0,0 +1,133 @@
+use core::{Candle, CandleCoverage, Interval, SessionCalendar, find_gaps};
+use gpui::{Div, div, prelude::*, rgb};
+use time::{Duration, OffsetDateTime, macros::format_description};
+
+use crate::chart::view::widgets::stat_row;
+
+/// Coverage summary for the active series, shown in the sidebar "Data health" card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataHealth {
+    pub first: Option<OffsetDateTime>,
+    pub last: Option<OffsetDateTime>,
+    pub count: usize,
+    pub gaps: usize,
+    pub largest_gap: Option<Duration>,
+    /// What the store holds for this symbol, which may differ from what is loaded.
+    pub stored: Option<CandleCoverage>,
+}
+
+/// Summarizes `candles`; raw series (`interval == None`) are checked against their smallest
+/// spacing.
+pub fn compute_data_health(
+    candles: &[Candle],
+    interval: Option<Interval>,
+    stored: Option<CandleCoverage>,
+) -> DataHealth {
+    let gaps = interval
+        .or_else(|| infer_interval(candles))
+        .map(|interval| find_gaps(candles, interval, &SessionCalendar::always_open()))
+        .unwrap_or_default();
+    DataHealth {
+        first: candles.first().map(|c| c.timestamp),
+        last: candles.last().map(|c| c.timestamp),
+        count: candles.len(),
+        gaps: gaps.len(),
+        largest_gap: gaps.iter().map(|(start, end)| *end - *start).max(),
+        stored,
+    }
+}
+
+fn infer_interval(candles: &[Candle]) -> Option<Interval> {
+    let step = candles
+        .windows(2)
+        .map(|pair| (pair[1].timestamp - pair[0].timestamp).whole_seconds())
+        .filter(|secs| *secs > 0)
+        .min()?;
+    u32::try_from(step).ok().map(Interval::Second)
+}
+
+fn format_ts(ts: Option<OffsetDateTime>) -> String {
+    let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+    ts.and_then(|ts| ts.format(&format).ok())
+        .unwrap_or_else(|| "--".to_string())
+}
+
+pub(crate) fn format_span(span: Duration) -> String {
+    let secs = span.whole_seconds();
+    if secs >= 86_400 {
+        format!("{}d {}h", secs / 86_400, secs % 86_400 / 3_600)
+    } else if secs >= 3_600 {
+        format!("{}h {}m", secs / 3_600, secs % 3_600 / 60)
+    } else if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+pub fn health_card(health: &DataHealth) -> Div {
+    let stored = health
+        .stored
+        .map(|c| format!("{} rows", c.count))
+        .unwrap_or_else(|| "--".to_string());
+    div()
+        .bg(rgb(0x0b1220))
+        .border_1()
+        .border_color(rgb(0x1f2937))
+        .rounded_md()
+        .p_3()
+        .flex()
+        .flex_col()
+        .gap_2()
+        .child(
+            div()
+                .text_sm()
+                .text_color(rgb(0x9ca3af))
+                .child("Data health"),
+        )
+        .child(stat_row("First", format_ts(health.first)))
+        .child(stat_row("Last", format_ts(health.last)))
+        .child(stat_row("Candles", health.count.to_string()))
+        .child(stat_row("Gaps", health.gaps.to_string()))
+        .child(stat_row(
+            "Largest gap",
+            health
+                .largest_gap
+                .map(format_span)
+                .unwrap_or_else(|| "--".to_string()),
+        ))
+        .child(stat_row("Stored", stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn health_counts_one_known_gap() {
+        let minutes = [0, 1, 2, 6, 7];
+        let candles: Vec<Candle> = minutes
+            .iter()
+            .map(|m| Candle {
+                timestamp: datetime!(2024-01-01 00:00:00 UTC) + Duration::minutes(*m),
+                open: 1.0,
+                high: 2.0,
+                low: 0.5,
+                close: 1.5,
+                volume: 10.0,
+            })
+            .collect();
+
+        let health = compute_data_health(&candles, None, None);
+        assert_eq!(health.first, Some(datetime!(2024-01-01 00:00:00 UTC)));
+        assert_eq!(health.last, Some(datetime!(2024-01-01 00:07:00 UTC)));
+        assert_eq!(health.count, 5);
+        assert_eq!(health.gaps, 1);
+        assert_eq!(health.largest_gap, Some(Duration::minutes(3)));
+        assert_eq!(format_span(Duration::minutes(3)), "3m 0s");
+
+        let resampled = compute_data_health(&candles, Some(Interval::Minute(1)), None);
+        assert_eq!(resampled.gaps, 1);
+    }
+}
@@ -1,4 +1,3 @@
-use core::Interval;
 use gpui::{
     Context, Div, KeyDownEvent, MouseButton, MouseDownEvent, MouseMoveEvent, Window, div,
     prelude::*, px, rgb, rgba,
@@ -13,6 +12,7 @@ use crate::chart::view::{
 use crate::components::button_effect;
 use crate::components::loading_sand::loading_sand;
 
+use super::health::health_card;
 use super::sidebar::sidebar;
 use super::watchlist::watchlist_panel;
 
@@ -23,8 +23,9 @@ pub(crate) fn build_sidebar_panels(
 ) -> Div {
     let watchlist_panel = watchlist_panel(view, cx);
     let instrument_card = instrument_card(state);
+    let health_card = health_card(&view.data_health());
     let trading_stub = trading_stub();
-    sidebar(watchlist_panel, instrument_card, trading_stub)
+    sidebar(watchlist_panel, instrument_card, health_card, trading_stub)
 }
 
 pub(crate) fn build_body_layout(chart_area: Div, sidebar: Div) -> Div {
@@ -61,12 +62,14 @@ pub(crate) fn build_root_container(header: Div, body: Div, footer: Div) -> Div {
         .child(footer)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn build_layered_view(
     view: &mut ChartView,
     cx: &mut Context<ChartView>,
     header: Div,
     body: Div,
     footer: Div,
+    event_markers: Option<Div>,
     search_overlay: Option<Div>,
     interval_menu: Option<Div>,
     settings_overlay: Option<Div>,
@@ -93,6 +96,10 @@ pub(crate) fn build_layered_view(
         .track_focus(&view.focus_handle)
         .child(root);
 
+    if let Some(markers) = event_markers {
+        layered = layered.child(markers);
+    }
+
     if let Some(overlay) = search_overlay {
         layered = layered.child(overlay);
     }
@@ -114,6 +121,23 @@ pub(crate) fn build_layered_view(
     }
 
     let handle_keys = cx.listener(|this: &mut ChartView, event: &KeyDownEvent, window, cx| {
+        if this.interval_select_open
+            && this.custom_interval_input.is_some()
+            && event.keystroke.key != "escape"
+        {
+            if this.handle_custom_interval_key(event) {
+                cx.stop_propagation();
+                window.refresh();
+            }
+            return;
+        }
+        if this.symbol_search_open && event.keystroke.key != "escape" {
+            if this.handle_symbol_search_key(event, window, cx) {
+                cx.stop_propagation();
+                window.refresh();
+            }
+            return;
+        }
         if event.is_held || event.keystroke.key != "escape" {
             return;
         }
@@ -123,8 +147,15 @@ pub(crate) fn build_layered_view(
         } else if this.symbol_search_open {
             this.symbol_search_open = false;
             this.symbol_search_add_to_watchlist = false;
+        } else if this.interval_select_open && this.custom_interval_input.is_some() {
+            this.custom_interval_input = None;
+            this.custom_interval_error = None;
         } else if this.interval_select_open {
             this.interval_select_open = false;
+        } else if this.context_menu.is_some() {
+            this.context_menu = None;
+        } else if this.log_panel_open() {
+            this.set_log_panel_open(false);
         } else {
             return;
         }
@@ -144,7 +175,7 @@ pub(crate) fn build_layered_view(
                 return;
             }
 
-            let should_clear = match this.chart_bounds {
+            let outside = |bounds: Option<gpui::Bounds<gpui::Pixels>>| match bounds {
                 Some(bounds) => {
                     let bx = f32::from(bounds.origin.x);
                     let by = f32::from(bounds.origin.y);
@@ -156,6 +187,7 @@ pub(crate) fn build_layered_view(
                 }
                 None => true,
             };
+            let should_clear = outside(this.chart_bounds) && outside(this.volume_bounds);
 
             if should_clear && (this.hover_index.is_some() || this.hover_position.is_some()) {
                 this.hover_index = None;
@@ -211,7 +243,6 @@ pub(crate) fn build_loading_overlay(view: &ChartView, cx: &mut Context<ChartView
 pub(crate) fn build_interval_menu(
     view: &mut ChartView,
     cx: &mut Context<ChartView>,
-    options: &[(Option<Interval>, &str)],
 ) -> Option<Div> {
     if !view.interval_select_open {
         return None;
@@ -224,7 +255,6 @@ pub(crate) fn build_interval_menu(
     interval_menu(
         view,
         cx,
-        options,
         origin,
         view.interval_trigger_height,
         INTERVAL_TRIGGER_WIDTH,
@@ -1,5 +1,7 @@
 pub mod body;
 pub mod header;
+pub mod health;
 pub mod layout;
+pub mod replay;
 pub mod sidebar;
 pub mod watchlist;
@@ -0,0 +1,131 @@
+use gpui::{
+    Bounds, Context, Div, MouseButton, MouseDownEvent, MouseMoveEvent, Pixels, div, prelude::*, px,
+    relative, rgb,
+};
+
+use crate::chart::view::ChartView;
+
+/// Draggable replay progress bar; only shown while replay is enabled.
+pub fn replay_scrubber(view: &mut ChartView, cx: &mut Context<ChartView>) -> Option<Div> {
+    if !view.replay_enabled() || view.candles.is_empty() {
+        return None;
+    }
+
+    let progress = view.replay_progress();
+    let revealed = view.replay_revealed();
+    let total = view.candles.len();
+
+    let track_bounds = cx.processor(|this: &mut ChartView, bounds: Vec<Bounds<Pixels>>, _, _| {
+        if let Some(track) = bounds.first() {
+            this.replay_scrubber_bounds = Some(*track);
+        }
+    });
+    let scrub_start = cx.listener(|this: &mut ChartView, event: &MouseDownEvent, window, _| {
+        this.scrub_replay_to(f32::from(event.position.x));
+        window.refresh();
+    });
+    let scrub_drag = cx.listener(|this: &mut ChartView, event: &MouseMoveEvent, window, _| {
+        if event.pressed_button != Some(MouseButton::Left) {
+            return;
+        }
+        this.scrub_replay_to(f32::from(event.position.x));
+        window.refresh();
+    });
+
+    let track = div()
+        .relative()
+        .flex_1()
+        .h(px(8.))
+        .rounded_full()
+        .bg(rgb(0x1f2937))
+        .on_mouse_down(MouseButton::Left, scrub_start)
+        .on_mouse_move(scrub_drag)
+        .child(
+            div()
+                .absolute()
+                .left(px(0.))
+                .top(px(0.))
+                .h_full()
+                .w(relative(progress))
+                .rounded_full()
+                .bg(rgb(0xf59e0b)),
+        )
+        .debug_selector(|| "replay-scrubber".to_string());
+
+    Some(
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .px_3()
+            .py_2()
+            .bg(rgb(0x0f172a))
+            .border_t_1()
+            .border_color(rgb(0x1f2937))
+            .child(div().text_xs().text_color(rgb(0x9ca3af)).child("Replay"))
+            .child(
+                div()
+                    .flex()
+                    .flex_1()
+                    .on_children_prepainted(track_bounds)
+                    .child(track),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0xe5e7eb))
+                    .child(format!("{revealed} / {total}")),
+            ),
+    )
+}
+
+impl ChartView {
+    fn scrub_replay_to(&mut self, x: f32) {
+        let Some(bounds) = self.replay_scrubber_bounds else {
+            return;
+        };
+        let width = f32::from(bounds.size.width);
+        if width <= 0.0 {
+            return;
+        }
+        let fraction = (x - f32::from(bounds.origin.x)) / width;
+        self.set_replay_progress(fraction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use gpui::{Modifiers, TestAppContext, point};
+
+    use crate::chart::view::tests::test_view;
+    use crate::test_support::minute_candles;
+
+    #[gpui::test]
+    async fn scrubbing_to_half_reveals_half_the_candles(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let (chart, cx) = test_view(&mut cx, minute_candles(20));
+
+        chart.update(cx, |chart, cx| {
+            chart.set_replay_mode(true);
+            cx.notify();
+        });
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert_eq!(chart.update(cx, |chart, _| chart.replay_revealed()), 20);
+
+        let track = cx
+            .debug_bounds("replay-scrubber")
+            .expect("replay-scrubber bounds");
+        let half = point(track.origin.x + track.size.width * 0.5, track.center().y);
+        cx.simulate_click(half, Modifiers::none());
+        cx.run_until_parked();
+
+        let (revealed, range) = chart.update(cx, |chart, _| {
+            (chart.replay_revealed(), chart.visible_range())
+        });
+        assert_eq!(revealed, 10);
+        assert_eq!(range.1, 10);
+    }
+}
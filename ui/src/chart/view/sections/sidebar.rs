@@ -2,7 +2,12 @@ use gpui::{Div, div, prelude::*, px, rgb};
 
 use crate::chart::view::SIDEBAR_WIDTH;
 
-pub fn sidebar(watchlist_panel: Div, instrument_card: Div, trading_stub: Div) -> Div {
+pub fn sidebar(
+    watchlist_panel: Div,
+    instrument_card: Div,
+    health_card: Div,
+    trading_stub: Div,
+) -> Div {
     div()
         .w(px(SIDEBAR_WIDTH))
         .bg(rgb(0x0b1220))
@@ -14,5 +19,6 @@ pub fn sidebar(watchlist_panel: Div, instrument_card: Div, trading_stub: Div) ->
         .gap_3()
         .child(watchlist_panel)
         .child(instrument_card)
+        .child(health_card)
         .child(trading_stub)
 }
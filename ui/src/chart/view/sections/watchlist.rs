@@ -15,6 +15,7 @@ pub fn watchlist_panel(view: &mut ChartView, cx: &mut Context<ChartView>) -> Div
             this.symbol_search_open = !should_close;
             this.interval_select_open = false;
             if this.symbol_search_open {
+                this.reset_symbol_search();
                 this.focus_handle.focus(window);
             }
             window.refresh();
@@ -157,6 +158,32 @@ fn watchlist_list(view: &mut ChartView, cx: &mut Context<ChartView>) -> gpui::St
                     .text_color(rgb(0x9ca3af))
                     .child(exchange),
             );
+        let retry = (view.load_error.is_some()
+            && view.failed_symbol.as_deref() == Some(symbol.as_str()))
+        .then(|| {
+            let retry_handler =
+                cx.listener(|this: &mut ChartView, _: &MouseDownEvent, window, cx| {
+                    this.retry_failed_load(window, cx);
+                    cx.stop_propagation();
+                    window.refresh();
+                });
+            let retry_id: gpui::SharedString = format!("watchlist-retry-{symbol}").into();
+            let symbol = symbol.clone();
+            button_effect::apply(
+                div()
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .bg(rgb(0x1f2937))
+                    .text_xs()
+                    .text_color(rgb(0xef4444))
+                    .on_mouse_down(MouseButton::Left, retry_handler)
+                    .child("Retry")
+                    .id(retry_id),
+                0x1f2937,
+            )
+            .debug_selector(move || format!("watchlist-retry-{symbol}"))
+        });
         let remove_button = button_effect::apply_custom(
             remove_button(remove_handler).id(remove_id),
             0x1f2937,
@@ -178,6 +205,7 @@ fn watchlist_list(view: &mut ChartView, cx: &mut Context<ChartView>) -> gpui::St
                     .truncate()
                     .child(label),
             )
+            .children(retry)
             .child(remove_button);
         watchlist_list = watchlist_list.child(button_effect::apply_custom(
             div()
@@ -212,6 +240,7 @@ mod tests {
     use gpui::{Modifiers, TestAppContext};
 
     use crate::ChartMeta;
+    use crate::data::symbols::SymbolMeta;
 
     #[gpui::test]
     async fn watchlist_remove_buttons_remove_the_clicked_symbol(cx: &TestAppContext) {
@@ -222,6 +251,7 @@ mod tests {
                 ChartMeta {
                     source: "AAPL".to_string(),
                     initial_interval: None,
+                    initial_visible: None,
                 },
                 None,
                 cx,
@@ -266,6 +296,7 @@ mod tests {
                 ChartMeta {
                     source: "AAPL".to_string(),
                     initial_interval: None,
+                    initial_visible: None,
                 },
                 None,
                 cx,
@@ -303,6 +334,7 @@ mod tests {
                 ChartMeta {
                     source: "AAPL".to_string(),
                     initial_interval: None,
+                    initial_visible: None,
                 },
                 None,
                 cx,
@@ -341,6 +373,7 @@ mod tests {
                 ChartMeta {
                     source: "US02Y".to_string(),
                     initial_interval: None,
+                    initial_visible: None,
                 },
                 None,
                 cx,
@@ -364,4 +397,75 @@ mod tests {
         let watchlist = chart.update(cx, |chart, _| chart.watchlist.clone());
         assert_eq!(watchlist, vec!["US02Y".to_string()]);
     }
+
+    #[gpui::test]
+    async fn retry_reloads_the_failed_symbol_and_clears_the_error(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let path = std::env::temp_dir().join(format!(
+            "gpui-kbar-retry-{}.csv",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let (chart, cx) = cx.add_window_view(|_, cx| {
+            ChartView::new(
+                Vec::<Candle>::new(),
+                ChartMeta {
+                    source: "AAPL".to_string(),
+                    initial_interval: None,
+                    initial_visible: None,
+                },
+                None,
+                cx,
+            )
+        });
+
+        let source = path.to_string_lossy().to_string();
+        chart.update_in(cx, |chart, window, cx| {
+            chart.symbols.insert(
+                "RETRY".to_string(),
+                SymbolMeta {
+                    symbol: "RETRY".to_string(),
+                    name: "Retry Corp".to_string(),
+                    source,
+                    exchange: "TEST".to_string(),
+                    badge: String::new(),
+                    market: String::new(),
+                    filters: Vec::new(),
+                    venue: String::new(),
+                },
+            );
+            chart.watchlist = vec!["RETRY".to_string()];
+            chart.start_symbol_load("RETRY".to_string(), false, window, cx);
+        });
+        cx.run_until_parked();
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+
+        let failed = chart.update(cx, |chart, _| chart.load_error.clone());
+        assert!(failed.is_some(), "missing file fails the load");
+        let retry_bounds = cx
+            .debug_bounds("watchlist-retry-RETRY")
+            .expect("retry control on the errored row");
+
+        std::fs::write(
+            &path,
+            "timestamp,open,high,low,close,volume\n\
+             2024-01-01T00:00:00Z,1,2,0.5,1.5,10\n",
+        )
+        .unwrap();
+        cx.simulate_click(retry_bounds.center(), Modifiers::none());
+        cx.run_until_parked();
+        std::fs::remove_file(&path).ok();
+
+        let (source, load_error) = chart.update(cx, |chart, _| {
+            (chart.current_source(), chart.load_error.clone())
+        });
+        assert_eq!(source, "RETRY");
+        assert_eq!(load_error, None);
+        cx.refresh().expect("refresh");
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("watchlist-retry-RETRY").is_none());
+    }
 }
@@ -5,26 +5,73 @@ use std::{
     time::Instant,
 };
 
-use core::{Candle, Interval, LoadOptions, bounds, load_csv, resample};
+use core::{
+    BoundsIndex, Candle, CandleCoverage, CandleExt, CrossDir, Interval, IntervalParseError,
+    LoadOptions, NullPolicy, PivotLevels, SourceFormat, StorageMode, aggregate, bounds, crossovers,
+    detect_format, load_csv, load_csv_ext, load_parquet_ext, pivot, resample, resample_to_count,
+    sma, time_to_close, write_csv,
+};
 use gpui::{
-    Bounds, Context, EventEmitter, FocusHandle, Pixels, SharedString, Subscription, Window,
+    App, AppContext, Bounds, Context, Entity, EventEmitter, FocusHandle, KeyDownEvent, Pixels,
+    SharedString, Subscription, Window,
 };
-use time::Duration;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
 use time::macros::format_description;
+use time::{Duration, OffsetDateTime, UtcOffset};
 
 use super::super::ChartMeta;
+use super::super::canvas::{
+    XAxisMode, candle_marks, default_grid_lines, fixed_step_ticks, gridline_ys, plot_width,
+    price_to_y,
+};
+use super::super::compare::{AxisMode, CompareSeries};
+use super::super::raster::{paint_price_pane, pixel};
+use super::super::spread::{SpreadQuote, spread_quotes};
+use super::super::theme::ChartTheme;
+use super::overlay::TooltipPlacement;
+use super::sections::health::format_span;
+use super::sections::health::{DataHealth, compute_data_health};
 use crate::data::{
     symbols::{SymbolMeta, load_symbols},
     universe::{SymbolSearchEntry, load_universe, load_universe_from_store},
 };
 use crate::live::{
-    DEFAULT_BACKFILL_LIMIT, LiveConfig, LiveEvent, LiveStatus, backfill_candles, cursor_key_for,
-    get_cursor, run_live_coordinator, tokio_runtime,
+    LiveConfig, LiveDebugSnapshot, LiveEvent, LiveStatus, backfill_history, cursor_key_for,
+    get_cursor, run_live_coordinator_with_debug, tokio_runtime,
 };
+use crate::logging::{log_live, log_loading};
 use crate::perf::{PerfSpec, generate_perf_candles, parse_perf_source, perf_label};
 use core::DuckDbStore;
 use tokio::sync::mpsc;
 
+/// Built-in interval menu entries; custom ones are added per view.
+pub(super) const INTERVAL_OPTIONS: &[(Option<Interval>, &str)] = &[
+    (None, "raw"),
+    (Some(Interval::Second(3)), "3s"),
+    (Some(Interval::Second(10)), "10s"),
+    (Some(Interval::Second(30)), "30s"),
+    (Some(Interval::Minute(1)), "1m"),
+    (Some(Interval::Minute(5)), "5m"),
+    (Some(Interval::Minute(10)), "10m"),
+    (Some(Interval::Minute(15)), "15m"),
+    (Some(Interval::Minute(30)), "30m"),
+    (Some(Interval::Hour(1)), "1h"),
+    (Some(Interval::Day(1)), "1d"),
+];
+
+/// Enables the frame-time debug overlay when set to anything other than `0`/`false`.
+pub const DEBUG_OVERLAY_ENV: &str = "GPUI_KBAR_DEBUG";
+
+fn debug_overlay_from_env() -> bool {
+    std::env::var(DEBUG_OVERLAY_ENV)
+        .map(|v| {
+            let v = v.trim();
+            !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false")
+        })
+        .unwrap_or(false)
+}
+
 pub const QUICK_RANGE_WINDOWS: [(&str, Option<Duration>); 8] = [
     ("1D", Some(Duration::days(1))),
     ("5D", Some(Duration::days(5))),
@@ -36,6 +83,52 @@ pub const QUICK_RANGE_WINDOWS: [(&str, Option<Duration>); 8] = [
     ("ALL", None),
 ];
 
+const SECOND_QUICK_RANGES: [(&str, Option<Duration>); 5] = [
+    ("15m", Some(Duration::minutes(15))),
+    ("1H", Some(Duration::hours(1))),
+    ("4H", Some(Duration::hours(4))),
+    ("1D", Some(Duration::days(1))),
+    ("ALL", None),
+];
+
+const MINUTE_QUICK_RANGES: [(&str, Option<Duration>); 6] = [
+    ("4H", Some(Duration::hours(4))),
+    ("1D", Some(Duration::days(1))),
+    ("5D", Some(Duration::days(5))),
+    ("1M", Some(Duration::days(30))),
+    ("3M", Some(Duration::days(90))),
+    ("ALL", None),
+];
+
+const HOUR_QUICK_RANGES: [(&str, Option<Duration>); 6] = [
+    ("5D", Some(Duration::days(5))),
+    ("1M", Some(Duration::days(30))),
+    ("3M", Some(Duration::days(90))),
+    ("6M", Some(Duration::days(180))),
+    ("1Y", Some(Duration::days(365))),
+    ("ALL", None),
+];
+
+const DAY_QUICK_RANGES: [(&str, Option<Duration>); 5] = [
+    ("1M", Some(Duration::days(30))),
+    ("6M", Some(Duration::days(180))),
+    ("1Y", Some(Duration::days(365))),
+    ("5Y", Some(Duration::days(365 * 5))),
+    ("ALL", None),
+];
+
+/// Quick-range buttons that make sense for `interval`; raw data keeps the full
+/// [`QUICK_RANGE_WINDOWS`] set. Every set ends with `ALL`.
+pub fn quick_ranges_for(interval: Option<Interval>) -> &'static [(&'static str, Option<Duration>)] {
+    match interval {
+        None => &QUICK_RANGE_WINDOWS,
+        Some(Interval::Second(_)) => &SECOND_QUICK_RANGES,
+        Some(Interval::Minute(_)) => &MINUTE_QUICK_RANGES,
+        Some(Interval::Hour(_)) => &HOUR_QUICK_RANGES,
+        Some(Interval::Day(_)) => &DAY_QUICK_RANGES,
+    }
+}
+
 #[derive(Clone)]
 struct LoadResult {
     symbol: String,
@@ -43,6 +136,8 @@ struct LoadResult {
     resamples: Vec<(Option<Interval>, Arc<[Candle]>)>,
     needs_persist: bool,
     live_last_sequence: Option<u64>,
+    /// Bid/ask quotes read alongside a file load; empty for cached and live loads.
+    spread: Arc<[SpreadQuote]>,
 }
 
 #[derive(Clone)]
@@ -61,7 +156,7 @@ enum LoadMsg {
 struct PersistSnapshot {
     store: Option<Arc<Mutex<DuckDbStore>>>,
     source: String,
-    interval_label: String,
+    interval_key: String,
     range_index: usize,
     view_offset: f32,
     zoom: f32,
@@ -119,6 +214,45 @@ fn dedup_candles_by_timestamp(candles: Vec<Candle>) -> Vec<Candle> {
     unique
 }
 
+/// Default cap on in-memory base candles during live mode; the store keeps full history.
+pub const DEFAULT_MAX_LIVE_CANDLES: usize = 500_000;
+
+// Live updates re-send the in-progress bar; treat float noise below this as "unchanged".
+const LIVE_MERGE_EPSILON: f64 = 1e-9;
+
+/// Appends `incoming`, replacing the last candle when timestamps match; returns whether
+/// anything changed.
+fn merge_live_tail(base: &mut Vec<Candle>, incoming: Vec<Candle>) -> bool {
+    let mut changed = false;
+    for candle in incoming {
+        match base.last_mut() {
+            Some(last) if last.timestamp == candle.timestamp => {
+                if !last.approx_eq(&candle, LIVE_MERGE_EPSILON) {
+                    *last = candle;
+                    changed = true;
+                }
+            }
+            _ => {
+                base.push(candle);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+/// `cached` (resampled from a prefix of `base`) extended to cover all of `base`; only the last
+/// cached bucket and anything after it are recomputed.
+fn extend_resampled(cached: &[Candle], base: &[Candle], interval: Interval) -> Vec<Candle> {
+    let keep = cached.len().saturating_sub(1);
+    let from = cached.get(keep).map_or(0, |tail| {
+        base.partition_point(|c| c.timestamp < tail.timestamp)
+    });
+    let mut out = cached[..keep].to_vec();
+    out.extend(resample(&base[from..], interval));
+    out
+}
+
 fn normalize_resamples(
     base: &Arc<[Candle]>,
     resamples: Vec<(Option<Interval>, Arc<[Candle]>)>,
@@ -152,6 +286,52 @@ fn format_age(age: std::time::Duration) -> String {
     }
 }
 
+/// Session key holding [`ChartView::serialize_state`].
+const VIEW_STATE_KEY: &str = "view_state";
+
+/// User-adjustable view configuration persisted as one JSON blob.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ViewSnapshot {
+    interval: String,
+    range_index: usize,
+    view_offset: f32,
+    zoom: f32,
+    replay_mode: bool,
+    replay_cursor: usize,
+    fast_wicks: bool,
+    tooltip_placement: String,
+    show_volume: bool,
+    #[serde(default)]
+    global_volume_scale: bool,
+    display_tz_secs: i32,
+    grid_lines: Option<usize>,
+    #[serde(default)]
+    price_tick_step: Option<f64>,
+    #[serde(default)]
+    right_padding_candles: usize,
+    #[serde(default)]
+    x_axis_mode: XAxisMode,
+    crossover_periods: Option<(usize, usize)>,
+    #[serde(default)]
+    show_pivots: bool,
+    #[serde(default = "default_reset_view_on_interval")]
+    reset_view_on_interval: bool,
+    #[serde(default)]
+    auto_interval: bool,
+    theme: ChartTheme,
+    max_candles: Option<usize>,
+    min_candle_px: Option<f32>,
+}
+
+/// Chart width per candle that auto interval aims for.
+pub const AUTO_INTERVAL_CANDLE_PX: f32 = 8.0;
+/// Candles auto interval aims for before the chart has been laid out.
+const AUTO_INTERVAL_FALLBACK_TARGET: usize = 150;
+
+fn default_reset_view_on_interval() -> bool {
+    true
+}
+
 pub struct ChartView {
     pub(super) focus_handle: FocusHandle,
     pub(super) base_candles: Arc<[Candle]>,
@@ -169,6 +349,13 @@ pub struct ChartView {
     live_generation: u64,
     live_last_sequence: u64,
     live_task: Option<tokio::task::JoinHandle<()>>,
+    live_debug: Option<tokio::sync::watch::Receiver<LiveDebugSnapshot>>,
+    /// Minimum time between repaints while streaming; `None` repaints on every batch.
+    live_refresh_interval: Option<std::time::Duration>,
+    /// A live batch was applied since the refresh pump last repainted.
+    live_repaint_pending: bool,
+    #[cfg(test)]
+    live_refresh_ticks: u64,
     pub(super) live_blink_on: bool,
     live_last_event: Option<Instant>,
     pub(super) live_status: LiveStatus,
@@ -177,32 +364,100 @@ pub struct ChartView {
     pub(super) zoom: f32,
     pub(super) root_origin: (f32, f32),
     pub(super) chart_bounds: Option<Bounds<Pixels>>,
+    pub(super) volume_bounds: Option<Bounds<Pixels>>,
     pub(super) interval_trigger_origin: (f32, f32),
     pub(super) interval_trigger_height: f32,
     pub(super) last_drag_position: Option<(f32, f32)>,
     pub(super) dragging: bool,
     pub(super) hover_index: Option<usize>,
     pub(super) hover_position: Option<(f32, f32)>,
+    pub(super) hover_in_volume: bool,
+    /// Candle pinned by a shift-click; the tooltip measures from it to the hovered candle.
+    pub(super) pinned_index: Option<usize>,
+    /// Whether shift was held on the last hover, which turns the measure readout on.
+    pub(super) measure_held: bool,
     pub(super) interval_select_open: bool,
+    /// Interval menu entries ordered by duration: [`INTERVAL_OPTIONS`] plus custom ones.
+    interval_options: Vec<(Option<Interval>, SharedString)>,
+    /// Text typed into the interval menu's custom entry while it is being edited.
+    pub(super) custom_interval_input: Option<String>,
+    pub(super) custom_interval_error: Option<String>,
+    /// Window position of the open right-click menu.
+    pub(super) context_menu: Option<(f32, f32)>,
     pub(super) symbol_search_open: bool,
     pub(super) symbol_search_add_to_watchlist: bool,
     force_symbol_reload: bool,
     active_range_index: usize,
+    /// Manual price-axis range; `None` auto-scales to the visible candles.
+    price_lock: Option<(f64, f64)>,
     replay_mode: bool,
+    replay_cursor: usize,
+    pub(super) replay_scrubber_bounds: Option<Bounds<Pixels>>,
+    fast_wicks: bool,
+    tooltip_placement: TooltipPlacement,
+    cache_candles: bool,
+    /// Opt-in: loads forward-fill null/non-finite prices instead of failing.
+    forward_fill: bool,
+    show_volume: bool,
+    global_volume_scale: bool,
+    display_tz: UtcOffset,
+    grid_lines: Option<usize>,
+    price_tick_step: Option<f64>,
+    right_padding_candles: usize,
+    x_axis_mode: XAxisMode,
+    pub(super) compare: Option<CompareSeries>,
+    /// Bid/ask quotes of the loaded file, drawn as a band; empty when it had none.
+    pub(super) spread_quotes: Arc<[SpreadQuote]>,
+    crossover_periods: Option<(usize, usize)>,
+    show_pivots: bool,
+    /// Re-frame the active quick range on interval changes instead of keeping the time window.
+    reset_view_on_interval: bool,
+    /// Derive the interval from the zoom level; see [`refresh_auto_interval`](Self::refresh_auto_interval).
+    auto_interval: bool,
+    /// Start of the current scroll-zoom burst and the zoom it started from.
+    pub(super) zoom_window: Option<(Instant, f32)>,
+    theme: ChartTheme,
+    /// Preset the theme was last picked from; cleared by [`set_theme`](Self::set_theme).
+    theme_preset: Option<String>,
+    /// Crossovers of the whole series, keyed by render revision and periods.
+    crossover_cache: Option<(u64, (usize, usize), Vec<(usize, CrossDir)>)>,
+    max_candles: Option<usize>,
+    min_candle_px: Option<f32>,
+    events: Vec<(OffsetDateTime, String)>,
+    in_progress: Option<OffsetDateTime>,
+    debug_overlay: bool,
+    log_panel_open: bool,
+    pub(super) last_frame_time: Option<std::time::Duration>,
+    pub(super) last_render_heavy: bool,
+    pending_initial_visible: Option<usize>,
     pub loading_symbol: Option<String>,
     pub load_error: Option<String>,
+    /// Symbol whose load produced `load_error`; its watchlist row offers a retry.
+    pub(super) failed_symbol: Option<String>,
     pub store: Option<Arc<Mutex<DuckDbStore>>>,
     pub watchlist: Vec<String>,
     load_events: Option<Subscription>,
     pub active_load_seq: u64,
     hydrated: bool,
-    symbols: HashMap<String, SymbolMeta>,
+    pub(super) symbols: HashMap<String, SymbolMeta>,
     symbol_search_filter: String,
+    symbol_search_query: String,
+    symbol_search_selected: usize,
     universe: Vec<SymbolSearchEntry>,
     resample_cache: Vec<(Option<Interval>, Arc<[Candle]>)>,
     render_cache_revision: u64,
     render_cache: Option<RenderCache>,
+    bounds_index: BoundsIndex,
     time_axis_cache: Option<TimeAxisCache>,
+    data_health_cache: Option<(u64, DataHealth)>,
+    /// Store coverage of the loaded series, queried once per data load.
+    stored_coverage: Option<Option<CandleCoverage>>,
+    /// Target of a storage mode switch still migrating in the background.
+    storage_switch: Option<StorageMode>,
+    /// Largest single-candle volume of the series, keyed by render revision.
+    global_volume_cache: Option<(u64, f64)>,
+    /// Whether any candle has volume, keyed by render revision.
+    has_volume_cache: Option<(u64, bool)>,
 }
 
 pub(super) struct RenderCache {
@@ -226,6 +481,20 @@ struct TimeAxisCache {
 }
 
 impl ChartView {
+    /// Creates a chart entity for embedding inside a host gpui view.
+    ///
+    /// Unlike `launch_chart`, this does not own the `Application` or open a window; render
+    /// the returned entity as a child of any element. Call `gpui_component::init` once at
+    /// startup if the host has not already.
+    pub fn embed(
+        candles: Vec<Candle>,
+        meta: ChartMeta,
+        store: Option<Arc<Mutex<DuckDbStore>>>,
+        cx: &mut App,
+    ) -> Entity<ChartView> {
+        cx.new(|cx| Self::new(candles, meta, store, cx))
+    }
+
     pub(crate) fn new(
         base_candles: Vec<Candle>,
         meta: ChartMeta,
@@ -238,8 +507,9 @@ impl ChartView {
             None => (base_arc.clone(), None),
         };
         let (price_min, price_max) = padded_bounds(&candles);
+        let bounds_index = BoundsIndex::new(&candles);
         let perf_from_source = parse_perf_source(&meta.source);
-        Self {
+        let mut view = Self {
             focus_handle: cx.focus_handle(),
             base_candles: base_arc.clone(),
             candles,
@@ -256,6 +526,11 @@ impl ChartView {
             live_generation: 0,
             live_last_sequence: 0,
             live_task: None,
+            live_debug: None,
+            live_refresh_interval: None,
+            live_repaint_pending: false,
+            #[cfg(test)]
+            live_refresh_ticks: 0,
             live_blink_on: false,
             live_last_event: None,
             live_status: LiveStatus::Disconnected,
@@ -264,20 +539,65 @@ impl ChartView {
             zoom: 1.0,
             root_origin: (0.0, 0.0),
             chart_bounds: None,
+            volume_bounds: None,
             interval_trigger_origin: (0.0, 0.0),
             interval_trigger_height: 40.0,
             last_drag_position: None,
             dragging: false,
             hover_index: None,
             hover_position: None,
+            hover_in_volume: false,
+            pinned_index: None,
+            measure_held: false,
             interval_select_open: false,
+            interval_options: INTERVAL_OPTIONS
+                .iter()
+                .map(|(interval, label)| (*interval, SharedString::from(*label)))
+                .collect(),
+            custom_interval_input: None,
+            custom_interval_error: None,
+            context_menu: None,
             symbol_search_open: false,
             symbol_search_add_to_watchlist: false,
             force_symbol_reload: false,
-            active_range_index: QUICK_RANGE_WINDOWS.len().saturating_sub(1),
+            active_range_index: quick_ranges_for(interval).len().saturating_sub(1),
+            price_lock: None,
             replay_mode: false,
+            replay_cursor: 0,
+            replay_scrubber_bounds: None,
+            fast_wicks: true,
+            tooltip_placement: TooltipPlacement::default(),
+            cache_candles: true,
+            forward_fill: false,
+            show_volume: true,
+            global_volume_scale: false,
+            display_tz: UtcOffset::UTC,
+            grid_lines: None,
+            price_tick_step: None,
+            right_padding_candles: 0,
+            x_axis_mode: XAxisMode::default(),
+            compare: None,
+            spread_quotes: Arc::from([]),
+            crossover_periods: None,
+            show_pivots: false,
+            reset_view_on_interval: true,
+            auto_interval: false,
+            zoom_window: None,
+            theme: ChartTheme::default(),
+            theme_preset: None,
+            crossover_cache: None,
+            max_candles: Some(DEFAULT_MAX_LIVE_CANDLES),
+            min_candle_px: None,
+            events: Vec::new(),
+            in_progress: None,
+            debug_overlay: debug_overlay_from_env(),
+            log_panel_open: false,
+            last_frame_time: None,
+            last_render_heavy: false,
+            pending_initial_visible: meta.initial_visible,
             loading_symbol: None,
             load_error: None,
+            failed_symbol: None,
             store,
             watchlist: Vec::new(),
             load_events: None,
@@ -285,23 +605,120 @@ impl ChartView {
             hydrated: false,
             symbols: HashMap::new(),
             symbol_search_filter: "All".to_string(),
+            symbol_search_query: String::new(),
+            symbol_search_selected: 0,
             universe: Vec::new(),
             resample_cache: vec![(None, base_arc)],
             render_cache_revision: 0,
             render_cache: None,
+            bounds_index,
             time_axis_cache: None,
-        }
+            data_health_cache: None,
+            stored_coverage: None,
+            storage_switch: None,
+            global_volume_cache: None,
+            has_volume_cache: None,
+        };
+        view.apply_pending_initial_visible();
+        view
     }
 
     pub fn interval_label(interval: Option<Interval>) -> SharedString {
-        let label = match interval {
-            Some(Interval::Second(n)) => format!("{n}s"),
-            Some(Interval::Minute(n)) => format!("{n}m"),
-            Some(Interval::Hour(n)) => format!("{n}h"),
-            Some(Interval::Day(n)) => format!("{n}d"),
-            None => "raw".to_string(),
+        SharedString::from(Self::interval_key(interval))
+    }
+
+    /// Canonical persisted form of an interval: the parser's input (`"5m"`), or `"raw"`.
+    pub fn interval_key(interval: Option<Interval>) -> String {
+        interval.map_or_else(|| "raw".to_string(), |interval| interval.to_string())
+    }
+
+    /// Inverse of [`interval_key`](Self::interval_key); `None` for unrecognised keys.
+    pub fn parse_interval_key(key: &str) -> Option<Option<Interval>> {
+        match key.trim() {
+            "raw" => Some(None),
+            s => s.parse::<Interval>().ok().map(Some),
+        }
+    }
+
+    pub fn interval_options(&self) -> &[(Option<Interval>, SharedString)] {
+        &self.interval_options
+    }
+
+    /// Parses `text` (e.g. `4h`) and adds it to the interval menu, persisting the custom
+    /// intervals in the session. Intervals matching an existing entry's duration are kept once.
+    pub fn add_custom_interval(&mut self, text: &str) -> Result<Interval, IntervalParseError> {
+        let interval = text.trim().parse::<Interval>()?;
+        if self.insert_interval_option(interval) {
+            let custom: Vec<String> = self
+                .interval_options
+                .iter()
+                .filter(|(option, _)| !INTERVAL_OPTIONS.iter().any(|(d, _)| d == option))
+                .map(|(_, label)| label.to_string())
+                .collect();
+            let _ = self.persist_session("custom_intervals", &custom.join(","));
+        }
+        Ok(interval)
+    }
+
+    fn insert_interval_option(&mut self, interval: Interval) -> bool {
+        let duration = interval.as_duration();
+        if self
+            .interval_options
+            .iter()
+            .any(|(option, _)| option.is_some_and(|o| o.as_duration() == duration))
+        {
+            return false;
+        }
+        let pos = self
+            .interval_options
+            .iter()
+            .position(|(option, _)| option.is_some_and(|o| o.as_duration() > duration))
+            .unwrap_or(self.interval_options.len());
+        self.interval_options
+            .insert(pos, (Some(interval), Self::interval_label(Some(interval))));
+        true
+    }
+
+    /// Edits the interval menu's custom entry: typing appends, Enter adds and switches to the
+    /// parsed interval, leaving the entry open with an error if it doesn't parse.
+    pub(super) fn handle_custom_interval_key(&mut self, event: &KeyDownEvent) -> bool {
+        let Some(input) = self.custom_interval_input.as_mut() else {
+            return false;
         };
-        SharedString::from(label)
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "backspace" => {
+                input.pop();
+            }
+            "enter" => {
+                if event.is_held {
+                    return true;
+                }
+                let text = input.clone();
+                match self.add_custom_interval(&text) {
+                    Ok(interval) => {
+                        self.custom_interval_input = None;
+                        self.custom_interval_error = None;
+                        self.apply_interval(Some(interval), true);
+                    }
+                    Err(err) => self.custom_interval_error = Some(err.to_string()),
+                }
+                return true;
+            }
+            _ => {
+                let modified = keystroke.modifiers.control
+                    || keystroke.modifiers.platform
+                    || keystroke.modifiers.alt;
+                match keystroke.key_char.as_deref() {
+                    Some(text) if !modified && !text.chars().any(char::is_control) => {
+                        input.push_str(text);
+                    }
+                    _ => return false,
+                }
+            }
+        }
+        self.custom_interval_error = None;
+        true
     }
 
     pub fn current_interval(&self) -> Option<Interval> {
@@ -312,10 +729,32 @@ impl ChartView {
         self.active_range_index
     }
 
+    /// Quick ranges offered for the active interval.
+    pub fn quick_ranges(&self) -> &'static [(&'static str, Option<Duration>)] {
+        quick_ranges_for(self.interval)
+    }
+
     pub fn replay_enabled(&self) -> bool {
         self.replay_mode
     }
 
+    pub fn debug_overlay_enabled(&self) -> bool {
+        self.debug_overlay
+    }
+
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        self.debug_overlay = enabled;
+    }
+
+    /// Whether the panel listing recent [`crate::logging`] entries is shown.
+    pub fn log_panel_open(&self) -> bool {
+        self.log_panel_open
+    }
+
+    pub fn set_log_panel_open(&mut self, open: bool) {
+        self.log_panel_open = open;
+    }
+
     pub(super) fn is_perf_mode(&self) -> bool {
         self.perf_mode || self.source.starts_with("__PERF__")
     }
@@ -465,6 +904,11 @@ impl ChartView {
         cx: &mut Context<Self>,
     ) {
         self.set_replay_mode(false);
+        self.set_fast_wicks(true);
+        self.set_tooltip_placement(TooltipPlacement::default());
+        self.set_cache_candles(true);
+        self.set_forward_fill(false);
+        self.set_show_volume(true);
         self.set_perf_n(200_000);
         self.set_perf_step_secs(60);
         self.cleanup_legacy_perf_active_source();
@@ -499,6 +943,479 @@ impl ChartView {
         }
     }
 
+    /// Whether loaded files are written to the store; disabled means the cache is read-only.
+    pub fn cache_candles_enabled(&self) -> bool {
+        self.cache_candles
+    }
+
+    pub fn set_cache_candles(&mut self, enabled: bool) {
+        self.cache_candles = enabled;
+        let _ = self.persist_session("cache_candles", if enabled { "true" } else { "false" });
+    }
+
+    /// Whether loads repair null or non-finite prices from the previous row; off (the
+    /// default) fails the load so bad data is noticed.
+    pub fn forward_fill_enabled(&self) -> bool {
+        self.forward_fill
+    }
+
+    pub fn set_forward_fill(&mut self, enabled: bool) {
+        self.forward_fill = enabled;
+        let _ = self.persist_session("forward_fill", if enabled { "true" } else { "false" });
+    }
+
+    pub(crate) fn null_policy(&self) -> NullPolicy {
+        if self.forward_fill {
+            NullPolicy::ForwardFill
+        } else {
+            NullPolicy::Error
+        }
+    }
+
+    /// Whether the volume pane is drawn under the price pane.
+    pub fn show_volume_enabled(&self) -> bool {
+        self.show_volume
+    }
+
+    /// False when every loaded candle has zero volume (e.g. FX data without a volume
+    /// column); the volume pane is then hidden regardless of [`Self::show_volume_enabled`].
+    /// Scanned once per render revision.
+    pub fn has_volume(&mut self) -> bool {
+        if let Some((revision, has)) = self.has_volume_cache
+            && revision == self.render_cache_revision
+        {
+            return has;
+        }
+        let has = self.candles.iter().any(|c| c.volume != 0.0);
+        self.has_volume_cache = Some((self.render_cache_revision, has));
+        has
+    }
+
+    /// Hiding volume lets the price pane take the full chart height.
+    pub fn set_show_volume(&mut self, enabled: bool) {
+        self.show_volume = enabled;
+        if !enabled {
+            self.hover_in_volume = false;
+        }
+        let _ = self.persist_session("show_volume", if enabled { "true" } else { "false" });
+        self.persist_view_state();
+    }
+
+    /// Whether volume bars are scaled against the whole series instead of the visible slice.
+    pub fn global_volume_scale(&self) -> bool {
+        self.global_volume_scale
+    }
+
+    /// Global scaling keeps bar heights comparable while panning; price still fits the view.
+    pub fn set_global_volume_scale(&mut self, enabled: bool) {
+        self.global_volume_scale = enabled;
+        self.persist_view_state();
+    }
+
+    /// Largest single-candle volume of the displayed series.
+    pub(super) fn global_max_volume(&mut self) -> f64 {
+        if let Some((revision, max)) = self.global_volume_cache
+            && revision == self.render_cache_revision
+        {
+            return max;
+        }
+        let max = self
+            .candles
+            .iter()
+            .map(|c| c.volume)
+            .fold(0.0_f64, f64::max);
+        self.global_volume_cache = Some((self.render_cache_revision, max));
+        max
+    }
+
+    /// Offset used to format axis and tooltip timestamps; candles stay stored in UTC.
+    pub fn display_tz(&self) -> UtcOffset {
+        self.display_tz
+    }
+
+    pub fn set_display_tz(&mut self, offset: UtcOffset) {
+        self.display_tz = offset;
+        self.time_axis_cache = None;
+        self.persist_view_state();
+    }
+
+    /// Horizontal gridlines (and price labels) in the price pane; derived from its height
+    /// unless set explicitly.
+    pub fn grid_lines(&self) -> usize {
+        self.grid_lines.unwrap_or_else(|| {
+            default_grid_lines(self.chart_bounds.map_or(0.0, |b| f32::from(b.size.height)))
+        })
+    }
+
+    /// The explicit gridline count, or `None` when it follows the pane height.
+    pub fn grid_lines_override(&self) -> Option<usize> {
+        self.grid_lines
+    }
+
+    /// `None` restores the height-derived density; explicit counts are at least 2.
+    pub fn set_grid_lines(&mut self, lines: Option<usize>) {
+        self.grid_lines = lines.map(|n| n.max(2));
+        self.persist_view_state();
+    }
+
+    /// Fixed price spacing between gridlines; `None` spreads [`grid_lines`](Self::grid_lines)
+    /// evenly over the visible range.
+    pub fn price_tick_step(&self) -> Option<f64> {
+        self.price_tick_step
+    }
+
+    /// Steps that aren't positive and finite clear the override. Steps so fine the pane would
+    /// get crowded fall back to the automatic gridlines when drawn.
+    pub fn set_price_tick_step(&mut self, step: Option<f64>) {
+        self.price_tick_step = step.filter(|step| step.is_finite() && *step > 0.0);
+        self.persist_view_state();
+    }
+
+    /// Empty candle slots reserved right of the last candle so it isn't glued to the axis.
+    pub fn right_padding_candles(&self) -> usize {
+        self.right_padding_candles
+    }
+
+    pub fn set_right_padding_candles(&mut self, candles: usize) {
+        self.right_padding_candles = candles;
+        self.invalidate_render_cache();
+        self.persist_view_state();
+    }
+
+    pub fn x_axis_mode(&self) -> XAxisMode {
+        self.x_axis_mode
+    }
+
+    pub fn set_x_axis_mode(&mut self, mode: XAxisMode) {
+        self.x_axis_mode = mode;
+        self.persist_view_state();
+    }
+
+    /// Width of the price pane left for candles after the right padding.
+    pub(super) fn candle_area_width(&self, pane_width: f32, candle_count: usize) -> f32 {
+        plot_width(pane_width, candle_count, self.right_padding_candles)
+    }
+
+    /// Renders the visible candles, gridlines and theme background at `width`×`height` px
+    /// without a window, e.g. for exports larger than the screen. Uses the same candle layout
+    /// as the on-screen price pane; hover, overlays and axis labels are left out.
+    pub fn render_to_image(&mut self, width: u32, height: u32) -> RgbaImage {
+        let mut image = RgbaImage::from_pixel(width, height, pixel(self.theme.background));
+        let (start, end) = self.visible_range();
+        let end = end.min(self.candles.len());
+        let start = start.min(end);
+        if start >= end || width == 0 || height == 0 {
+            return image;
+        }
+
+        let plot = self.candle_area_width(width as f32, end - start);
+        let columns = plot.floor().max(1.0) as usize;
+        let cached = self
+            .render_cache(start, end, columns)
+            .map(|cache| (cache.aggregated.clone(), cache.padded_min, cache.padded_max));
+        let (aggregated, price_bounds) = match cached {
+            Some((aggregated, min, max)) => (Some(aggregated), (min, max)),
+            None => (None, self.visible_padded_bounds(start, end)),
+        };
+        let forming = self.in_progress().is_some() && end == self.candles.len();
+        let marks = candle_marks(
+            &self.candles[start..end],
+            aggregated.as_deref(),
+            (0.0, 0.0, plot, height as f32),
+            price_bounds,
+            forming,
+            self.x_axis_mode,
+            &self.theme,
+        );
+        let ticks = self
+            .price_tick_step
+            .and_then(|step| fixed_step_ticks(price_bounds.0, price_bounds.1, step));
+        let gridlines = match ticks {
+            Some(ticks) => ticks
+                .iter()
+                .map(|tick| price_to_y(*tick, price_bounds, 0.0, height as f32))
+                .collect(),
+            None => gridline_ys(0.0, height as f32, self.grid_lines()),
+        };
+        paint_price_pane(&mut image, &marks, &gridlines, &self.theme);
+        image
+    }
+
+    /// Overlays the closes of `candles` (another symbol) on the price pane.
+    pub fn set_compare(&mut self, source: String, candles: Vec<Candle>, axis_mode: AxisMode) {
+        self.compare = Some(CompareSeries {
+            source,
+            candles: Arc::from(candles),
+            axis_mode,
+        });
+    }
+
+    pub fn theme(&self) -> ChartTheme {
+        self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: ChartTheme) {
+        self.theme = theme;
+        if self.theme_preset.take().is_some() {
+            let _ = self.persist_session("theme_preset", "");
+        }
+        self.persist_view_state();
+    }
+
+    /// Sets [`ChartTheme::doji_threshold`] without forgetting the preset the theme came from.
+    pub fn set_doji_threshold(&mut self, threshold: f64) {
+        self.theme.doji_threshold = if threshold.is_finite() {
+            threshold.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.persist_view_state();
+    }
+
+    pub fn theme_preset(&self) -> Option<&str> {
+        self.theme_preset.as_deref()
+    }
+
+    /// Switches to [`ChartTheme::preset`] `name` and remembers the name in the session.
+    pub fn apply_theme_preset(&mut self, name: &str) {
+        self.theme = ChartTheme {
+            doji_threshold: self.theme.doji_threshold,
+            ..ChartTheme::preset(name)
+        };
+        self.theme_preset = Some(name.to_string());
+        let _ = self.persist_session("theme_preset", name);
+        self.persist_view_state();
+    }
+
+    /// Fast/slow SMA periods whose crossovers are marked on the price pane.
+    pub fn crossover_periods(&self) -> Option<(usize, usize)> {
+        self.crossover_periods
+    }
+
+    pub fn set_crossover_periods(&mut self, periods: Option<(usize, usize)>) {
+        self.crossover_periods = periods.filter(|&(fast, slow)| fast > 0 && slow > 0);
+        self.persist_view_state();
+    }
+
+    /// Crossovers within `start..end`, with indices relative to `start`.
+    pub(super) fn visible_crossovers(
+        &mut self,
+        start: usize,
+        end: usize,
+    ) -> Vec<(usize, CrossDir)> {
+        let Some(periods) = self.crossover_periods else {
+            return Vec::new();
+        };
+        let stale = self
+            .crossover_cache
+            .as_ref()
+            .is_none_or(|(revision, cached, _)| {
+                *revision != self.render_cache_revision || *cached != periods
+            });
+        if stale {
+            let closes: Vec<f64> = self.candles.iter().map(|c| c.close).collect();
+            let all = crossovers(&sma(&closes, periods.0), &sma(&closes, periods.1));
+            self.crossover_cache = Some((self.render_cache_revision, periods, all));
+        }
+        let Some((_, _, all)) = self.crossover_cache.as_ref() else {
+            return Vec::new();
+        };
+        let from = all.partition_point(|(idx, _)| *idx < start);
+        all[from..]
+            .iter()
+            .take_while(|(idx, _)| *idx < end)
+            .map(|&(idx, dir)| (idx - start, dir))
+            .collect()
+    }
+
+    pub fn show_pivots_enabled(&self) -> bool {
+        self.show_pivots
+    }
+
+    /// Draws pivot levels for the latest visible session; see [`core::pivot`].
+    pub fn set_show_pivots(&mut self, enabled: bool) {
+        self.show_pivots = enabled;
+        self.persist_view_state();
+    }
+
+    pub fn reset_view_on_interval(&self) -> bool {
+        self.reset_view_on_interval
+    }
+
+    /// On (default), switching interval frames the active quick range over the new data;
+    /// off keeps roughly the same time window in view.
+    pub fn set_reset_view_on_interval(&mut self, enabled: bool) {
+        self.reset_view_on_interval = enabled;
+        self.persist_view_state();
+    }
+
+    /// Pivot levels for the session of the last visible candle, from the previous session's
+    /// high/low/close, with the session's first visible index relative to `start`. Sessions
+    /// are calendar days in [`display_tz`](Self::display_tz).
+    pub(super) fn visible_pivots(&self, start: usize, end: usize) -> Option<(usize, PivotLevels)> {
+        let end = end.min(self.candles.len());
+        if !self.show_pivots || start >= end {
+            return None;
+        }
+        let day = |candle: &Candle| candle.timestamp.to_offset(self.display_tz).date();
+        let session_start = |end: usize| {
+            let current = day(&self.candles[end - 1]);
+            self.candles[..end]
+                .iter()
+                .rposition(|c| day(c) != current)
+                .map_or(0, |idx| idx + 1)
+        };
+        let current_start = session_start(end);
+        if current_start == 0 {
+            return None;
+        }
+        let prev = &self.candles[session_start(current_start)..current_start];
+        let first = prev.first()?;
+        let session = Candle::new(
+            first.timestamp,
+            first.open,
+            prev.iter()
+                .map(|c| c.high)
+                .fold(f64::NEG_INFINITY, f64::max),
+            prev.iter().map(|c| c.low).fold(f64::INFINITY, f64::min),
+            prev.last()?.close,
+            prev.iter().map(|c| c.volume).sum(),
+        );
+        Some((current_start.max(start) - start, pivot(&session)))
+    }
+
+    pub fn clear_compare(&mut self) {
+        self.compare = None;
+    }
+
+    /// Source of the compare overlay, if one is shown.
+    pub fn compare_source(&self) -> Option<&str> {
+        self.compare.as_ref().map(|c| c.source.as_str())
+    }
+
+    pub fn compare_axis_mode(&self) -> Option<AxisMode> {
+        self.compare.as_ref().map(|c| c.axis_mode)
+    }
+
+    pub fn set_compare_axis_mode(&mut self, axis_mode: AxisMode) {
+        if let Some(compare) = self.compare.as_mut() {
+            compare.axis_mode = axis_mode;
+        }
+    }
+
+    /// Loads `symbol` (cached candles first, then its file) in the background and shows it
+    /// as the compare overlay on an independent axis.
+    pub fn start_compare_load(
+        &mut self,
+        symbol: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let resolved = resolve_source_path(&self.resolve_symbol_source(&symbol));
+        let store = self.store.clone();
+        let entity = cx.entity();
+        window
+            .spawn(cx, async move |async_cx| {
+                let bg = async_cx.background_executor().clone();
+                let symbol_for_task = symbol.clone();
+                let task = bg.spawn(async move {
+                    let cached = store
+                        .as_ref()
+                        .and_then(|store| store.lock().ok())
+                        .and_then(|guard| guard.load_candles(&symbol_for_task, None, None).ok())
+                        .filter(|c| !c.is_empty());
+                    if let Some(cached) = cached {
+                        return Ok(cached);
+                    }
+                    let options = LoadOptions {
+                        volume_optional: true,
+                        ..Default::default()
+                    };
+                    load_csv(&resolved, options).map_err(|e| {
+                        format!(
+                            "failed to load {symbol_for_task} from {}: {e}",
+                            resolved.display()
+                        )
+                    })
+                });
+                let result = task.await;
+                async_cx
+                    .update(|window, app| {
+                        entity.update(app, |view, _| match result {
+                            Ok(candles) => {
+                                log_loading(format!(
+                                    "loaded {} compare candles for {symbol}",
+                                    candles.len()
+                                ));
+                                view.set_compare(symbol, candles, AxisMode::Independent);
+                            }
+                            Err(msg) => log_loading(format!("compare load failed: {msg}")),
+                        });
+                        window.refresh();
+                    })
+                    .ok();
+            })
+            .detach();
+    }
+
+    /// Drops cached candles for the active source so the next load reads the file again.
+    pub(crate) fn clear_cached_symbol(&mut self) {
+        if let Some(store) = self.store.as_ref().and_then(|store| store.lock().ok()) {
+            let _ = store.delete_symbol(&self.source);
+        }
+    }
+
+    /// Backends of the shared store (the target while a switch runs); `None` when DuckDB
+    /// couldn't be opened.
+    pub fn storage_mode(&self) -> Option<StorageMode> {
+        if let Some(pending) = self.storage_switch {
+            return Some(pending);
+        }
+        let store = self.store.as_ref()?.lock().ok()?;
+        Some(store.mode())
+    }
+
+    /// Switches the shared store's backends live in the background, e.g. to `Memory` to stop
+    /// persisting for this session. Cached candles, session state and the watchlist are
+    /// re-written into the new backends; a failed switch keeps the previous mode.
+    pub fn set_storage_mode(&mut self, mode: StorageMode, cx: &mut Context<Self>) {
+        let Some(store) = self.store.clone() else {
+            return;
+        };
+        if self.storage_switch.is_some() || self.storage_mode() == Some(mode) {
+            return;
+        }
+        self.storage_switch = Some(mode);
+        let task = cx.background_executor().spawn(async move {
+            let mut guard = store
+                .lock()
+                .map_err(|_| "store lock poisoned".to_string())?;
+            guard.set_mode(mode).map_err(|err| err.to_string())
+        });
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |view, cx| {
+                match result {
+                    Ok(()) => log_loading(format!("storage mode set to {mode:?}")),
+                    Err(err) => log_loading(format!(
+                        "storage mode switch failed, kept the previous mode: {err}"
+                    )),
+                }
+                view.storage_switch = None;
+                view.stored_coverage = None;
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    pub(crate) fn clear_candle_cache(&mut self) {
+        if let Some(store) = self.store.as_ref().and_then(|store| store.lock().ok()) {
+            let _ = store.clear_candles();
+        }
+    }
+
     pub(crate) fn set_perf_mode_flag_only(&mut self, enabled: bool) {
         self.perf_mode = enabled;
         let _ = self.persist_session("perf_mode", if enabled { "true" } else { "false" });
@@ -517,6 +1434,7 @@ impl ChartView {
 
         let was_live = self.live_mode;
         self.live_mode = enabled;
+        self.stored_coverage = None;
         let _ = self.persist_session("live_mode", if enabled { "true" } else { "false" });
 
         if enabled {
@@ -541,6 +1459,7 @@ impl ChartView {
 
     fn set_live_mode_flag_only(&mut self, enabled: bool) {
         self.live_mode = enabled;
+        self.stored_coverage = None;
         let _ = self.persist_session("live_mode", if enabled { "true" } else { "false" });
         if !enabled {
             self.stop_live_subscription();
@@ -550,6 +1469,7 @@ impl ChartView {
     fn stop_live_subscription(&mut self) {
         self.live_generation = self.live_generation.wrapping_add(1);
         self.live_last_sequence = 0;
+        self.in_progress = None;
         self.live_blink_on = false;
         self.live_last_event = None;
         self.live_status = LiveStatus::Disconnected;
@@ -557,6 +1477,12 @@ impl ChartView {
         if let Some(task) = self.live_task.take() {
             task.abort();
         }
+        self.live_debug = None;
+    }
+
+    /// Latest coordinator state of the running subscription, for debug tooling.
+    pub fn live_debug_snapshot(&self) -> Option<LiveDebugSnapshot> {
+        self.live_debug.as_ref().map(|rx| rx.borrow().clone())
     }
 
     pub(crate) fn begin_external_loading(&mut self, label: String) -> u64 {
@@ -567,14 +1493,23 @@ impl ChartView {
         load_id
     }
 
+    pub(crate) fn fail_external_loading(&mut self, load_id: u64, error: String) {
+        if self.active_load_seq != load_id {
+            return;
+        }
+        self.loading_symbol = None;
+        self.load_error = Some(error);
+    }
+
+    /// Returns `false` when a newer load has superseded `load_id`.
     pub(crate) fn apply_external_loaded(
         &mut self,
         load_id: u64,
         candles: Vec<Candle>,
         source: String,
-    ) {
+    ) -> bool {
         if self.active_load_seq != load_id {
-            return;
+            return false;
         }
         self.loading_symbol = None;
         self.load_error = None;
@@ -588,6 +1523,13 @@ impl ChartView {
             self.perf_mode = false;
             self.replace_data(candles, source, false, false);
         }
+        true
+    }
+
+    /// Keeps the bid/ask of `rows` (from an `_ext` loader) for the spread band. Call after
+    /// the candles are in place: replacing data clears the quotes.
+    pub(crate) fn set_spread_quotes(&mut self, rows: &[CandleExt]) {
+        self.spread_quotes = spread_quotes(rows).into();
     }
 
     pub fn watchlist_symbols(&self) -> Vec<String> {
@@ -617,6 +1559,104 @@ impl ChartView {
 
     pub fn set_symbol_search_filter(&mut self, filter: &str) {
         self.symbol_search_filter = filter.to_string();
+        self.symbol_search_selected = 0;
+    }
+
+    pub fn symbol_search_query(&self) -> &str {
+        &self.symbol_search_query
+    }
+
+    pub fn symbol_search_selected(&self) -> usize {
+        self.symbol_search_selected
+    }
+
+    pub fn push_symbol_search_text(&mut self, text: &str) {
+        self.symbol_search_query.push_str(text);
+        self.symbol_search_selected = 0;
+    }
+
+    pub fn pop_symbol_search_char(&mut self) {
+        self.symbol_search_query.pop();
+        self.symbol_search_selected = 0;
+    }
+
+    pub fn reset_symbol_search(&mut self) {
+        self.symbol_search_query.clear();
+        self.symbol_search_selected = 0;
+    }
+
+    /// Moves the highlighted search result by `delta`, clamped to the filtered results.
+    pub fn move_symbol_search_selection(&mut self, delta: isize) {
+        let len = self.filtered_symbol_universe().len();
+        if len == 0 {
+            self.symbol_search_selected = 0;
+            return;
+        }
+        let next = self.symbol_search_selected as isize + delta;
+        self.symbol_search_selected = next.clamp(0, len as isize - 1) as usize;
+    }
+
+    /// Universe entries matching the active filter chip and the typed query.
+    pub fn filtered_symbol_universe(&mut self) -> Vec<SymbolSearchEntry> {
+        self.ensure_symbol_universe();
+        let filter = self.symbol_search_filter.as_str();
+        let query = self.symbol_search_query.trim().to_ascii_lowercase();
+        self.universe
+            .iter()
+            .filter(|entry| {
+                filter == "All" || entry.filters.iter().any(|f| f.eq_ignore_ascii_case(filter))
+            })
+            .filter(|entry| {
+                query.is_empty()
+                    || entry.symbol.to_ascii_lowercase().contains(&query)
+                    || entry.name.to_ascii_lowercase().contains(&query)
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn selected_search_symbol(&mut self) -> Option<String> {
+        let selected = self.symbol_search_selected;
+        self.filtered_symbol_universe()
+            .get(selected)
+            .map(|entry| entry.symbol.clone())
+    }
+
+    /// Applies a key press to the open symbol search: typing edits the query, arrows move
+    /// the highlight and Enter loads the highlighted symbol. Returns whether it was handled.
+    pub(super) fn handle_symbol_search_key(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "up" => self.move_symbol_search_selection(-1),
+            "down" => self.move_symbol_search_selection(1),
+            "backspace" => self.pop_symbol_search_char(),
+            "enter" => {
+                if event.is_held {
+                    return true;
+                }
+                if let Some(symbol) = self.selected_search_symbol() {
+                    let add_to_watchlist = self.symbol_search_add_to_watchlist;
+                    self.start_symbol_load(symbol, add_to_watchlist, window, cx);
+                }
+            }
+            _ => {
+                let modified = keystroke.modifiers.control
+                    || keystroke.modifiers.platform
+                    || keystroke.modifiers.alt;
+                match keystroke.key_char.as_deref() {
+                    Some(text) if !modified && !text.chars().any(char::is_control) => {
+                        self.push_symbol_search_text(text);
+                    }
+                    _ => return false,
+                }
+            }
+        }
+        true
     }
 
     pub fn symbol_universe(&mut self) -> &[SymbolSearchEntry] {
@@ -624,6 +1664,11 @@ impl ChartView {
         &self.universe
     }
 
+    pub fn set_symbol_universe(&mut self, entries: Vec<SymbolSearchEntry>) {
+        self.universe = entries;
+        self.symbol_search_selected = 0;
+    }
+
     pub fn start_symbol_load(
         &mut self,
         symbol: String,
@@ -648,6 +1693,17 @@ impl ChartView {
         });
     }
 
+    /// Re-runs the load that set `load_error`, even when that symbol is still the active
+    /// source (which a plain row click would treat as already loaded).
+    pub fn retry_failed_load(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(symbol) = self.failed_symbol.take() else {
+            return;
+        };
+        self.load_error = None;
+        self.force_symbol_reload = true;
+        self.start_symbol_load(symbol, false, window, cx);
+    }
+
     fn ensure_load_subscription(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.load_events.is_some() {
             return;
@@ -689,6 +1745,7 @@ impl ChartView {
                     return;
                 }
 
+                log_loading(format!("loading {symbol}"));
                 if self.live_mode {
                     self.stop_live_subscription();
 
@@ -720,7 +1777,11 @@ impl ChartView {
                                     return (Vec::new(), None);
                                 };
                                 let cached = guard
-                                    .load_candles(&symbol_for_cache, None)
+                                    .load_candles(
+                                        &symbol_for_cache,
+                                        Some(&cfg_for_cache.interval),
+                                        None,
+                                    )
                                     .ok()
                                     .unwrap_or_default();
                                 let cursor_key = cursor_key_for(&cfg_for_cache, &symbol_for_cache);
@@ -740,41 +1801,13 @@ impl ChartView {
                                 let cursor = get_cursor(&cfg_for_backfill, &symbol_for_backfill)
                                     .await
                                     .ok();
-                                let end_ts_ms = cursor
-                                    .map(|c| {
-                                        if c.latest_ts_ms == 0 {
-                                            None
-                                        } else {
-                                            Some(c.latest_ts_ms)
-                                        }
-                                    })
-                                    .flatten();
-
-                                let mut from_sequence = last_sequence;
-                                let mut start_sequence =
-                                    from_sequence.unwrap_or(0).saturating_add(1);
-                                let mut candles = Vec::new();
-                                loop {
-                                    let chunk = backfill_candles(
-                                        &cfg_for_backfill,
-                                        &symbol_for_backfill,
-                                        from_sequence,
-                                        DEFAULT_BACKFILL_LIMIT,
-                                        end_ts_ms,
-                                    )
-                                    .await?;
-                                    if candles.is_empty() {
-                                        start_sequence = chunk.start_sequence;
-                                    }
-                                    let len = chunk.candles.len();
-                                    candles.extend(chunk.candles);
-                                    if !chunk.has_more || chunk.next_sequence.is_none() || len == 0
-                                    {
-                                        break;
-                                    }
-                                    from_sequence = chunk.next_sequence;
-                                }
-                                Ok((start_sequence, candles))
+                                backfill_history(
+                                    &cfg_for_backfill,
+                                    &symbol_for_backfill,
+                                    last_sequence,
+                                    cursor,
+                                )
+                                .await
                             });
 
                             let result = match handle.await {
@@ -803,6 +1836,7 @@ impl ChartView {
                                             if let Ok(guard) = store.lock() {
                                                 let _ = guard.write_candles(
                                                     &symbol_for_persist,
+                                                    Some(&cfg_for_persist.interval),
                                                     base_for_persist.as_ref(),
                                                 );
                                                 if let Some(cursor_value) = cursor_value {
@@ -823,6 +1857,7 @@ impl ChartView {
                                         resamples,
                                         needs_persist: false,
                                         live_last_sequence,
+                                        spread: Arc::from([]),
                                     })
                                 }
                                 Ok(Err(err)) => Err(err),
@@ -859,6 +1894,8 @@ impl ChartView {
 
                 let entity = cx.entity();
                 let store = self.store.clone();
+                let cache_candles = self.cache_candles;
+                let null_policy = self.null_policy();
                 let resample_intervals = resample_intervals.clone();
                 let symbol_for_task = symbol.clone();
                 let resolved_path = resolved.clone();
@@ -874,7 +1911,7 @@ impl ChartView {
                                     .lock()
                                     .ok()
                                     .and_then(|guard| {
-                                        guard.load_candles(&symbol_for_task, None).ok()
+                                        guard.load_candles(&symbol_for_task, None, None).ok()
                                     })
                                     .filter(|c| !c.is_empty());
 
@@ -891,29 +1928,42 @@ impl ChartView {
                                         resamples,
                                         needs_persist: false,
                                         live_last_sequence: None,
+                                        spread: Arc::from([]),
                                     });
                                 }
                             }
 
-                            let candles = load_csv(&resolved_path, LoadOptions::default())
-                                .map_err(|e| {
-                                    format!(
-                                        "failed to load {symbol_for_task} from {}: {e}",
-                                        resolved_path.display()
-                                    )
-                                })?;
-
-                            if candles.is_empty() {
+                            let options = LoadOptions {
+                                volume_optional: true,
+                                null_policy,
+                                ..Default::default()
+                            };
+                            let rows = match detect_format(&resolved_path.to_string_lossy()) {
+                                SourceFormat::Parquet => load_parquet_ext(&resolved_path, options),
+                                SourceFormat::Csv | SourceFormat::Url => {
+                                    load_csv_ext(&resolved_path, options)
+                                }
+                            }
+                            .map_err(|e| {
+                                format!(
+                                    "failed to load {symbol_for_task} from {}: {e}",
+                                    resolved_path.display()
+                                )
+                            })?;
+
+                            if rows.is_empty() {
                                 Err(format!("no candles loaded for {symbol_for_task}"))
                             } else {
-                                let base_arc: Arc<[Candle]> = Arc::from(candles);
+                                let base_arc: Arc<[Candle]> =
+                                    rows.iter().map(|row| row.candle.clone()).collect();
                                 let resamples = build_resamples(&base_arc, &resample_intervals);
                                 Ok(LoadResult {
                                     symbol: symbol_for_task.clone(),
                                     base: base_arc,
                                     resamples,
-                                    needs_persist: store_for_task.is_some(),
+                                    needs_persist: cache_candles && store_for_task.is_some(),
                                     live_last_sequence: None,
+                                    spread: spread_quotes(&rows).into(),
                                 })
                             }
                         });
@@ -933,6 +1983,7 @@ impl ChartView {
                                         if let Ok(guard) = store_arc.lock() {
                                             let _ = guard.write_candles(
                                                 &symbol_for_persist,
+                                                None,
                                                 base_for_persist.as_ref(),
                                             );
                                             let _ = guard.set_session_value(
@@ -993,8 +2044,10 @@ impl ChartView {
                         base,
                         resamples,
                         live_last_sequence,
+                        spread,
                         ..
                     }) => {
+                        log_loading(format!("loaded {} candles for {symbol}", base.len()));
                         self.load_error = None;
                         self.replace_data_from_load(
                             base,
@@ -1003,6 +2056,7 @@ impl ChartView {
                             false,
                             *add_to_watchlist,
                         );
+                        self.spread_quotes = spread;
                         if self.live_mode {
                             if let Some(last) = live_last_sequence {
                                 self.live_last_sequence = last;
@@ -1020,7 +2074,7 @@ impl ChartView {
                         persist_snapshot = Some(PersistSnapshot {
                             store: self.store.clone(),
                             source: self.source.clone(),
-                            interval_label: ChartView::interval_label(self.interval).to_string(),
+                            interval_key: ChartView::interval_key(self.interval),
                             range_index: self.active_range_index,
                             view_offset: self.view_offset,
                             zoom: self.zoom,
@@ -1029,7 +2083,9 @@ impl ChartView {
                         cx.notify();
                     }
                     Err(msg) => {
+                        log_loading(format!("load failed: {msg}"));
                         self.load_error = Some(msg);
+                        self.failed_symbol = self.loading_symbol.clone();
                     }
                 }
                 self.loading_symbol = None;
@@ -1040,8 +2096,7 @@ impl ChartView {
                         if let Some(store) = snapshot.store {
                             if let Ok(guard) = store.lock() {
                                 let _ = guard.set_session_value("active_source", &snapshot.source);
-                                let _ =
-                                    guard.set_session_value("interval", &snapshot.interval_label);
+                                let _ = guard.set_session_value("interval", &snapshot.interval_key);
                                 let _ = guard.set_session_value(
                                     "range_index",
                                     &snapshot.range_index.to_string(),
@@ -1077,12 +2132,20 @@ impl ChartView {
         self.live_last_error = None;
 
         let (tx, mut rx) = mpsc::unbounded_channel::<LiveEvent>();
+        let (debug_tx, debug_rx) = tokio::sync::watch::channel(LiveDebugSnapshot::default());
+        self.live_debug = Some(debug_rx);
         let cfg = self.live_config.clone();
         let entity = cx.entity();
 
         self.live_task = Some(tokio_runtime().spawn(async move {
-            if let Err(err) =
-                run_live_coordinator(cfg, symbol, last_applied_sequence, tx.clone()).await
+            if let Err(err) = run_live_coordinator_with_debug(
+                cfg,
+                symbol,
+                last_applied_sequence,
+                tx.clone(),
+                debug_tx,
+            )
+            .await
             {
                 let _ = tx.send(LiveEvent::Error(err));
             }
@@ -1093,24 +2156,99 @@ impl ChartView {
                 while let Some(event) = rx.recv().await {
                     async_cx
                         .update(|window, app| {
-                            entity.update(app, |this, cx| {
+                            let repaint = entity.update(app, |this, cx| {
                                 if !this.live_mode || this.live_generation != load_id {
-                                    return;
+                                    return false;
                                 }
                                 this.apply_live_event(event, cx);
+                                if this.live_refresh_interval.is_some() {
+                                    // The refresh pump repaints on its next tick.
+                                    this.live_repaint_pending = true;
+                                    return false;
+                                }
                                 cx.notify();
+                                true
                             });
-                            window.refresh();
+                            if repaint {
+                                window.refresh();
+                            }
                         })
                         .ok();
                 }
             })
             .detach();
+
+        if let Some(cadence) = self.live_refresh_interval {
+            self.start_live_refresh_pump(cadence, window, cx);
+        }
+    }
+
+    pub fn live_refresh_interval(&self) -> Option<std::time::Duration> {
+        self.live_refresh_interval
+    }
+
+    /// Throttles live repaints to one per `interval` (e.g. 100ms for 10fps on low-power
+    /// machines). Takes effect on the next live subscription.
+    pub fn set_live_refresh_interval(&mut self, interval: Option<std::time::Duration>) {
+        self.live_refresh_interval = interval.filter(|interval| !interval.is_zero());
+    }
+
+    /// Timer ticks run by the refresh pump so far.
+    #[cfg(test)]
+    pub(crate) fn live_refresh_ticks(&self) -> u64 {
+        self.live_refresh_ticks
+    }
+
+    /// Repaints every `cadence` if a live batch arrived since the last tick. Stops once the
+    /// current live subscription is replaced or stopped.
+    pub(crate) fn start_live_refresh_pump(
+        &mut self,
+        cadence: std::time::Duration,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let generation = self.live_generation;
+        let entity = cx.entity();
+        window
+            .spawn(cx, async move |async_cx| {
+                loop {
+                    async_cx.background_executor().timer(cadence).await;
+                    let running = async_cx
+                        .update(|window, app| {
+                            let (running, repaint) = entity.update(app, |this, cx| {
+                                if this.live_generation != generation {
+                                    return (false, false);
+                                }
+                                #[cfg(test)]
+                                {
+                                    this.live_refresh_ticks += 1;
+                                }
+                                let repaint = std::mem::take(&mut this.live_repaint_pending);
+                                if repaint {
+                                    cx.notify();
+                                }
+                                (true, repaint)
+                            });
+                            if repaint {
+                                window.refresh();
+                            }
+                            running
+                        })
+                        .unwrap_or(false);
+                    if !running {
+                        break;
+                    }
+                }
+            })
+            .detach();
     }
 
     fn apply_live_event(&mut self, event: LiveEvent, cx: &mut Context<Self>) {
         match event {
             LiveEvent::Status(status) => {
+                if status != self.live_status {
+                    log_live(format!("{status:?}"));
+                }
                 self.live_status = status;
             }
             LiveEvent::CandleBatch {
@@ -1124,6 +2262,7 @@ impl ChartView {
                 self.append_live_batch(start_sequence, candles, cx)
             }
             LiveEvent::Error(err) => {
+                log_live(format!("error: {err}"));
                 self.live_last_error = Some(err);
             }
         }
@@ -1177,16 +2316,42 @@ impl ChartView {
                     return ("Subscribed".to_string(), Some("Idle".to_string()));
                 };
                 let age = t.elapsed();
+                let mut detail = format_age(age);
+                if let Some(left) = self.live_time_to_close(OffsetDateTime::now_utc()) {
+                    detail.push_str(&format!(" • closes in {}", format_span(left)));
+                }
                 if age > std::time::Duration::from_secs(2) {
-                    ("Stalled".to_string(), Some(format_age(age)))
+                    ("Stalled".to_string(), Some(detail))
                 } else {
-                    ("Live".to_string(), Some(format_age(age)))
+                    ("Live".to_string(), Some(detail))
                 }
             }
         }
     }
 
-    fn append_live_batch(
+    /// Countdown until the forming candle closes, at the view interval (the live feed's
+    /// interval for raw data). `None` outside live mode, in replay, or with no candles.
+    pub fn live_time_to_close(&self, now: OffsetDateTime) -> Option<Duration> {
+        if !self.live_mode || self.replay_enabled() {
+            return None;
+        }
+        let interval = self
+            .interval
+            .or_else(|| self.live_config.interval.parse().ok())?;
+        let last = self.candles.last()?;
+        Some(time_to_close(interval, last.timestamp, now))
+    }
+
+    pub fn max_candles(&self) -> Option<usize> {
+        self.max_candles
+    }
+
+    /// Caps base candles kept in memory while live; `None` keeps everything.
+    pub fn set_max_candles(&mut self, max: Option<usize>) {
+        self.max_candles = max.map(|n| n.max(1));
+    }
+
+    pub(super) fn append_live_batch(
         &mut self,
         start_sequence: u64,
         mut candles: Vec<Candle>,
@@ -1209,31 +2374,175 @@ impl ChartView {
             candles.drain(0..skip);
         }
 
-        if let Some(store) = self.store.clone() {
-            let bg = cx.background_executor().clone();
-            let symbol_for_store = self.source.clone();
-            let cfg_for_store = self.live_config.clone();
-            let cursor_key = cursor_key_for(&cfg_for_store, &symbol_for_store);
-            let cursor_value = last_seq.to_string();
-            let candles_for_store = candles.clone();
-            bg.spawn(async move {
+        self.persist_live_candles(candles.clone(), Some(last_seq), cx);
+
+        let appends_only = self
+            .base_candles
+            .last()
+            .is_none_or(|last| candles[0].timestamp > last.timestamp);
+        if appends_only {
+            self.live_last_sequence = last_seq;
+            // Anything newer than the last candle closes the forming one; the newest
+            // candle of the batch keeps forming until a later one arrives.
+            self.in_progress = None;
+            let forming = candles.pop().expect("batch is non-empty");
+            self.append_candles(candles);
+            self.update_in_progress(forming, cx);
+            return;
+        }
+
+        let mut base: Vec<Candle> = self.base_candles.iter().cloned().collect();
+        let changed = merge_live_tail(&mut base, candles);
+        self.live_last_sequence = last_seq;
+        if !changed {
+            return;
+        }
+        // A snapshot for a later interval closes the forming candle.
+        if let (Some(ts), Some(last)) = (self.in_progress, base.last())
+            && last.timestamp > ts
+        {
+            self.in_progress = None;
+        }
+        self.set_live_base(base);
+    }
+
+    /// The forming candle for the latest interval, if live updates are still merging into it.
+    pub fn in_progress(&self) -> Option<&Candle> {
+        let ts = self.in_progress?;
+        self.base_candles.last().filter(|c| c.timestamp == ts)
+    }
+
+    /// Merges a same-interval update into the forming candle (extends high/low, takes the
+    /// close, adds volume). An update for a later interval finalizes the current candle and
+    /// starts a new one; stale updates are ignored.
+    pub fn update_in_progress(&mut self, update: Candle, cx: &mut Context<Self>) {
+        let ts = update.timestamp;
+        match self.base_candles.last() {
+            Some(last) if ts < last.timestamp => {}
+            Some(last) if ts == last.timestamp => {
+                let mut base: Vec<Candle> = self.base_candles.iter().cloned().collect();
+                let last = base.last_mut().expect("checked above");
+                if self.in_progress == Some(ts) {
+                    last.high = last.high.max(update.high);
+                    last.low = last.low.min(update.low);
+                    last.close = update.close;
+                    last.volume += update.volume;
+                } else {
+                    *last = update;
+                }
+                self.in_progress = Some(ts);
+                self.set_live_base(base);
+            }
+            _ => {
+                if let Some(finished) = self.in_progress().cloned() {
+                    self.persist_live_candles(vec![finished], None, cx);
+                }
+                self.append_candles(vec![update]);
+                self.in_progress = Some(ts);
+            }
+        }
+    }
+
+    fn persist_live_candles(
+        &self,
+        candles: Vec<Candle>,
+        last_seq: Option<u64>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(store) = self.store.clone() else {
+            return;
+        };
+        let symbol = self.source.clone();
+        let cfg = self.live_config.clone();
+        let cursor = last_seq.map(|seq| (cursor_key_for(&cfg, &symbol), seq.to_string()));
+        cx.background_executor()
+            .spawn(async move {
                 if let Ok(guard) = store.lock() {
-                    let _ = guard.append_candles(&symbol_for_store, &candles_for_store);
-                    let _ = guard.set_session_value(&cursor_key, &cursor_value);
+                    let _ = guard.append_candles(&symbol, Some(&cfg.interval), &candles);
+                    if let Some((key, value)) = cursor {
+                        let _ = guard.set_session_value(&key, &value);
+                    }
                 }
             })
             .detach();
+    }
+
+    /// Extends the base series with `new`, re-resampling only the last bucket of each cached
+    /// interval. Falls back to a full rebuild when `new` overlaps the existing tail, is out of
+    /// order, or pushes the series past `max_candles`.
+    pub fn append_candles(&mut self, new: Vec<Candle>) {
+        let Some(first) = new.first() else {
+            return;
+        };
+        let in_order = new.windows(2).all(|w| w[0].timestamp < w[1].timestamp)
+            && self
+                .base_candles
+                .last()
+                .is_none_or(|last| last.timestamp < first.timestamp);
+        let overflows = self
+            .max_candles
+            .is_some_and(|max| self.base_candles.len() + new.len() > max);
+        if !in_order || overflows {
+            let mut base: Vec<Candle> = self.base_candles.iter().cloned().collect();
+            merge_live_tail(&mut base, new);
+            self.set_live_base(base);
+            return;
         }
 
-        let mut base: Vec<Candle> = self.base_candles.iter().cloned().collect();
-        base.extend(candles);
-        self.live_last_sequence = last_seq;
+        let mut base: Vec<Candle> = Vec::with_capacity(self.base_candles.len() + new.len());
+        base.extend_from_slice(&self.base_candles);
+        base.extend(new);
+        let base: Arc<[Candle]> = Arc::from(base);
+        self.resample_cache = self
+            .resample_cache
+            .iter()
+            .map(|(interval, cached)| match interval {
+                Some(i) => (*interval, Arc::from(extend_resampled(cached, &base, *i))),
+                None => (None, base.clone()),
+            })
+            .collect();
+        self.base_candles = base;
+        self.candles = self.resampled_for(self.interval);
+        self.invalidate_render_cache();
+
+        let visible_count = self.visible_len().round().max(1.0) as usize;
+        self.view_offset = self.clamp_offset(self.view_offset, visible_count);
+    }
+
+    fn set_live_base(&mut self, mut base: Vec<Candle>) {
+        let overflow = self
+            .max_candles
+            .map_or(0, |max| base.len().saturating_sub(max));
+        // Remember what is on screen so trimming the head doesn't shift the viewport.
+        let anchor = (overflow > 0 && !self.candles.is_empty()).then(|| {
+            let (start, end) = self.visible_range();
+            (self.candles[start].timestamp, end - start)
+        });
+        if overflow > 0 {
+            base.drain(..overflow);
+        }
+        // Follow the pinned candle by timestamp; trimming shifts every index.
+        let pinned_ts = self
+            .pinned_index
+            .and_then(|idx| self.candles.get(idx))
+            .map(|c| c.timestamp);
 
         let base_arc: Arc<[Candle]> = Arc::from(base);
         self.base_candles = base_arc.clone();
         self.resample_cache = vec![(None, base_arc.clone())];
         self.candles = self.resampled_for(self.interval);
         self.invalidate_render_cache();
+        self.pinned_index =
+            pinned_ts.and_then(|ts| self.candles.binary_search_by(|c| c.timestamp.cmp(&ts)).ok());
+
+        if let Some((first_visible, visible_count)) = anchor {
+            let len = self.candles.len();
+            self.zoom = (len as f32 / visible_count.max(1) as f32).max(1.0);
+            let start = self
+                .candles
+                .partition_point(|c| c.timestamp < first_visible);
+            self.view_offset = start as f32;
+        }
 
         let visible_count = self.visible_len().round().max(1.0) as usize;
         self.view_offset = self.clamp_offset(self.view_offset, visible_count);
@@ -1264,6 +2573,34 @@ impl ChartView {
             if let Some(live_mode) = session.live_mode {
                 self.live_mode = live_mode;
             }
+            if let Some(fast_wicks) = session.fast_wicks {
+                self.fast_wicks = fast_wicks;
+            }
+            if let Some(placement) = session
+                .tooltip_placement
+                .as_deref()
+                .and_then(TooltipPlacement::parse)
+            {
+                self.tooltip_placement = placement;
+            }
+            if let Some(cache_candles) = session.cache_candles {
+                self.cache_candles = cache_candles;
+            }
+            if let Some(forward_fill) = session.forward_fill {
+                self.forward_fill = forward_fill;
+            }
+            if let Some(show_volume) = session.show_volume {
+                self.show_volume = show_volume;
+            }
+            for label in &session.custom_intervals {
+                if let Ok(interval) = label.parse::<Interval>() {
+                    self.insert_interval_option(interval);
+                }
+            }
+            if let Some(name) = session.theme_preset {
+                self.theme = ChartTheme::preset(&name);
+                self.theme_preset = Some(name);
+            }
             if let Some(live_pub) = session.live_pub {
                 self.live_config.live_pub = live_pub;
             }
@@ -1286,34 +2623,7 @@ impl ChartView {
 
             if let Some(interval) = session
                 .interval
-                .and_then(|interval| match interval.as_str() {
-                    "raw" => Some(None),
-                    s if s.ends_with('s') => s
-                        .trim_end_matches('s')
-                        .parse()
-                        .ok()
-                        .map(Interval::Second)
-                        .map(Some),
-                    s if s.ends_with('m') => s
-                        .trim_end_matches('m')
-                        .parse()
-                        .ok()
-                        .map(Interval::Minute)
-                        .map(Some),
-                    s if s.ends_with('h') => s
-                        .trim_end_matches('h')
-                        .parse()
-                        .ok()
-                        .map(Interval::Hour)
-                        .map(Some),
-                    s if s.ends_with('d') => s
-                        .trim_end_matches('d')
-                        .parse()
-                        .ok()
-                        .map(Interval::Day)
-                        .map(Some),
-                    _ => None,
-                })
+                .and_then(|interval| Self::parse_interval_key(&interval))
             {
                 self.apply_interval(interval, false);
             }
@@ -1335,6 +2645,14 @@ impl ChartView {
                 let visible_count = self.visible_len().round().max(1.0) as usize;
                 self.view_offset = self.clamp_offset(offset, visible_count);
             }
+
+            if let Some(state) = store_arc
+                .lock()
+                .ok()
+                .and_then(|s| s.get_session_value(VIEW_STATE_KEY).ok().flatten())
+            {
+                let _ = self.restore_state(&state);
+            }
             self.hydrated = true;
         }
     }
@@ -1361,10 +2679,49 @@ impl ChartView {
         if self.candles.is_empty() {
             return 0.0;
         }
-        let zoom = self.zoom.max(1.0).min(self.candles.len() as f32);
+        let zoom = self
+            .zoom
+            .max(self.min_zoom())
+            .min(self.candles.len() as f32);
         (self.candles.len() as f32 / zoom).max(1.0)
     }
 
+    pub fn min_candle_px(&self) -> Option<f32> {
+        self.min_candle_px
+    }
+
+    /// Narrowest a candle may get; zooming out further scrolls instead of shrinking.
+    /// `None` lets candles shrink until the whole series fits.
+    pub fn set_min_candle_px(&mut self, px: Option<f32>) {
+        self.min_candle_px = px.filter(|px| *px > 0.0);
+        let visible_count = self.visible_len().round().max(1.0) as usize;
+        self.view_offset = self.clamp_offset(self.view_offset, visible_count);
+        self.invalidate_render_cache();
+    }
+
+    /// Most candles that fit the chart width at `min_candle_px`.
+    fn max_visible_candles(&self) -> Option<usize> {
+        let min_px = self.min_candle_px?;
+        let width = f32::from(self.chart_bounds?.size.width);
+        Some(((width / min_px).floor() as usize).max(1))
+    }
+
+    /// Smallest zoom (most zoomed out) allowed by `min_candle_px`.
+    pub(super) fn min_zoom(&self) -> f32 {
+        let len = self.candles.len();
+        match self.max_visible_candles() {
+            Some(max) if len > max => len as f32 / max as f32,
+            _ => 1.0,
+        }
+    }
+
+    /// Width of the full series at the current zoom; wider than the chart when scrolling.
+    pub(super) fn content_width(&self) -> Option<f32> {
+        let width = f32::from(self.chart_bounds?.size.width);
+        let visible = self.visible_len().round().max(1.0);
+        Some(width * self.candles.len() as f32 / visible)
+    }
+
     pub(super) fn clamp_offset(&self, offset: f32, visible_count: usize) -> f32 {
         if self.candles.is_empty() {
             return 0.0;
@@ -1378,15 +2735,130 @@ impl ChartView {
             return (0, 0);
         }
         let visible = self.visible_len().round().max(1.0) as usize;
+        if self.replay_mode {
+            // Replay keeps the newest revealed candle at the right edge.
+            let end = self.replay_revealed();
+            return (end.saturating_sub(visible), end);
+        }
         let start = self.clamp_offset(self.view_offset, visible).round() as usize;
         let end = (start + visible).min(self.candles.len());
         (start, end)
     }
 
+    /// The visible candles merged into one, as a period summary: first open, high, low, last
+    /// close and total volume. `None` when nothing is loaded.
+    pub fn visible_ohlc(&self) -> Option<Candle> {
+        let (start, end) = self.visible_range();
+        aggregate(&self.candles[start..end])
+    }
+
+    pub fn events(&self) -> &[(OffsetDateTime, String)] {
+        &self.events
+    }
+
+    /// Replaces the event/news annotations drawn under the price pane.
+    pub fn set_events(&mut self, events: Vec<(OffsetDateTime, String)>) {
+        self.events = events;
+    }
+
+    /// Index of the candle at `ts`, or the nearest one (earlier wins ties) when `ts` falls
+    /// between candles; `None` outside the loaded range.
+    pub fn index_of_time(&self, ts: OffsetDateTime) -> Option<usize> {
+        let first = self.candles.first()?.timestamp;
+        let last = self.candles.last()?.timestamp;
+        if ts < first || ts > last {
+            return None;
+        }
+        let idx = self.candles.partition_point(|c| c.timestamp < ts);
+        if self.candles[idx].timestamp == ts || idx == 0 {
+            return Some(idx);
+        }
+        let before = ts - self.candles[idx - 1].timestamp;
+        let after = self.candles[idx].timestamp - ts;
+        Some(if before <= after { idx - 1 } else { idx })
+    }
+
+    /// Candles currently on screen.
+    pub fn visible_candles(&self) -> &[Candle] {
+        let (start, end) = self.visible_range();
+        &self.candles[start..end]
+    }
+
+    /// The visible candles as CSV text (header included), as copied to the clipboard.
+    pub fn visible_csv(&self) -> String {
+        let mut out = String::new();
+        // Writing into a String cannot fail.
+        let _ = write_csv(&mut out, self.visible_candles());
+        out
+    }
+
+    /// Zooms so only the last `n` candles are visible.
+    pub(super) fn show_last_candles(&mut self, n: usize) {
+        let len = self.candles.len();
+        if len == 0 {
+            return;
+        }
+        let n = n.clamp(1, len);
+        self.zoom = len as f32 / n as f32;
+        self.view_offset = self.clamp_offset((len - n) as f32, n);
+    }
+
+    fn apply_pending_initial_visible(&mut self) {
+        if self.candles.is_empty() {
+            return;
+        }
+        if let Some(n) = self.pending_initial_visible.take() {
+            self.show_last_candles(n);
+        }
+    }
+
     fn invalidate_render_cache(&mut self) {
         self.render_cache_revision = self.render_cache_revision.wrapping_add(1);
         self.render_cache = None;
         self.time_axis_cache = None;
+        self.bounds_index = BoundsIndex::new(&self.candles);
+    }
+
+    /// Coverage summary of the displayed series, recomputed only when the candles change.
+    pub(super) fn data_health(&mut self) -> DataHealth {
+        if let Some((revision, health)) = &self.data_health_cache
+            && *revision == self.render_cache_revision
+        {
+            return health.clone();
+        }
+        let stored = match self.stored_coverage {
+            Some(stored) => stored,
+            None => {
+                let stored = self.query_stored_coverage();
+                self.stored_coverage = Some(stored);
+                stored
+            }
+        };
+        let health = compute_data_health(&self.candles, self.interval, stored);
+        self.data_health_cache = Some((self.render_cache_revision, health.clone()));
+        health
+    }
+
+    /// `COUNT(DISTINCT ...)` over the store; too slow to run on every live revision.
+    fn query_stored_coverage(&self) -> Option<CandleCoverage> {
+        let stored_interval = self.live_mode.then(|| self.live_config.interval.clone());
+        self.store
+            .as_ref()
+            .and_then(|store| store.lock().ok())
+            .and_then(|store| {
+                store
+                    .candle_coverage(&self.source, stored_interval.as_deref())
+                    .ok()
+                    .flatten()
+            })
+    }
+
+    /// Padded price bounds of `candles[start..end]` in O(log n) via the bounds index.
+    pub(super) fn visible_padded_bounds(&self, start: usize, end: usize) -> (f64, f64) {
+        match self.bounds_index.bounds(start, end) {
+            Some((min, max)) => padded_bounds_from_min_max(min, max),
+            None => padded_bounds(&[]),
+        }
     }
 
     pub(super) fn time_axis_labels(
@@ -1418,30 +2890,17 @@ impl ChartView {
             };
 
             let time_fmt = format_description!("[year]-[month]-[day] [hour]:[minute]");
-            let start_label = visible
-                .first()
-                .map(|c| {
-                    c.timestamp
-                        .format(&time_fmt)
-                        .unwrap_or_else(|_| c.timestamp.to_string())
-                })
-                .unwrap_or_else(|| "---".into());
+            let display_tz = self.display_tz;
+            let label = |c: &Candle| {
+                let ts = c.timestamp.to_offset(display_tz);
+                ts.format(&time_fmt).unwrap_or_else(|_| ts.to_string())
+            };
+            let start_label = visible.first().map(label).unwrap_or_else(|| "---".into());
             let mid_label = visible
                 .get(visible.len().saturating_sub(1) / 2)
-                .map(|c| {
-                    c.timestamp
-                        .format(&time_fmt)
-                        .unwrap_or_else(|_| c.timestamp.to_string())
-                })
-                .unwrap_or_else(|| "---".into());
-            let end_label = visible
-                .last()
-                .map(|c| {
-                    c.timestamp
-                        .format(&time_fmt)
-                        .unwrap_or_else(|_| c.timestamp.to_string())
-                })
+                .map(label)
                 .unwrap_or_else(|| "---".into());
+            let end_label = visible.last().map(label).unwrap_or_else(|| "---".into());
 
             self.time_axis_cache = Some(TimeAxisCache {
                 revision: self.render_cache_revision,
@@ -1470,6 +2929,7 @@ impl ChartView {
         end: usize,
         columns: usize,
     ) -> Option<&RenderCache> {
+        self.last_render_heavy = false;
         if columns == 0 || start >= end || self.candles.is_empty() {
             self.render_cache = None;
             return None;
@@ -1493,6 +2953,7 @@ impl ChartView {
             None => true,
         };
         if needs_rebuild {
+            self.last_render_heavy = true;
             let visible = &self.candles[start..end];
             let mut aggregated = Vec::with_capacity(columns);
             let mut min_low = f64::INFINITY;
@@ -1533,19 +2994,118 @@ impl ChartView {
     }
 
     pub(super) fn apply_interval(&mut self, interval: Option<Interval>, persist: bool) {
+        // Keep the same range button when the new interval offers it, otherwise show all.
+        let active_label = self
+            .quick_ranges()
+            .get(self.active_range_index)
+            .map(|(label, _)| *label);
+        let ranges = quick_ranges_for(interval);
+        self.active_range_index = ranges
+            .iter()
+            .position(|(label, _)| Some(*label) == active_label)
+            .unwrap_or(ranges.len() - 1);
+        let kept_span = if self.reset_view_on_interval {
+            None
+        } else {
+            self.visible_time_span()
+        };
         self.interval = interval;
         self.candles = self.resampled_for(interval);
         self.invalidate_render_cache();
         self.view_offset = 0.0;
         self.zoom = 1.0;
+        self.pinned_index = None;
         self.hover_index = None;
         self.hover_position = None;
         self.interval_select_open = false;
         self.symbol_search_open = false;
-        self.apply_range_index(self.active_range_index, persist);
+        match kept_span {
+            Some((start, end)) => {
+                self.frame_time_span(start, end);
+                if persist {
+                    let _ = self.persist_viewport();
+                }
+            }
+            None => self.apply_range_index(self.active_range_index, persist),
+        }
         if persist {
-            let _ = self.persist_session("interval", &Self::interval_label(self.interval));
+            let _ = self.persist_session("interval", &Self::interval_key(self.interval));
+            self.persist_view_state();
+        }
+    }
+
+    pub fn auto_interval_enabled(&self) -> bool {
+        self.auto_interval
+    }
+
+    /// In auto mode the interval follows zoom: see [`refresh_auto_interval`](Self::refresh_auto_interval).
+    /// Picking an interval from the menu turns it off.
+    pub fn set_auto_interval(&mut self, enabled: bool) {
+        self.auto_interval = enabled;
+        self.refresh_auto_interval();
+        self.persist_view_state();
+    }
+
+    /// In auto mode, re-picks the interval so the visible time window holds about one candle
+    /// per [`AUTO_INTERVAL_CANDLE_PX`] of chart width (finest [`core::STANDARD_INTERVALS`]
+    /// entry that fits, raw when no aggregation is needed), keeping the window in view.
+    pub(super) fn refresh_auto_interval(&mut self) {
+        if !self.auto_interval || self.base_candles.is_empty() {
+            return;
+        }
+        let Some((start, last_start)) = self.visible_time_span() else {
+            return;
+        };
+        // The last visible bucket runs until the next one would start.
+        let end = match self.interval {
+            Some(interval) => last_start + interval.as_duration() - Duration::nanoseconds(1),
+            None => last_start,
+        };
+        let from = self.base_candles.partition_point(|c| c.timestamp < start);
+        let to = self.base_candles.partition_point(|c| c.timestamp <= end);
+        let visible = &self.base_candles[from..to.max(from)];
+        if visible.is_empty() {
+            return;
+        }
+        let target = self
+            .chart_bounds
+            .map(|b| (f32::from(b.size.width) / AUTO_INTERVAL_CANDLE_PX) as usize)
+            .unwrap_or(AUTO_INTERVAL_FALLBACK_TARGET)
+            .max(1);
+        let (resampled, interval) = resample_to_count(visible, target);
+        let next = (resampled.len() < visible.len()).then_some(interval);
+        if next != self.interval {
+            self.apply_interval(next, false);
+            self.frame_time_span(start, end);
+        }
+    }
+
+    /// Timestamps of the first and last visible candles.
+    fn visible_time_span(&self) -> Option<(OffsetDateTime, OffsetDateTime)> {
+        let (start, end) = self.visible_range();
+        (start < end).then(|| {
+            (
+                self.candles[start].timestamp,
+                self.candles[end - 1].timestamp,
+            )
+        })
+    }
+
+    /// Zooms and scrolls so the candles covering `start..=end` fill the view; a bucket that
+    /// starts before `start` but contains it counts as covering it.
+    fn frame_time_span(&mut self, start: OffsetDateTime, end: OffsetDateTime) {
+        if self.candles.is_empty() {
+            return;
         }
+        let first = self
+            .candles
+            .partition_point(|c| c.timestamp <= start)
+            .saturating_sub(1);
+        let last = self.candles.partition_point(|c| c.timestamp <= end);
+        let visible = last.saturating_sub(first).max(1);
+        let len = self.candles.len() as f32;
+        self.zoom = (len / visible as f32).clamp(1.0, len);
+        self.view_offset = self.clamp_offset(first as f32, visible);
     }
 
     fn resampled_for(&mut self, interval: Option<Interval>) -> Arc<[Candle]> {
@@ -1586,6 +3146,7 @@ impl ChartView {
         let cache = normalize_resamples(&base, resamples);
         let interval = self.interval;
         self.base_candles = base.clone();
+        self.in_progress = None;
         self.resample_cache = cache;
         let next_candles = self
             .resample_cache
@@ -1596,11 +3157,16 @@ impl ChartView {
 
         self.candles = next_candles;
         self.interval = interval;
+        self.pinned_index = None;
         self.invalidate_render_cache();
         self.apply_range_index(self.active_range_index, persist_session);
+        self.apply_pending_initial_visible();
 
         self.source = source;
+        self.stored_coverage = None;
+        self.spread_quotes = Arc::from([]);
         self.load_error = None;
+        self.failed_symbol = None;
         self.loading_symbol = None;
 
         if add_to_watchlist {
@@ -1612,7 +3178,8 @@ impl ChartView {
         }
         if persist_session {
             let _ = self.persist_session("active_source", &self.source);
-            let _ = self.persist_session("interval", &Self::interval_label(self.interval));
+            let _ = self.persist_session("interval", &Self::interval_key(self.interval));
+            self.persist_view_state();
         }
     }
 
@@ -1639,7 +3206,8 @@ impl ChartView {
     }
 
     pub(super) fn apply_range_index(&mut self, index: usize, persist: bool) {
-        let clamped_index = index.min(QUICK_RANGE_WINDOWS.len().saturating_sub(1));
+        let ranges = self.quick_ranges();
+        let clamped_index = index.min(ranges.len().saturating_sub(1));
         self.active_range_index = clamped_index;
         self.interval_select_open = false;
         self.symbol_search_open = false;
@@ -1652,7 +3220,7 @@ impl ChartView {
             return;
         }
 
-        let (_, duration) = QUICK_RANGE_WINDOWS[clamped_index];
+        let (_, duration) = ranges[clamped_index];
         match duration {
             Some(duration) => {
                 let last_ts = self.candles.last().map(|c| c.timestamp);
@@ -1681,9 +3249,96 @@ impl ChartView {
         }
     }
 
+    pub fn price_lock(&self) -> Option<(f64, f64)> {
+        self.price_lock
+    }
+
+    /// Pins the price axis to `min..max` instead of auto-scaling; empty or non-finite
+    /// ranges unlock it.
+    pub fn set_price_lock(&mut self, range: Option<(f64, f64)>) {
+        self.price_lock =
+            range.filter(|(min, max)| min.is_finite() && max.is_finite() && min < max);
+    }
+
+    /// Footer "Lock" button: freezes the price axis at the range currently drawn, or
+    /// unlocks it again.
+    pub fn toggle_price_lock(&mut self) {
+        let next = match self.price_lock {
+            Some(_) => None,
+            None => Some((self.price_min, self.price_max)),
+        };
+        self.set_price_lock(next);
+    }
+
+    /// Recovery action behind the footer "Fit" button: drops the price lock so the axis
+    /// auto-scales again and re-frames the active quick range (all data for "ALL").
+    pub fn fit_to_data(&mut self) {
+        self.price_lock = None;
+        self.apply_range_index(self.active_range_index, true);
+    }
+
     pub(super) fn set_replay_mode(&mut self, enabled: bool) {
+        if enabled && !self.replay_mode {
+            self.replay_cursor = self.candles.len();
+        }
         self.replay_mode = enabled;
         let _ = self.persist_session("replay_mode", if enabled { "true" } else { "false" });
+        self.persist_view_state();
+    }
+
+    /// Number of candles revealed so far; everything when replay is off.
+    pub fn replay_revealed(&self) -> usize {
+        let len = self.candles.len();
+        if self.replay_mode && len > 0 {
+            self.replay_cursor.clamp(1, len)
+        } else {
+            len
+        }
+    }
+
+    /// Replay progress in `0.0..=1.0`.
+    pub fn replay_progress(&self) -> f32 {
+        if self.candles.is_empty() {
+            return 0.0;
+        }
+        self.replay_revealed() as f32 / self.candles.len() as f32
+    }
+
+    /// Reveals `fraction` of the dataset (at least one candle) and keeps it in view.
+    pub(super) fn set_replay_progress(&mut self, fraction: f32) {
+        let len = self.candles.len();
+        if len == 0 {
+            self.replay_cursor = 0;
+            return;
+        }
+        let fraction = if fraction.is_finite() {
+            fraction.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.replay_cursor = ((fraction * len as f32).round() as usize).clamp(1, len);
+        self.hover_index = None;
+        self.hover_position = None;
+    }
+
+    pub fn fast_wicks_enabled(&self) -> bool {
+        self.fast_wicks
+    }
+
+    pub(super) fn set_fast_wicks(&mut self, enabled: bool) {
+        self.fast_wicks = enabled;
+        let _ = self.persist_session("fast_wicks", if enabled { "true" } else { "false" });
+        self.persist_view_state();
+    }
+
+    pub fn tooltip_placement(&self) -> TooltipPlacement {
+        self.tooltip_placement
+    }
+
+    pub fn set_tooltip_placement(&mut self, placement: TooltipPlacement) {
+        self.tooltip_placement = placement;
+        let _ = self.persist_session("tooltip_placement", placement.as_str());
+        self.persist_view_state();
     }
 
     fn persist_session(&self, key: &str, value: &str) -> Result<(), ()> {
@@ -1703,7 +3358,88 @@ impl ChartView {
             guard
                 .set_session_value("zoom", &self.zoom.to_string())
                 .map_err(|_| ())?;
+            guard
+                .set_session_value(VIEW_STATE_KEY, &self.serialize_state())
+                .map_err(|_| ())?;
+        }
+        Ok(())
+    }
+
+    fn persist_view_state(&self) {
+        let _ = self.persist_session(VIEW_STATE_KEY, &self.serialize_state());
+    }
+
+    /// Snapshot of zoom, offset, interval, range, replay, indicators, theme and display
+    /// settings as JSON; see [`restore_state`](Self::restore_state).
+    pub fn serialize_state(&self) -> String {
+        let snapshot = ViewSnapshot {
+            interval: Self::interval_key(self.interval),
+            range_index: self.active_range_index,
+            view_offset: self.view_offset,
+            zoom: self.zoom,
+            replay_mode: self.replay_mode,
+            replay_cursor: self.replay_cursor,
+            fast_wicks: self.fast_wicks,
+            tooltip_placement: self.tooltip_placement.as_str().to_string(),
+            show_volume: self.show_volume,
+            global_volume_scale: self.global_volume_scale,
+            display_tz_secs: self.display_tz.whole_seconds(),
+            grid_lines: self.grid_lines,
+            price_tick_step: self.price_tick_step,
+            right_padding_candles: self.right_padding_candles,
+            x_axis_mode: self.x_axis_mode,
+            crossover_periods: self.crossover_periods,
+            show_pivots: self.show_pivots,
+            reset_view_on_interval: self.reset_view_on_interval,
+            auto_interval: self.auto_interval,
+            theme: self.theme,
+            max_candles: self.max_candles,
+            min_candle_px: self.min_candle_px,
+        };
+        serde_json::to_string(&snapshot).expect("view snapshot has no non-string map keys")
+    }
+
+    /// Applies a [`serialize_state`](Self::serialize_state) blob without persisting the
+    /// individual session keys. Zoom and offset are clamped to the loaded candles.
+    pub fn restore_state(&mut self, state: &str) -> Result<(), serde_json::Error> {
+        let snapshot: ViewSnapshot = serde_json::from_str(state)?;
+
+        if let Some(interval) = Self::parse_interval_key(&snapshot.interval) {
+            self.apply_interval(interval, false);
+        }
+        self.apply_range_index(snapshot.range_index, false);
+
+        self.fast_wicks = snapshot.fast_wicks;
+        if let Some(placement) = TooltipPlacement::parse(&snapshot.tooltip_placement) {
+            self.tooltip_placement = placement;
         }
+        self.show_volume = snapshot.show_volume;
+        self.global_volume_scale = snapshot.global_volume_scale;
+        if let Ok(offset) = UtcOffset::from_whole_seconds(snapshot.display_tz_secs) {
+            self.display_tz = offset;
+        }
+        self.time_axis_cache = None;
+        self.grid_lines = snapshot.grid_lines.map(|n| n.max(2));
+        self.price_tick_step = snapshot.price_tick_step.filter(|step| *step > 0.0);
+        self.right_padding_candles = snapshot.right_padding_candles;
+        self.x_axis_mode = snapshot.x_axis_mode;
+        self.crossover_periods = snapshot
+            .crossover_periods
+            .filter(|&(fast, slow)| fast > 0 && slow > 0);
+        self.show_pivots = snapshot.show_pivots;
+        self.reset_view_on_interval = snapshot.reset_view_on_interval;
+        self.auto_interval = snapshot.auto_interval;
+        self.theme = snapshot.theme;
+        self.max_candles = snapshot.max_candles.map(|n| n.max(1));
+        self.min_candle_px = snapshot.min_candle_px.filter(|px| *px > 0.0);
+
+        self.replay_mode = snapshot.replay_mode;
+        self.replay_cursor = snapshot.replay_cursor.min(self.candles.len());
+        let max_zoom = self.candles.len().max(1) as f32;
+        self.zoom = snapshot.zoom.clamp(1.0, max_zoom);
+        let visible_count = self.visible_len().round().max(1.0) as usize;
+        self.view_offset = self.clamp_offset(snapshot.view_offset, visible_count);
+        self.invalidate_render_cache();
         Ok(())
     }
 
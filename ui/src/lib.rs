@@ -2,11 +2,17 @@ mod assets;
 mod chart;
 pub mod components;
 mod live;
+pub mod logging;
 pub mod perf;
 mod runtime;
+#[cfg(test)]
+mod test_support;
 
-pub use chart::{ChartMeta, ChartView, launch_chart};
+pub use chart::{
+    AxisMode, ChartMeta, ChartTheme, ChartView, TooltipPlacement, XAxisMode, launch_chart,
+};
 pub use runtime::{PerfOptions, RuntimeOptions, launch_runtime, launch_runtime_with_options};
 pub mod data;
 pub mod store;
+pub mod wire;
 pub use assets::application_with_assets;
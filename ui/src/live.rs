@@ -1,24 +1,148 @@
+use std::net::{IpAddr, SocketAddr};
 use std::sync::OnceLock;
+use std::time::Duration;
 use std::{collections::BTreeMap, future};
 
-use core::Candle;
+use core::{Candle, Interval};
 use flux_schema::{WIRE_SCHEMA_VERSION, fb};
-use time::OffsetDateTime;
+use tokio::sync::watch;
 use tokio::time::sleep;
 use zeromq::{Socket, SocketRecv, SocketSend};
 
+use crate::wire::decode_candles;
+
 pub const DEFAULT_LIVE_PUB: &str = "tcp://127.0.0.1:5556";
 pub const DEFAULT_CHUNK_REP: &str = "tcp://127.0.0.1:5557";
 pub const DEFAULT_SOURCE_ID: &str = "SIM";
 pub const DEFAULT_INTERVAL: &str = "1s";
 pub const DEFAULT_BACKFILL_LIMIT: u32 = 10_000;
 
+/// Exponential backoff between reconnects and request retries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    pub initial_ms: u64,
+    pub max_ms: u64,
+}
+
+impl Backoff {
+    /// Doubles `current_ms`, capped at `max_ms`.
+    pub fn next(&self, current_ms: u64) -> u64 {
+        current_ms.saturating_mul(2).min(self.max_ms)
+    }
+}
+
+pub const RECONNECT_BACKOFF: Backoff = Backoff {
+    initial_ms: 200,
+    max_ms: 5_000,
+};
+
+/// Bounded retry for one-shot `chunk_rep` requests (cursor and backfill).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestRetry {
+    pub attempts: u32,
+    /// zeromq keeps retrying a refused connect on its own, so each attempt is cut off here.
+    pub attempt_timeout: Duration,
+    pub backoff: Backoff,
+}
+
+pub const REQUEST_RETRY: RequestRetry = RequestRetry {
+    attempts: 4,
+    attempt_timeout: Duration::from_secs(10),
+    backoff: RECONNECT_BACKOFF,
+};
+
+/// Runs `op` up to `retry.attempts` times, sleeping with backoff between failures.
+/// Returns the last error once attempts run out.
+pub async fn with_retry<T, F, Fut>(retry: RequestRetry, mut op: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, String>>,
+{
+    let mut backoff_ms = retry.backoff.initial_ms;
+    let mut last_err = String::from("no attempts made");
+    for attempt in 0..retry.attempts.max(1) {
+        if attempt > 0 {
+            sleep(Duration::from_millis(backoff_ms)).await;
+            backoff_ms = retry.backoff.next(backoff_ms);
+        }
+        match tokio::time::timeout(retry.attempt_timeout, op()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(err)) => last_err = err,
+            Err(_) => {
+                last_err = format!(
+                    "chunk_rep request timed out after {:?}",
+                    retry.attempt_timeout
+                )
+            }
+        }
+    }
+    Err(last_err)
+}
+
+/// How backfill requests reach `chunk_rep`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChunkTransport {
+    /// One REQ socket per request; works against plain REP servers like the mock replay.
+    #[default]
+    Req,
+    /// A single DEALER socket with several pages in flight, matched by `correlation_id`.
+    Dealer,
+}
+
+impl ChunkTransport {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "req" | "rep" => Some(Self::Req),
+            "dealer" | "router" => Some(Self::Dealer),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LiveConfig {
     pub live_pub: String,
     pub chunk_rep: String,
+    pub chunk_transport: ChunkTransport,
+    /// Local interface (`ip` or `ip:port`) to originate SUB/REQ connections from on
+    /// multi-homed hosts. Validated before every connect so a typo fails fast, but not yet
+    /// applied: zeromq 0.4 connects without a source address, so the OS still picks one.
+    pub bind_local: Option<String>,
     pub source_id: String,
     pub interval: String,
+    /// Report applied candles whose spacing differs from `interval` as [`LiveEvent::Error`].
+    /// Off by default so feeds with irregular bars stay quiet.
+    pub validate_interval: bool,
+    /// Caps the candles fetched while catching up at startup to the most recent N, so a
+    /// long history does not delay going live. `None` fetches everything.
+    pub max_initial_backfill: Option<usize>,
+}
+
+impl LiveConfig {
+    /// Where an initial fill of `(from_sequence_exclusive, latest_sequence]` should start so
+    /// that at most [`max_initial_backfill`](Self::max_initial_backfill) candles are fetched.
+    pub fn initial_backfill_from(&self, from_sequence_exclusive: u64, latest_sequence: u64) -> u64 {
+        match self.max_initial_backfill {
+            Some(cap) => from_sequence_exclusive.max(latest_sequence.saturating_sub(cap as u64)),
+            None => from_sequence_exclusive,
+        }
+    }
+
+    /// Parses `bind_local`; a bare IP means an ephemeral local port.
+    pub fn bind_local_addr(&self) -> Result<Option<SocketAddr>, String> {
+        let Some(raw) = self.bind_local.as_deref() else {
+            return Ok(None);
+        };
+        let raw = raw.trim();
+        if let Ok(addr) = raw.parse::<SocketAddr>() {
+            return Ok(Some(addr));
+        }
+        raw.trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map(|ip| Some(SocketAddr::new(ip, 0)))
+            .map_err(|_| format!("invalid bind_local address: {raw}"))
+    }
 }
 
 impl Default for LiveConfig {
@@ -26,8 +150,20 @@ impl Default for LiveConfig {
         Self {
             live_pub: std::env::var("FLUX_LIVE_PUB").unwrap_or_else(|_| DEFAULT_LIVE_PUB.into()),
             chunk_rep: std::env::var("FLUX_CHUNK_REP").unwrap_or_else(|_| DEFAULT_CHUNK_REP.into()),
+            chunk_transport: std::env::var("FLUX_CHUNK_TRANSPORT")
+                .ok()
+                .and_then(|v| ChunkTransport::parse(&v))
+                .unwrap_or_default(),
+            bind_local: std::env::var("FLUX_BIND_LOCAL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
             source_id: std::env::var("FLUX_SOURCE_ID").unwrap_or_else(|_| DEFAULT_SOURCE_ID.into()),
             interval: std::env::var("FLUX_INTERVAL").unwrap_or_else(|_| DEFAULT_INTERVAL.into()),
+            validate_interval: std::env::var("FLUX_VALIDATE_INTERVAL")
+                .is_ok_and(|v| matches!(v.trim(), "1" | "true" | "yes" | "on")),
+            max_initial_backfill: std::env::var("FLUX_MAX_INITIAL_BACKFILL")
+                .ok()
+                .and_then(|v| v.trim().parse().ok()),
         }
     }
 }
@@ -50,12 +186,30 @@ pub enum LiveEvent {
     Error(String),
 }
 
+/// Coordinator state published for debugging sequence gaps; see
+/// [`run_live_coordinator_with_debug`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiveDebugSnapshot {
+    pub expected_next_sequence: u64,
+    /// Start sequences of batches held back until the gap before them is filled, ascending.
+    pub buffered_starts: Vec<u64>,
+    pub backfill_inflight: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct StreamCursor {
     pub latest_sequence: u64,
     pub latest_ts_ms: i64,
 }
 
+/// One page of a backfill request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillPage {
+    pub from_sequence_exclusive: Option<u64>,
+    pub limit: u32,
+    pub end_ts_ms: Option<i64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct BackfillChunk {
     pub start_sequence: u64,
@@ -74,28 +228,38 @@ pub fn tokio_runtime() -> &'static tokio::runtime::Runtime {
     })
 }
 
+/// Milliseconds per candle for an interval like `"5m"`; `None` unless it parses as a
+/// positive [`Interval`].
 pub fn parse_interval_ms(interval: &str) -> Option<i64> {
-    if interval.len() < 2 {
-        return None;
-    }
-    let (num, unit) = interval.split_at(interval.len() - 1);
-    let n: i64 = num.parse().ok()?;
-    let unit_ms = match unit {
-        "s" => 1_000,
-        "m" => 60_000,
-        "h" => 3_600_000,
-        "d" => 86_400_000,
-        _ => return None,
-    };
-    n.checked_mul(unit_ms)
+    let ms = interval
+        .parse::<Interval>()
+        .ok()?
+        .as_duration()
+        .whole_milliseconds();
+    i64::try_from(ms).ok().filter(|ms| *ms > 0)
 }
 
+/// Fetches one backfill page, retried per [`REQUEST_RETRY`].
 pub async fn backfill_candles(
     cfg: &LiveConfig,
     symbol: &str,
     from_sequence_exclusive: Option<u64>,
     limit: u32,
     end_ts_ms: Option<i64>,
+) -> Result<BackfillChunk, String> {
+    cfg.bind_local_addr()?;
+    with_retry(REQUEST_RETRY, || {
+        backfill_candles_once(cfg, symbol, from_sequence_exclusive, limit, end_ts_ms)
+    })
+    .await
+}
+
+async fn backfill_candles_once(
+    cfg: &LiveConfig,
+    symbol: &str,
+    from_sequence_exclusive: Option<u64>,
+    limit: u32,
+    end_ts_ms: Option<i64>,
 ) -> Result<BackfillChunk, String> {
     let mut socket = zeromq::ReqSocket::new();
     socket
@@ -103,7 +267,7 @@ pub async fn backfill_candles(
         .await
         .map_err(|e| format!("chunk_rep connect failed: {e}"))?;
 
-    let req = encode_backfill_request(cfg, symbol, from_sequence_exclusive, limit, end_ts_ms);
+    let req = encode_backfill_request(cfg, symbol, from_sequence_exclusive, limit, end_ts_ms, None);
     socket
         .send(req.into())
         .await
@@ -116,10 +280,202 @@ pub async fn backfill_candles(
     let bytes: Vec<u8> = repl
         .try_into()
         .map_err(|e| format!("chunk_rep response invalid: {e}"))?;
-    decode_backfill_response(&bytes)
+    decode_backfill_response(&bytes).1
+}
+
+/// Splits `(from_sequence_exclusive, latest_sequence]` into pages of at most `limit` candles.
+pub fn plan_backfill_pages(
+    from_sequence_exclusive: Option<u64>,
+    latest_sequence: u64,
+    limit: u32,
+    end_ts_ms: Option<i64>,
+) -> Vec<BackfillPage> {
+    let limit = limit.max(1);
+    let mut from = from_sequence_exclusive.unwrap_or(0);
+    let mut pages = Vec::new();
+    while from < latest_sequence {
+        pages.push(BackfillPage {
+            from_sequence_exclusive: (from > 0).then_some(from),
+            limit,
+            end_ts_ms,
+        });
+        from = from.saturating_add(limit as u64);
+    }
+    pages
+}
+
+/// Fetches several backfill pages and returns the chunks ordered by `start_sequence`.
+///
+/// With [`ChunkTransport::Dealer`] every page is in flight at once on one socket and
+/// responses are matched back by `correlation_id`; otherwise pages are fetched one by one.
+pub async fn backfill_pages(
+    cfg: &LiveConfig,
+    symbol: &str,
+    pages: &[BackfillPage],
+) -> Result<Vec<BackfillChunk>, String> {
+    let mut chunks = match cfg.chunk_transport {
+        ChunkTransport::Req => {
+            let mut chunks = Vec::with_capacity(pages.len());
+            for page in pages {
+                chunks.push(
+                    backfill_candles(
+                        cfg,
+                        symbol,
+                        page.from_sequence_exclusive,
+                        page.limit,
+                        page.end_ts_ms,
+                    )
+                    .await?,
+                );
+            }
+            chunks
+        }
+        ChunkTransport::Dealer => {
+            cfg.bind_local_addr()?;
+            with_retry(REQUEST_RETRY, || backfill_pages_dealer(cfg, symbol, pages)).await?
+        }
+    };
+    chunks.sort_by_key(|chunk| chunk.start_sequence);
+    Ok(chunks)
+}
+
+async fn backfill_pages_dealer(
+    cfg: &LiveConfig,
+    symbol: &str,
+    pages: &[BackfillPage],
+) -> Result<Vec<BackfillChunk>, String> {
+    let mut socket = zeromq::DealerSocket::new();
+    socket
+        .connect(&cfg.chunk_rep)
+        .await
+        .map_err(|e| format!("chunk_rep connect failed: {e}"))?;
+
+    for (idx, page) in pages.iter().enumerate() {
+        let req = encode_backfill_request(
+            cfg,
+            symbol,
+            page.from_sequence_exclusive,
+            page.limit,
+            page.end_ts_ms,
+            Some(&idx.to_string()),
+        );
+        // Empty delimiter frame so REP/ROUTER peers see a REQ-style envelope.
+        let mut msg = zeromq::ZmqMessage::from(Vec::<u8>::new());
+        msg.push_back(req.into());
+        socket
+            .send(msg)
+            .await
+            .map_err(|e| format!("chunk_rep send failed: {e}"))?;
+    }
+
+    let mut slots: Vec<Option<BackfillChunk>> = vec![None; pages.len()];
+    for _ in 0..pages.len() {
+        let repl = socket
+            .recv()
+            .await
+            .map_err(|e| format!("chunk_rep recv failed: {e}"))?;
+        let payload = repl
+            .into_vec()
+            .pop()
+            .ok_or_else(|| "chunk_rep response empty".to_string())?;
+        let (correlation_id, chunk) = decode_backfill_response(&payload);
+        let slot = correlation_id
+            .and_then(|id| id.parse::<usize>().ok())
+            .and_then(|idx| slots.get_mut(idx))
+            .ok_or_else(|| "chunk_rep response has unknown correlation_id".to_string())?;
+        *slot = Some(chunk?);
+    }
+    slots
+        .into_iter()
+        .map(|slot| slot.ok_or_else(|| "chunk_rep response missing".to_string()))
+        .collect()
 }
 
+/// Fetches everything after `from_sequence_exclusive`: pages up to the cursor's
+/// `latest_sequence` go out together, then `next_sequence` is followed for anything newer.
+/// With a known cursor, [`LiveConfig::max_initial_backfill`] skips all but the newest pages.
+pub async fn backfill_history(
+    cfg: &LiveConfig,
+    symbol: &str,
+    from_sequence_exclusive: Option<u64>,
+    cursor: Option<StreamCursor>,
+) -> Result<(u64, Vec<Candle>), String> {
+    let end_ts_ms = cursor.map(|c| c.latest_ts_ms).filter(|&ts_ms| ts_ms != 0);
+    let from_sequence_exclusive = match cursor {
+        Some(c) if cfg.max_initial_backfill.is_some() => {
+            let from =
+                cfg.initial_backfill_from(from_sequence_exclusive.unwrap_or(0), c.latest_sequence);
+            (from > 0).then_some(from)
+        }
+        _ => from_sequence_exclusive,
+    };
+    let mut pages = cursor
+        .map(|c| {
+            plan_backfill_pages(
+                from_sequence_exclusive,
+                c.latest_sequence,
+                DEFAULT_BACKFILL_LIMIT,
+                end_ts_ms,
+            )
+        })
+        .unwrap_or_default();
+    if pages.is_empty() {
+        pages.push(BackfillPage {
+            from_sequence_exclusive,
+            limit: DEFAULT_BACKFILL_LIMIT,
+            end_ts_ms,
+        });
+    }
+
+    let mut chunks = backfill_pages(cfg, symbol, &pages).await?;
+    let mut next = chunks.last().and_then(follow_up_sequence);
+    while let Some(from) = next {
+        let chunk =
+            backfill_candles(cfg, symbol, Some(from), DEFAULT_BACKFILL_LIMIT, end_ts_ms).await?;
+        next = follow_up_sequence(&chunk);
+        chunks.push(chunk);
+    }
+
+    let (start_sequence, candles) = reassemble_chunks(chunks);
+    if candles.is_empty() {
+        return Ok((
+            from_sequence_exclusive.unwrap_or(0).saturating_add(1),
+            candles,
+        ));
+    }
+    Ok((start_sequence, candles))
+}
+
+fn follow_up_sequence(chunk: &BackfillChunk) -> Option<u64> {
+    if !chunk.has_more || chunk.candles.is_empty() {
+        return None;
+    }
+    chunk.next_sequence
+}
+
+/// Concatenates chunks ordered by sequence, dropping overlap between neighbours.
+pub fn reassemble_chunks(chunks: Vec<BackfillChunk>) -> (u64, Vec<Candle>) {
+    let start_sequence = chunks.first().map_or(0, |chunk| chunk.start_sequence);
+    let mut next_sequence = start_sequence;
+    let mut candles = Vec::new();
+    for chunk in chunks {
+        let skip = next_sequence.saturating_sub(chunk.start_sequence) as usize;
+        if skip >= chunk.candles.len() {
+            continue;
+        }
+        next_sequence = chunk.start_sequence + chunk.candles.len() as u64;
+        candles.extend(chunk.candles.into_iter().skip(skip));
+    }
+    (start_sequence, candles)
+}
+
+/// Asks `chunk_rep` for the stream's latest sequence, retried per [`REQUEST_RETRY`].
 pub async fn get_cursor(cfg: &LiveConfig, symbol: &str) -> Result<StreamCursor, String> {
+    cfg.bind_local_addr()?;
+    with_retry(REQUEST_RETRY, || get_cursor_once(cfg, symbol)).await
+}
+
+async fn get_cursor_once(cfg: &LiveConfig, symbol: &str) -> Result<StreamCursor, String> {
     let mut socket = zeromq::ReqSocket::new();
     socket
         .connect(&cfg.chunk_rep)
@@ -148,8 +504,9 @@ pub async fn subscribe_candles(
     symbol: String,
     sender: tokio::sync::mpsc::UnboundedSender<LiveEvent>,
 ) -> Result<(), String> {
+    cfg.bind_local_addr()?;
     let topic = topic_for(&cfg, &symbol);
-    let mut backoff_ms = 200u64;
+    let mut backoff_ms = RECONNECT_BACKOFF.initial_ms;
     loop {
         let _ = sender.send(LiveEvent::Status(LiveStatus::Connecting));
         match subscribe_candles_once(&cfg, &topic, &sender).await {
@@ -157,8 +514,8 @@ pub async fn subscribe_candles(
             Err(err) => {
                 let _ = sender.send(LiveEvent::Error(err));
                 let _ = sender.send(LiveEvent::Status(LiveStatus::Disconnected));
-                sleep(std::time::Duration::from_millis(backoff_ms)).await;
-                backoff_ms = (backoff_ms.saturating_mul(2)).min(5_000);
+                sleep(Duration::from_millis(backoff_ms)).await;
+                backoff_ms = RECONNECT_BACKOFF.next(backoff_ms);
             }
         }
     }
@@ -170,11 +527,31 @@ pub async fn run_live_coordinator(
     last_applied_sequence: u64,
     sender: tokio::sync::mpsc::UnboundedSender<LiveEvent>,
 ) -> Result<(), String> {
+    let (debug, _) = watch::channel(LiveDebugSnapshot::default());
+    run_live_coordinator_with_debug(cfg, symbol, last_applied_sequence, sender, debug).await
+}
+
+/// [`run_live_coordinator`] that also publishes a [`LiveDebugSnapshot`] to `debug` after
+/// every message or backfill it handles. Read-only: the coordinator never reads it back.
+pub async fn run_live_coordinator_with_debug(
+    cfg: LiveConfig,
+    symbol: String,
+    last_applied_sequence: u64,
+    sender: tokio::sync::mpsc::UnboundedSender<LiveEvent>,
+    debug: watch::Sender<LiveDebugSnapshot>,
+) -> Result<(), String> {
+    cfg.bind_local_addr()?;
     let topic = topic_for(&cfg, &symbol);
-    let interval_ms = parse_interval_ms(&cfg.interval).unwrap_or(1_000).max(1);
+    let interval_ms = parse_interval_ms(&cfg.interval)
+        .ok_or_else(|| format!("invalid live interval '{}'", cfg.interval))?;
     let mut expected_next_sequence = last_applied_sequence.saturating_add(1).max(1);
+    let mut interval_check = cfg
+        .validate_interval
+        .then(|| IntervalCheck::new(interval_ms));
+    // Cleared once the first gap is planned; later gaps are always filled in full.
+    let mut initial_fill = true;
 
-    let mut backoff_ms = 200u64;
+    let mut backoff_ms = RECONNECT_BACKOFF.initial_ms;
     loop {
         let _ = sender.send(LiveEvent::Status(LiveStatus::Connecting));
 
@@ -182,19 +559,19 @@ pub async fn run_live_coordinator(
         if let Err(err) = socket.connect(&cfg.live_pub).await {
             let _ = sender.send(LiveEvent::Error(format!("live_pub connect failed: {err}")));
             let _ = sender.send(LiveEvent::Status(LiveStatus::Disconnected));
-            sleep(std::time::Duration::from_millis(backoff_ms)).await;
-            backoff_ms = (backoff_ms.saturating_mul(2)).min(5_000);
+            sleep(Duration::from_millis(backoff_ms)).await;
+            backoff_ms = RECONNECT_BACKOFF.next(backoff_ms);
             continue;
         }
         if let Err(err) = socket.subscribe(&topic).await {
             let _ = sender.send(LiveEvent::Error(format!("subscribe failed: {err}")));
             let _ = sender.send(LiveEvent::Status(LiveStatus::Disconnected));
-            sleep(std::time::Duration::from_millis(backoff_ms)).await;
-            backoff_ms = (backoff_ms.saturating_mul(2)).min(5_000);
+            sleep(Duration::from_millis(backoff_ms)).await;
+            backoff_ms = RECONNECT_BACKOFF.next(backoff_ms);
             continue;
         }
         let _ = sender.send(LiveEvent::Status(LiveStatus::Subscribed));
-        backoff_ms = 200;
+        backoff_ms = RECONNECT_BACKOFF.initial_ms;
 
         let mut buffered: BTreeMap<u64, Vec<Candle>> = BTreeMap::new();
         let mut backfill_inflight: Option<
@@ -202,6 +579,12 @@ pub async fn run_live_coordinator(
         > = None;
 
         loop {
+            // Runs after every branch below, including the ones that `continue`.
+            debug.send_replace(LiveDebugSnapshot {
+                expected_next_sequence,
+                buffered_starts: buffered.keys().copied().collect(),
+                backfill_inflight: backfill_inflight.is_some(),
+            });
             tokio::select! {
                 msg = socket.recv() => {
                     let msg: zeromq::ZmqMessage = match msg {
@@ -232,7 +615,22 @@ pub async fn run_live_coordinator(
                     }
 
                     if start_sequence > expected_next_sequence {
-                        buffered.insert(start_sequence, candles);
+                        buffer_batch(&mut buffered, start_sequence, candles);
+                        if initial_fill {
+                            initial_fill = false;
+                            expected_next_sequence = cfg
+                                .initial_backfill_from(expected_next_sequence - 1, start_sequence - 1)
+                                + 1;
+                            if expected_next_sequence == start_sequence {
+                                drain_buffered_batches(
+                                    &sender,
+                                    &mut interval_check,
+                                    &mut expected_next_sequence,
+                                    &mut buffered,
+                                );
+                                continue;
+                            }
+                        }
                         if backfill_inflight.is_none() {
                             let from_exclusive = expected_next_sequence.saturating_sub(1);
                             let (end_ts_ms, missing_limit) = gap_backfill_bounds(
@@ -262,6 +660,7 @@ pub async fn run_live_coordinator(
                         continue;
                     }
 
+                    initial_fill = false;
                     // In-order or overlapping batch: trim duplicates and apply.
                     let mut candles = candles;
                     if start_sequence < expected_next_sequence {
@@ -273,14 +672,16 @@ pub async fn run_live_coordinator(
                     }
                     if !candles.is_empty() {
                         let len = candles.len() as u64;
-                        let _ = sender.send(LiveEvent::CandleBatch {
-                            start_sequence: expected_next_sequence,
-                            candles,
-                        });
+                        send_batch(&sender, &mut interval_check, expected_next_sequence, candles);
                         expected_next_sequence = expected_next_sequence.saturating_add(len);
                     }
 
-                    drain_buffered_batches(&sender, &mut expected_next_sequence, &mut buffered);
+                    drain_buffered_batches(
+                        &sender,
+                        &mut interval_check,
+                        &mut expected_next_sequence,
+                        &mut buffered,
+                    );
 
                     if backfill_inflight.is_none() && should_backfill_gap(expected_next_sequence, &buffered) {
                         let from_exclusive = expected_next_sequence.saturating_sub(1);
@@ -321,33 +722,15 @@ pub async fn run_live_coordinator(
                     backfill_inflight = None;
                     match res {
                         Ok(Ok(chunk)) => {
-                            if !chunk.candles.is_empty() {
-                                let chunk_start = chunk.start_sequence;
-                                if chunk_start > expected_next_sequence {
-                                    // Unexpected gap still: buffer the chunk and retry from expected.
-                                    buffered.insert(chunk_start, chunk.candles);
-                                } else {
-                                    let mut candles = chunk.candles;
-                                    if chunk_start < expected_next_sequence {
-                                        let skip = (expected_next_sequence - chunk_start) as usize;
-                                        if skip < candles.len() {
-                                            candles.drain(0..skip);
-                                        } else {
-                                            candles.clear();
-                                        }
-                                    }
-                                    if !candles.is_empty() {
-                                        let len = candles.len() as u64;
-                                        let _ = sender.send(LiveEvent::CandleBatch {
-                                            start_sequence: expected_next_sequence,
-                                            candles,
-                                        });
-                                        expected_next_sequence = expected_next_sequence.saturating_add(len);
-                                    }
-                                }
-                            }
-
-                            drain_buffered_batches(&sender, &mut expected_next_sequence, &mut buffered);
+                            // Reconciled with the buffered live batches by sequence; a chunk
+                            // still past the gap waits there for the retry below.
+                            buffer_batch(&mut buffered, chunk.start_sequence, chunk.candles);
+                            drain_buffered_batches(
+                                &sender,
+                                &mut interval_check,
+                                &mut expected_next_sequence,
+                                &mut buffered,
+                            );
 
                             if should_backfill_gap(expected_next_sequence, &buffered) {
                                 let from_exclusive = expected_next_sequence.saturating_sub(1);
@@ -421,13 +804,86 @@ fn gap_backfill_bounds(
     let missing_limit = (missing.min(DEFAULT_BACKFILL_LIMIT as u64).max(1)) as u32;
     let end_ts_ms = next_batch
         .and_then(|candles| candles.first())
-        .map(|c| (c.timestamp.unix_timestamp_nanos() / 1_000_000) as i64)
+        .map(Candle::ts_ms)
         .and_then(|first_ts| first_ts.checked_sub(interval_ms));
     (end_ts_ms, missing_limit)
 }
 
+/// Tracks the last applied timestamp to check candle spacing against the configured interval.
+#[derive(Debug, Clone)]
+struct IntervalCheck {
+    interval_ms: i64,
+    last_ts_ms: Option<i64>,
+}
+
+impl IntervalCheck {
+    fn new(interval_ms: i64) -> Self {
+        Self {
+            interval_ms,
+            last_ts_ms: None,
+        }
+    }
+
+    /// Advances past `candles` and describes the first spacing mismatch, if any.
+    fn check(&mut self, start_sequence: u64, candles: &[Candle]) -> Option<String> {
+        let mut mismatch = None;
+        for (offset, candle) in candles.iter().enumerate() {
+            let ts_ms = candle.ts_ms();
+            if let Some(prev) = self.last_ts_ms
+                && mismatch.is_none()
+                && ts_ms - prev != self.interval_ms
+            {
+                mismatch = Some(format!(
+                    "candle seq {} is {}ms after the previous one, expected {}ms",
+                    start_sequence + offset as u64,
+                    ts_ms - prev,
+                    self.interval_ms
+                ));
+            }
+            self.last_ts_ms = Some(ts_ms);
+        }
+        mismatch
+    }
+}
+
+fn send_batch(
+    sender: &tokio::sync::mpsc::UnboundedSender<LiveEvent>,
+    interval_check: &mut Option<IntervalCheck>,
+    start_sequence: u64,
+    candles: Vec<Candle>,
+) {
+    if let Some(msg) = interval_check
+        .as_mut()
+        .and_then(|check| check.check(start_sequence, &candles))
+    {
+        let _ = sender.send(LiveEvent::Error(msg));
+    }
+    let _ = sender.send(LiveEvent::CandleBatch {
+        start_sequence,
+        candles,
+    });
+}
+
+/// Queues `candles` (sequences from `start_sequence`) for [`drain_buffered_batches`]. When a
+/// batch already waits at the same start, the one reaching further wins; overlaps between
+/// different starts are trimmed on drain, so every sequence is applied once.
+fn buffer_batch(
+    buffered: &mut BTreeMap<u64, Vec<Candle>>,
+    start_sequence: u64,
+    candles: Vec<Candle>,
+) {
+    if candles.is_empty() {
+        return;
+    }
+    let queued = buffered.entry(start_sequence).or_default();
+    if candles.len() > queued.len() {
+        *queued = candles;
+    }
+}
+
 fn drain_buffered_batches(
     sender: &tokio::sync::mpsc::UnboundedSender<LiveEvent>,
+    interval_check: &mut Option<IntervalCheck>,
     expected_next_sequence: &mut u64,
     buffered: &mut BTreeMap<u64, Vec<Candle>>,
 ) {
@@ -453,10 +909,7 @@ fn drain_buffered_batches(
             continue;
         }
         let len = candles.len() as u64;
-        let _ = sender.send(LiveEvent::CandleBatch {
-            start_sequence: *expected_next_sequence,
-            candles,
-        });
+        send_batch(sender, interval_check, *expected_next_sequence, candles);
         *expected_next_sequence = expected_next_sequence.saturating_add(len);
     }
 }
@@ -516,9 +969,11 @@ fn encode_backfill_request(
     from_sequence_exclusive: Option<u64>,
     limit: u32,
     end_ts_ms: Option<i64>,
+    correlation_id: Option<&str>,
 ) -> Vec<u8> {
     let mut fbb = flatbuffers::FlatBufferBuilder::new();
     let key = build_stream_key(&mut fbb, cfg, symbol);
+    let correlation_id = correlation_id.map(|id| fbb.create_string(id));
     let req = fb::BackfillCandlesRequest::create(
         &mut fbb,
         &fb::BackfillCandlesRequestArgs {
@@ -535,7 +990,7 @@ fn encode_backfill_request(
         &fb::EnvelopeArgs {
             schema_version: WIRE_SCHEMA_VERSION,
             type_hint: fb::MessageType::BACKFILL_CANDLES_REQUEST,
-            correlation_id: None,
+            correlation_id,
             message_type: fb::Message::BackfillCandlesRequest,
             message: Some(req.as_union_value()),
         },
@@ -544,8 +999,16 @@ fn encode_backfill_request(
     fbb.finished_data().to_vec()
 }
 
-fn decode_backfill_response(bytes: &[u8]) -> Result<BackfillChunk, String> {
-    let env = fb::root_as_envelope(bytes).map_err(|_| "invalid envelope".to_string())?;
+/// Decodes a backfill reply along with the `correlation_id` it echoes, if any.
+fn decode_backfill_response(bytes: &[u8]) -> (Option<String>, Result<BackfillChunk, String>) {
+    let Ok(env) = fb::root_as_envelope(bytes) else {
+        return (None, Err("invalid envelope".to_string()));
+    };
+    let correlation_id = env.correlation_id().map(|id| id.to_string());
+    (correlation_id, decode_backfill_envelope(env))
+}
+
+fn decode_backfill_envelope(env: fb::Envelope<'_>) -> Result<BackfillChunk, String> {
     if env.schema_version() != WIRE_SCHEMA_VERSION {
         return Err("unsupported schema_version".to_string());
     }
@@ -633,31 +1096,7 @@ fn decode_get_cursor_response(bytes: &[u8]) -> Result<StreamCursor, String> {
     })
 }
 
-fn decode_candles(
-    candles: Option<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<fb::Candle<'_>>>>,
-) -> Result<Vec<Candle>, String> {
-    let mut out = Vec::new();
-    let Some(candles) = candles else {
-        return Ok(out);
-    };
-    out.reserve(candles.len());
-    for c in candles {
-        let ts_ms = c.ts_ms();
-        let timestamp = OffsetDateTime::from_unix_timestamp_nanos((ts_ms as i128) * 1_000_000)
-            .map_err(|e| format!("invalid ts_ms={ts_ms}: {e}"))?;
-        out.push(Candle {
-            timestamp,
-            open: c.open(),
-            high: c.high(),
-            low: c.low(),
-            close: c.close(),
-            volume: c.volume(),
-        });
-    }
-    Ok(out)
-}
-
-fn build_stream_key<'a>(
+pub(crate) fn build_stream_key<'a>(
     fbb: &mut flatbuffers::FlatBufferBuilder<'a>,
     cfg: &LiveConfig,
     symbol: &str,
@@ -687,6 +1126,10 @@ mod tests {
     use zeromq::{Socket, SocketRecv, SocketSend};
 
     use super::*;
+    use crate::test_support::{
+        MockFluxServer, encode_backfill_response, encode_candle_batch, second_candle,
+        second_candles,
+    };
 
     fn pick_unused_tcp_port() -> u16 {
         TcpListener::bind("127.0.0.1:0")
@@ -704,104 +1147,6 @@ mod tests {
         std::env::temp_dir().join(format!("gpui-kbar-live-roundtrip-{nonce}.duckdb"))
     }
 
-    fn encode_candle_batch(
-        cfg: &LiveConfig,
-        symbol: &str,
-        start_sequence: u64,
-        candles: &[Candle],
-    ) -> Vec<u8> {
-        let mut fbb = flatbuffers::FlatBufferBuilder::new();
-        let key = super::build_stream_key(&mut fbb, cfg, symbol);
-
-        let mut candle_offsets = Vec::with_capacity(candles.len());
-        for candle in candles {
-            let ts_ms = candle.timestamp.unix_timestamp_nanos() / 1_000_000;
-            candle_offsets.push(fb::Candle::create(
-                &mut fbb,
-                &fb::CandleArgs {
-                    ts_ms: ts_ms as i64,
-                    open: candle.open,
-                    high: candle.high,
-                    low: candle.low,
-                    close: candle.close,
-                    volume: candle.volume,
-                },
-            ));
-        }
-        let candle_vec = fbb.create_vector(&candle_offsets);
-        let batch = fb::CandleBatch::create(
-            &mut fbb,
-            &fb::CandleBatchArgs {
-                key: Some(key),
-                start_sequence,
-                candles: Some(candle_vec),
-            },
-        );
-
-        let env = fb::Envelope::create(
-            &mut fbb,
-            &fb::EnvelopeArgs {
-                schema_version: WIRE_SCHEMA_VERSION,
-                type_hint: fb::MessageType::CANDLE_BATCH,
-                correlation_id: None,
-                message_type: fb::Message::CandleBatch,
-                message: Some(batch.as_union_value()),
-            },
-        );
-        fb::finish_envelope_buffer(&mut fbb, env);
-        fbb.finished_data().to_vec()
-    }
-
-    fn encode_backfill_response(
-        cfg: &LiveConfig,
-        symbol: &str,
-        start_sequence: u64,
-        candles: &[Candle],
-    ) -> Vec<u8> {
-        let mut fbb = flatbuffers::FlatBufferBuilder::new();
-        let key = super::build_stream_key(&mut fbb, cfg, symbol);
-
-        let mut candle_offsets = Vec::with_capacity(candles.len());
-        for candle in candles {
-            let ts_ms = candle.timestamp.unix_timestamp_nanos() / 1_000_000;
-            candle_offsets.push(fb::Candle::create(
-                &mut fbb,
-                &fb::CandleArgs {
-                    ts_ms: ts_ms as i64,
-                    open: candle.open,
-                    high: candle.high,
-                    low: candle.low,
-                    close: candle.close,
-                    volume: candle.volume,
-                },
-            ));
-        }
-        let candle_vec = fbb.create_vector(&candle_offsets);
-        let resp = fb::BackfillCandlesResponse::create(
-            &mut fbb,
-            &fb::BackfillCandlesResponseArgs {
-                key: Some(key),
-                start_sequence,
-                candles: Some(candle_vec),
-                has_more: false,
-                next_sequence: 0,
-            },
-        );
-
-        let env = fb::Envelope::create(
-            &mut fbb,
-            &fb::EnvelopeArgs {
-                schema_version: WIRE_SCHEMA_VERSION,
-                type_hint: fb::MessageType::BACKFILL_CANDLES_RESPONSE,
-                correlation_id: None,
-                message_type: fb::Message::BackfillCandlesResponse,
-                message: Some(resp.as_union_value()),
-            },
-        );
-        fb::finish_envelope_buffer(&mut fbb, env);
-        fbb.finished_data().to_vec()
-    }
-
     #[test]
     fn live_roundtrip_persists_and_restores_candles() {
         tokio_runtime().block_on(async {
@@ -811,8 +1156,12 @@ mod tests {
             let cfg = LiveConfig {
                 live_pub: format!("tcp://127.0.0.1:{live_port}"),
                 chunk_rep: format!("tcp://127.0.0.1:{rep_port}"),
+                chunk_transport: ChunkTransport::Req,
+                bind_local: None,
                 source_id: "SIM".to_string(),
                 interval: "1s".to_string(),
+                validate_interval: false,
+                max_initial_backfill: None,
             };
             let symbol = "TEST";
 
@@ -890,6 +1239,7 @@ mod tests {
                         symbol,
                         from_exclusive.saturating_add(1),
                         slice,
+                        None,
                     );
                     rep_socket.send(resp.into()).await.expect("rep send");
                 }
@@ -917,7 +1267,9 @@ mod tests {
                 .expect("backfill");
             assert_eq!(chunk.start_sequence, 1);
             assert_eq!(chunk.candles.len(), 3);
-            store.write_candles(symbol, &chunk.candles).expect("write");
+            store
+                .write_candles(symbol, Some(&cfg.interval), &chunk.candles)
+                .expect("write");
             store
                 .set_session_value(&cursor_key_for(&cfg, symbol), "3")
                 .expect("cursor");
@@ -938,14 +1290,18 @@ mod tests {
             let (start_sequence, live) = super::decode_candle_batch(payload).expect("decode batch");
             assert_eq!(start_sequence, 4);
             assert_eq!(live.len(), 2);
-            store.append_candles(symbol, &live).expect("append");
+            store
+                .append_candles(symbol, Some(&cfg.interval), &live)
+                .expect("append");
             store
                 .set_session_value(&cursor_key_for(&cfg, symbol), "5")
                 .expect("cursor update");
 
             drop(store);
             let reopened = DuckDbStore::new(&path, StorageMode::Disk).expect("reopen store");
-            let loaded = reopened.load_candles(symbol, None).expect("load");
+            let loaded = reopened
+                .load_candles(symbol, Some(&cfg.interval), None)
+                .expect("load");
             assert_eq!(loaded.len(), 5);
             assert_eq!(loaded.last().unwrap().close, 3.5);
             let cursor = reopened
@@ -958,4 +1314,394 @@ mod tests {
             pub_task.abort();
         });
     }
+
+    #[test]
+    fn cursor_request_retries_until_server_binds() {
+        tokio_runtime().block_on(async {
+            let live_pub = format!("tcp://127.0.0.1:{}", pick_unused_tcp_port());
+            let chunk_rep = format!("tcp://127.0.0.1:{}", pick_unused_tcp_port());
+            let cfg = LiveConfig {
+                live_pub: live_pub.clone(),
+                chunk_rep: chunk_rep.clone(),
+                chunk_transport: ChunkTransport::Req,
+                bind_local: None,
+                source_id: "SIM".to_string(),
+                interval: "1s".to_string(),
+                validate_interval: false,
+                max_initial_backfill: None,
+            };
+            let retry = RequestRetry {
+                attempts: 6,
+                attempt_timeout: Duration::from_millis(300),
+                backoff: Backoff {
+                    initial_ms: 50,
+                    max_ms: 200,
+                },
+            };
+
+            let server_task = tokio::spawn(async move {
+                sleep(Duration::from_millis(400)).await;
+                MockFluxServer::bind("TEST", second_candles(1), &live_pub, &chunk_rep).await
+            });
+
+            let mut attempts = 0;
+            let cursor = with_retry(retry, || {
+                attempts += 1;
+                get_cursor_once(&cfg, "TEST")
+            })
+            .await
+            .expect("cursor after retry");
+            assert_eq!(cursor.latest_sequence, 1);
+            assert!(
+                attempts > 1,
+                "first attempt should fail before the server binds"
+            );
+
+            server_task.await.expect("server task").shutdown().await;
+        });
+    }
+
+    #[test]
+    fn coordinator_backfills_gap_then_applies_live_batch() {
+        tokio_runtime().block_on(async {
+            let symbol = "TEST";
+            let candles = second_candles(5);
+            let server = MockFluxServer::start(symbol, candles.clone()).await;
+            let cfg = server.config();
+
+            let cursor = get_cursor(&cfg, symbol).await.expect("cursor");
+            assert_eq!(cursor.latest_sequence, 5);
+
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let coordinator = tokio::spawn(run_live_coordinator(cfg, symbol.to_string(), 0, tx));
+
+            let mut applied: Vec<(u64, Vec<Candle>)> = Vec::new();
+            let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+            while applied.iter().map(|(_, c)| c.len()).sum::<usize>() < candles.len() {
+                assert!(
+                    tokio::time::Instant::now() < deadline,
+                    "timed out: {applied:?}"
+                );
+                // Republish until the subscription has joined; overlaps are trimmed.
+                server.publish(4, &candles[3..]);
+                while let Ok(Some(event)) =
+                    timeout(std::time::Duration::from_millis(100), rx.recv()).await
+                {
+                    match event {
+                        LiveEvent::CandleBatch {
+                            start_sequence,
+                            candles,
+                        } => applied.push((start_sequence, candles)),
+                        LiveEvent::Error(err) => panic!("live error: {err}"),
+                        _ => {}
+                    }
+                }
+            }
+
+            assert_eq!(
+                applied[0].0, 1,
+                "gap is backfilled before live candles apply"
+            );
+            let flattened: Vec<Candle> = applied.into_iter().flat_map(|(_, c)| c).collect();
+            assert_eq!(flattened, candles);
+
+            coordinator.abort();
+            server.shutdown().await;
+        });
+    }
+
+    #[test]
+    fn coordinator_caps_initial_backfill_to_most_recent_candles() {
+        tokio_runtime().block_on(async {
+            let symbol = "TEST";
+            let candles = second_candles(20);
+            let server = MockFluxServer::start(symbol, candles.clone()).await;
+            let cfg = LiveConfig {
+                max_initial_backfill: Some(5),
+                ..server.config()
+            };
+
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let coordinator = tokio::spawn(run_live_coordinator(cfg, symbol.to_string(), 0, tx));
+
+            let mut applied: Vec<(u64, Vec<Candle>)> = Vec::new();
+            let mut statuses = Vec::new();
+            let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+            while applied.iter().map(|(_, c)| c.len()).sum::<usize>() < 6
+                || statuses.last() != Some(&LiveStatus::Subscribed)
+            {
+                assert!(
+                    tokio::time::Instant::now() < deadline,
+                    "timed out: {applied:?} {statuses:?}"
+                );
+                server.publish(20, &candles[19..]);
+                while let Ok(Some(event)) =
+                    timeout(std::time::Duration::from_millis(100), rx.recv()).await
+                {
+                    match event {
+                        LiveEvent::CandleBatch {
+                            start_sequence,
+                            candles,
+                        } => applied.push((start_sequence, candles)),
+                        LiveEvent::Status(status) => statuses.push(status),
+                        LiveEvent::Error(err) => panic!("live error: {err}"),
+                    }
+                }
+            }
+
+            assert_eq!(applied[0].0, 15, "only the newest 5 candles are backfilled");
+            assert!(statuses.contains(&LiveStatus::Backfilling));
+            let flattened: Vec<Candle> = applied.into_iter().flat_map(|(_, c)| c).collect();
+            assert_eq!(flattened, candles[14..]);
+
+            coordinator.abort();
+            server.shutdown().await;
+        });
+    }
+
+    #[test]
+    fn debug_snapshot_reports_buffered_gap_and_inflight_backfill() {
+        tokio_runtime().block_on(async {
+            let symbol = "TEST";
+            let candles = second_candles(10);
+            let server = MockFluxServer::start(symbol, candles.clone()).await;
+            // A REP socket that never answers keeps the gap backfill in flight.
+            let mut silent_rep = zeromq::RepSocket::new();
+            let silent_endpoint = silent_rep
+                .bind("tcp://127.0.0.1:0")
+                .await
+                .expect("silent rep bind");
+            let cfg = LiveConfig {
+                chunk_rep: silent_endpoint.to_string(),
+                ..server.config()
+            };
+
+            let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+            let (debug_tx, mut debug_rx) = watch::channel(LiveDebugSnapshot::default());
+            let coordinator = tokio::spawn(run_live_coordinator_with_debug(
+                cfg,
+                symbol.to_string(),
+                0,
+                tx,
+                debug_tx,
+            ));
+
+            let expected = LiveDebugSnapshot {
+                expected_next_sequence: 1,
+                buffered_starts: vec![10],
+                backfill_inflight: true,
+            };
+            let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+            while *debug_rx.borrow_and_update() != expected {
+                assert!(
+                    tokio::time::Instant::now() < deadline,
+                    "timed out: {:?}",
+                    *debug_rx.borrow()
+                );
+                server.publish(10, &candles[9..]);
+                let _ = timeout(std::time::Duration::from_millis(100), debug_rx.changed()).await;
+            }
+
+            coordinator.abort();
+            server.shutdown().await;
+        });
+    }
+
+    #[test]
+    fn dealer_backfill_reassembles_concurrent_pages() {
+        tokio_runtime().block_on(async {
+            let rep_port = pick_unused_tcp_port();
+            let cfg = LiveConfig {
+                live_pub: "tcp://127.0.0.1:1".to_string(),
+                chunk_rep: format!("tcp://127.0.0.1:{rep_port}"),
+                chunk_transport: ChunkTransport::Dealer,
+                bind_local: None,
+                source_id: "SIM".to_string(),
+                interval: "1s".to_string(),
+                validate_interval: false,
+                max_initial_backfill: None,
+            };
+            let symbol = "TEST";
+            let candles = second_candles(9);
+
+            let router_addr = cfg.chunk_rep.clone();
+            let router_cfg = cfg.clone();
+            let router_candles = candles.clone();
+            let router_task = tokio::spawn(async move {
+                let mut router = zeromq::RouterSocket::new();
+                router.bind(&router_addr).await.expect("router bind");
+                // Hold every request before answering so all three are in flight at once.
+                let mut pending = Vec::new();
+                while pending.len() < 3 {
+                    let mut frames = router.recv().await.expect("router recv").into_vec();
+                    let payload = frames.pop().expect("payload frame");
+                    let env = fb::root_as_envelope(&payload).expect("valid envelope");
+                    let correlation_id = env.correlation_id().expect("correlation id").to_string();
+                    let req = env
+                        .message_as_backfill_candles_request()
+                        .expect("BackfillCandlesRequest body");
+                    let from = req.from_sequence_exclusive() as usize;
+                    let end = router_candles.len().min(from + req.limit() as usize);
+                    let resp = encode_backfill_response(
+                        &router_cfg,
+                        symbol,
+                        from as u64 + 1,
+                        &router_candles[from..end],
+                        Some(&correlation_id),
+                    );
+                    pending.push((frames, resp));
+                }
+                for (frames, resp) in pending.into_iter().rev() {
+                    let mut frames = frames.into_iter();
+                    let mut msg = zeromq::ZmqMessage::from(frames.next().expect("identity"));
+                    for frame in frames {
+                        msg.push_back(frame);
+                    }
+                    msg.push_back(resp.into());
+                    router.send(msg).await.expect("router send");
+                }
+            });
+
+            let pages = plan_backfill_pages(None, 9, 3, None);
+            assert_eq!(pages.len(), 3);
+            let chunks = timeout(
+                std::time::Duration::from_secs(5),
+                backfill_pages(&cfg, symbol, &pages),
+            )
+            .await
+            .expect("backfill timeout")
+            .expect("backfill");
+            let starts: Vec<u64> = chunks.iter().map(|c| c.start_sequence).collect();
+            assert_eq!(starts, vec![1, 4, 7]);
+
+            let (start_sequence, reassembled) = reassemble_chunks(chunks);
+            assert_eq!(start_sequence, 1);
+            assert_eq!(reassembled, candles);
+
+            router_task.abort();
+        });
+    }
+
+    #[test]
+    fn bind_local_is_validated_and_roundtrip_still_works() {
+        tokio_runtime().block_on(async {
+            let symbol = "TEST";
+            let candles = second_candles(3);
+            let server = MockFluxServer::start(symbol, candles.clone()).await;
+            let mut cfg = LiveConfig {
+                bind_local: Some("127.0.0.1".to_string()),
+                ..server.config()
+            };
+            assert_eq!(
+                cfg.bind_local_addr(),
+                Ok(Some("127.0.0.1:0".parse().unwrap()))
+            );
+
+            let chunk = timeout(
+                std::time::Duration::from_secs(5),
+                backfill_candles(&cfg, symbol, None, 10, None),
+            )
+            .await
+            .expect("backfill timeout")
+            .expect("backfill");
+            assert_eq!(chunk.candles, candles);
+
+            cfg.bind_local = Some("[::1]:6000".to_string());
+            assert_eq!(
+                cfg.bind_local_addr(),
+                Ok(Some("[::1]:6000".parse().unwrap()))
+            );
+
+            cfg.bind_local = Some("eth0:not-a-port".to_string());
+            let err = backfill_candles(&cfg, symbol, None, 10, None)
+                .await
+                .expect_err("invalid bind_local");
+            assert!(err.contains("bind_local"));
+
+            server.shutdown().await;
+        });
+    }
+
+    #[test]
+    fn overlapping_backfill_chunk_applies_each_sequence_once() {
+        let at = |sequence: u64| second_candle(sequence as i64, sequence as f64);
+        let run = |candles: std::ops::RangeInclusive<u64>| candles.map(at).collect::<Vec<_>>();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut expected_next_sequence = 5;
+        // Live batches that arrived during the gap, one sharing the chunk's start below.
+        let mut buffered = BTreeMap::from([(8, run(8..=9)), (12, run(12..=14))]);
+
+        // A chunk past the gap that collides with a longer live batch keeps the longer one.
+        buffer_batch(&mut buffered, 12, run(12..=13));
+        assert_eq!(buffered[&12], run(12..=14));
+
+        // The backfill chunk starts before `expected` and runs past the batch at 8.
+        buffer_batch(&mut buffered, 3, run(3..=12));
+        drain_buffered_batches(&tx, &mut None, &mut expected_next_sequence, &mut buffered);
+        drop(tx);
+
+        let mut applied = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let LiveEvent::CandleBatch {
+                start_sequence,
+                candles,
+            } = event
+            {
+                for (offset, candle) in candles.iter().enumerate() {
+                    assert_eq!(candle.close, (start_sequence + offset as u64) as f64);
+                    applied.push(start_sequence + offset as u64);
+                }
+            }
+        }
+        assert_eq!(applied, (5..=14).collect::<Vec<_>>());
+        assert_eq!(expected_next_sequence, 15);
+        assert!(buffered.is_empty());
+    }
+
+    #[test]
+    fn parse_interval_ms_rejects_non_positive_intervals() {
+        assert_eq!(parse_interval_ms("5m"), Some(300_000));
+        assert_eq!(parse_interval_ms("1d"), Some(86_400_000));
+        assert_eq!(parse_interval_ms("0s"), None);
+        assert_eq!(parse_interval_ms("-5s"), None);
+        assert_eq!(parse_interval_ms("s"), None);
+    }
+
+    #[test]
+    fn interval_check_warns_on_mismatched_spacing() {
+        let at = |secs: i64| second_candle(secs, secs as f64);
+        let batches = || BTreeMap::from([(1, vec![at(0), at(1)]), (3, vec![at(2), at(5)])]);
+        let drain = |mut interval_check: Option<IntervalCheck>| {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let mut expected_next_sequence = 1;
+            drain_buffered_batches(
+                &tx,
+                &mut interval_check,
+                &mut expected_next_sequence,
+                &mut batches(),
+            );
+            assert_eq!(expected_next_sequence, 5);
+            let mut errors = Vec::new();
+            let mut applied = 0;
+            while let Ok(event) = rx.try_recv() {
+                match event {
+                    LiveEvent::Error(msg) => errors.push(msg),
+                    LiveEvent::CandleBatch { candles, .. } => applied += candles.len(),
+                    _ => {}
+                }
+            }
+            assert_eq!(applied, 4, "mismatched candles are still applied");
+            errors
+        };
+
+        let errors = drain(Some(IntervalCheck::new(1_000)));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("seq 4"), "{}", errors[0]);
+        assert!(errors[0].contains("3000ms"), "{}", errors[0]);
+
+        assert!(
+            drain(None).is_empty(),
+            "disabled check allows irregular data"
+        );
+    }
 }
@@ -1 +1,83 @@
-﻿#![allow(dead_code)]
+//! In-memory ring of recent loading and live-stream messages, shown by the log panel.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use time::OffsetDateTime;
+
+/// Entries kept before the oldest are dropped.
+pub const MAX_LOG_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    Loading,
+    Live,
+}
+
+impl LogSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Loading => "loading",
+            Self::Live => "live",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Increases by one per entry for the life of the process; survives [`clear_logs`].
+    pub seq: u64,
+    pub at: OffsetDateTime,
+    pub source: LogSource,
+    pub message: String,
+}
+
+struct LogRing {
+    next_seq: u64,
+    entries: VecDeque<LogEntry>,
+}
+
+static LOG: Mutex<LogRing> = Mutex::new(LogRing {
+    next_seq: 0,
+    entries: VecDeque::new(),
+});
+
+fn push(source: LogSource, message: String) {
+    let Ok(mut ring) = LOG.lock() else {
+        return;
+    };
+    let seq = ring.next_seq;
+    ring.next_seq += 1;
+    if ring.entries.len() == MAX_LOG_ENTRIES {
+        ring.entries.pop_front();
+    }
+    ring.entries.push_back(LogEntry {
+        seq,
+        at: OffsetDateTime::now_utc(),
+        source,
+        message,
+    });
+}
+
+/// Records a symbol load step (start, finish, failure).
+pub fn log_loading(message: impl Into<String>) {
+    push(LogSource::Loading, message.into());
+}
+
+/// Records a live-stream status change or error.
+pub fn log_live(message: impl Into<String>) {
+    push(LogSource::Live, message.into());
+}
+
+/// Retained entries, oldest first.
+pub fn recent_logs() -> Vec<LogEntry> {
+    LOG.lock()
+        .map(|ring| ring.entries.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+pub fn clear_logs() {
+    if let Ok(mut ring) = LOG.lock() {
+        ring.entries.clear();
+    }
+}
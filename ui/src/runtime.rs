@@ -1,18 +1,30 @@
-use core::Candle;
+use core::{
+    Candle, CandleExt, LoadOptions, NullPolicy, SourceFormat, detect_format, load_csv_ext,
+    load_parquet_ext,
+};
 use gpui::{
-    App, Bounds, Context, Render, Window, WindowBounds, WindowOptions, div, prelude::*, px, rgb,
-    size,
+    App, Bounds, Context, MouseButton, MouseDownEvent, Render, SharedString, Window, WindowBounds,
+    WindowOptions, div, prelude::*, px, rgb, size,
 };
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use crate::perf::{PerfSpec, generate_perf_candles, perf_label, perf_source};
-use crate::store::default_store;
+use crate::store::open_default_store;
 use crate::{ChartMeta, ChartView, application_with_assets};
 
 #[derive(Clone, Default)]
 pub struct RuntimeOptions {
     pub initial_symbol: Option<String>,
+    /// File loaded on startup instead of restoring the cached session.
+    pub initial_source: Option<PathBuf>,
+    pub initial_visible: Option<usize>,
+    /// Resample interval shown first; `None` shows raw candles.
+    pub initial_interval: Option<core::Interval>,
     pub perf: Option<PerfOptions>,
+    /// Minimum time between chart repaints while streaming live candles, e.g. 100ms to cap
+    /// at 10fps on low-power machines; `None` repaints on every batch.
+    pub live_refresh_interval: Option<std::time::Duration>,
 }
 
 #[derive(Clone)]
@@ -52,33 +64,52 @@ struct RuntimeView {
     store: Option<Arc<Mutex<core::DuckDbStore>>>,
     restored: bool,
     options: RuntimeOptions,
+    /// Shown once, dismissible, when DuckDB couldn't be opened and everything is in-memory.
+    cache_notice: Option<SharedString>,
 }
 
 impl RuntimeView {
     fn new_with_options(options: RuntimeOptions, cx: &mut Context<Self>) -> Self {
-        let store = default_store();
-        let store_arc = store;
+        Self::with_store(options, open_default_store(), cx)
+    }
+
+    fn with_store(
+        options: RuntimeOptions,
+        store: Result<Arc<Mutex<core::DuckDbStore>>, String>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let (store_arc, cache_notice) = match store {
+            Ok(store) => (Some(store), None),
+            Err(reason) => (
+                None,
+                Some(SharedString::from(format!("Cache disabled: {reason}"))),
+            ),
+        };
 
         let default_source = options
             .initial_symbol
             .clone()
             .unwrap_or_else(|| "AAPL".to_string());
         let chart = cx.new(|cx| {
-            ChartView::new(
+            let mut chart = ChartView::new(
                 Vec::<Candle>::new(),
                 ChartMeta {
                     source: default_source,
-                    initial_interval: None,
+                    initial_interval: options.initial_interval,
+                    initial_visible: options.initial_visible,
                 },
                 store_arc.clone(),
                 cx,
-            )
+            );
+            chart.set_live_refresh_interval(options.live_refresh_interval);
+            chart
         });
         Self {
             chart,
             store: store_arc,
             restored: false,
             options,
+            cache_notice,
         }
     }
 
@@ -90,22 +121,27 @@ impl RuntimeView {
         cx: &mut Context<Self>,
         persist_session: bool,
     ) {
+        let cache_candles = self.chart.read(cx).cache_candles_enabled();
         if persist_session
             && let Some(store) = &self.store
             && let Ok(guard) = store.lock()
         {
-            let _ = guard.write_candles(&source, &candles);
+            if cache_candles {
+                let _ = guard.write_candles(&source, None, &candles);
+            }
             let _ = guard.set_session_value("active_source", &source);
             let interval = self.chart.update(cx, |chart, _| {
-                ChartView::interval_label(chart.current_interval())
+                ChartView::interval_key(chart.current_interval())
             });
-            let _ = guard.set_session_value("interval", interval.as_str());
+            let _ = guard.set_session_value("interval", &interval);
             let range = self
                 .chart
                 .update(cx, |chart, _| chart.current_range_index().to_string());
             let _ = guard.set_session_value("range_index", &range);
             let replay = self.chart.update(cx, |chart, _| chart.replay_enabled());
             let _ = guard.set_session_value("replay_mode", if replay { "true" } else { "false" });
+            // A large ingest should survive an abrupt exit without replaying the WAL.
+            let _ = guard.flush();
         }
         self.chart.update(cx, |chart, cx| {
             chart.replace_data(candles, source, persist_session, persist_session);
@@ -119,11 +155,18 @@ impl RuntimeView {
             return;
         }
         self.restored = true;
-        let Some(store) = &self.store else {
+        if self.options.perf.is_none()
+            && let Some(path) = self.options.initial_source.clone()
+        {
+            self.load_initial_source(path, window, cx);
             return;
-        };
-
-        let session = store.lock().ok().and_then(|s| s.load_user_session().ok());
+        }
+        // Without a store there is no session to restore, but a perf override still applies.
+        let session = self
+            .store
+            .as_ref()
+            .and_then(|store| store.lock().ok())
+            .and_then(|s| s.load_user_session().ok());
         let perf_override = self.options.perf.clone();
         let perf_from_session = session.as_ref().and_then(|s| {
             if s.perf_mode.unwrap_or(false) {
@@ -170,6 +213,9 @@ impl RuntimeView {
             return;
         }
 
+        let Some(store) = &self.store else {
+            return;
+        };
         let cached = session.as_ref().and_then(|session| {
             session
                 .active_source
@@ -177,7 +223,7 @@ impl RuntimeView {
                 .filter(|source| !source.starts_with("__PERF__"))
                 .and_then(|source| {
                     store.lock().ok().and_then(|s| {
-                        s.load_candles(source, None)
+                        s.load_candles(source, None, None)
                             .ok()
                             .filter(|c| !c.is_empty())
                             .map(|candles| (source.to_string(), candles))
@@ -191,6 +237,59 @@ impl RuntimeView {
             self.apply_loaded(source, candles, window, cx, false);
         }
     }
+
+    fn load_initial_source(&mut self, path: PathBuf, window: &mut Window, cx: &mut Context<Self>) {
+        let source = path.display().to_string();
+        let (load_id, null_policy) = self.chart.update(cx, |chart, _| {
+            (
+                chart.begin_external_loading(source.clone()),
+                chart.null_policy(),
+            )
+        });
+
+        let chart_entity = self.chart.clone();
+        window
+            .spawn(cx, async move |async_cx| {
+                let task = async_cx
+                    .background_executor()
+                    .spawn(async move { load_source_file(&path, null_policy) });
+                let result = task.await;
+                async_cx
+                    .update(|window, app| {
+                        chart_entity.update(app, |chart, cx| {
+                            match result {
+                                Ok(rows) => {
+                                    let candles =
+                                        rows.iter().map(|row| row.candle.clone()).collect();
+                                    if chart.apply_external_loaded(load_id, candles, source) {
+                                        chart.set_spread_quotes(&rows);
+                                    }
+                                }
+                                Err(err) => chart.fail_external_loading(load_id, err),
+                            }
+                            cx.notify();
+                        });
+                        window.refresh();
+                    })
+                    .ok();
+            })
+            .detach();
+    }
+}
+
+/// Candles plus any bid/ask columns, for the spread band. Bad prices fail the load unless
+/// the user opted into forward-filling them.
+fn load_source_file(path: &Path, null_policy: NullPolicy) -> Result<Vec<CandleExt>, String> {
+    let options = LoadOptions {
+        volume_optional: true,
+        null_policy,
+        ..Default::default()
+    };
+    let result = match detect_format(&path.to_string_lossy()) {
+        SourceFormat::Parquet => load_parquet_ext(path, options),
+        SourceFormat::Csv | SourceFormat::Url => load_csv_ext(path, options),
+    };
+    result.map_err(|e| format!("failed to load {}: {e}", path.display()))
 }
 
 impl Render for RuntimeView {
@@ -205,6 +304,29 @@ impl Render for RuntimeView {
             .bg(rgb(0x0b1220))
             .child(self.chart.clone());
 
+        let dismiss_notice = cx.listener(|this: &mut Self, _: &MouseDownEvent, _, cx| {
+            this.cache_notice = None;
+            cx.notify();
+        });
+        let cache_notice = self.cache_notice.clone().map(|notice| {
+            div()
+                .absolute()
+                .top(px(12.))
+                .right(px(12.))
+                .px_3()
+                .py_2()
+                .rounded_md()
+                .bg(rgb(0x422006))
+                .border_1()
+                .border_color(rgb(0xb45309))
+                .text_xs()
+                .text_color(rgb(0xfde68a))
+                .cursor_pointer()
+                .debug_selector(|| "cache-notice".to_string())
+                .on_mouse_down(MouseButton::Left, dismiss_notice)
+                .child(notice)
+        });
+
         div()
             .flex()
             .w_full()
@@ -212,5 +334,151 @@ impl Render for RuntimeView {
             .relative()
             .bg(rgb(0x0b1220))
             .child(chart_area)
+            .children(cache_notice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use core::StorageMode;
+    use gpui::TestAppContext;
+    use time::macros::datetime;
+
+    fn memory_store() -> Arc<Mutex<core::DuckDbStore>> {
+        let path = std::env::temp_dir().join("gpui-kbar-runtime-unused.duckdb");
+        let store = core::DuckDbStore::new(path, StorageMode::Memory).expect("memory store");
+        Arc::new(Mutex::new(store))
+    }
+
+    fn loaded_candles() -> Vec<Candle> {
+        vec![Candle {
+            timestamp: datetime!(2024-01-01 00:00:00 UTC),
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close: 1.5,
+            volume: 10.0,
+        }]
+    }
+
+    async fn cached_rows_after_load(cx: &TestAppContext, cache_candles: bool) -> usize {
+        let mut cx = cx.clone();
+        let store = memory_store();
+        let (runtime, cx) = cx.add_window_view(|_, cx| {
+            let chart = cx.new(|cx| {
+                let mut chart = ChartView::new(
+                    Vec::new(),
+                    ChartMeta {
+                        source: "CACHE".to_string(),
+                        initial_interval: None,
+                        initial_visible: None,
+                    },
+                    Some(store.clone()),
+                    cx,
+                );
+                chart.set_cache_candles(cache_candles);
+                chart
+            });
+            RuntimeView {
+                chart,
+                store: Some(store.clone()),
+                restored: true,
+                options: RuntimeOptions::default(),
+                cache_notice: None,
+            }
+        });
+
+        runtime.update_in(cx, |runtime, window, cx| {
+            runtime.apply_loaded("CACHE".to_string(), loaded_candles(), window, cx, true);
+        });
+        cx.run_until_parked();
+
+        let guard = store.lock().expect("store lock");
+        guard
+            .load_candles("CACHE", None, None)
+            .expect("load candles")
+            .len()
+    }
+
+    #[gpui::test]
+    async fn disabled_cache_skips_writing_loaded_candles(cx: &TestAppContext) {
+        assert_eq!(cached_rows_after_load(cx, true).await, 1);
+        assert_eq!(cached_rows_after_load(cx, false).await, 0);
+    }
+
+    #[gpui::test]
+    async fn live_refresh_pump_ticks_at_configured_cadence(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let (runtime, cx) = cx.add_window_view(|_, cx| {
+            RuntimeView::with_store(
+                RuntimeOptions {
+                    live_refresh_interval: Some(Duration::from_millis(100)),
+                    ..RuntimeOptions::default()
+                },
+                Err("no store in tests".to_string()),
+                cx,
+            )
+        });
+        let chart = runtime.read_with(cx, |runtime, _| runtime.chart.clone());
+        chart.update_in(cx, |chart, window, cx| {
+            let cadence = chart.live_refresh_interval().expect("configured cadence");
+            assert_eq!(cadence, Duration::from_millis(100));
+            chart.start_live_refresh_pump(cadence, window, cx);
+        });
+
+        cx.executor().advance_clock(Duration::from_millis(1_050));
+        cx.run_until_parked();
+        assert_eq!(
+            chart.read_with(cx, |chart, _| chart.live_refresh_ticks()),
+            10
+        );
+
+        cx.executor().advance_clock(Duration::from_millis(200));
+        cx.run_until_parked();
+        assert_eq!(
+            chart.read_with(cx, |chart, _| chart.live_refresh_ticks()),
+            12
+        );
+    }
+
+    #[gpui::test]
+    async fn missing_store_degrades_to_in_memory_with_notice(cx: &TestAppContext) {
+        let mut cx = cx.clone();
+        let (runtime, cx) = cx.add_window_view(|_, cx| {
+            RuntimeView::with_store(
+                RuntimeOptions::default(),
+                Err("database is locked".to_string()),
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        assert!(cx.debug_bounds("cache-notice").is_some());
+
+        runtime.update_in(cx, |runtime, window, cx| {
+            runtime.restored = false;
+            runtime.restore_session(window, cx);
+            runtime.apply_loaded("MEM".to_string(), loaded_candles(), window, cx, true);
+            runtime.chart.update(cx, |chart, _| {
+                chart.hydrate_from_store();
+                chart.add_to_watchlist("MSFT".to_string());
+                chart.remove_from_watchlist("MSFT");
+            });
+        });
+        cx.run_until_parked();
+
+        runtime.update(cx, |runtime, cx| {
+            assert!(runtime.store.is_none());
+            assert_eq!(
+                runtime.cache_notice.as_ref().map(|n| n.to_string()),
+                Some("Cache disabled: database is locked".to_string())
+            );
+            let chart = runtime.chart.read(cx);
+            assert_eq!(chart.visible_candles().len(), 1);
+            assert!(chart.watchlist_symbols().iter().any(|s| s == "MEM"));
+        });
     }
 }
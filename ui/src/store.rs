@@ -5,16 +5,23 @@ use core::{DuckDbStore, StorageMode};
 
 /// Helper for constructing a shared DuckDbStore in UI code.
 pub fn default_store() -> Option<Arc<Mutex<DuckDbStore>>> {
+    open_default_store().ok()
+}
+
+/// Like [`default_store`], but reports why DuckDB couldn't be opened so the UI can say
+/// that caching and session persistence are off.
+pub fn open_default_store() -> Result<Arc<Mutex<DuckDbStore>>, String> {
     let legacy = PathBuf::from("data/cache.duckdb");
     let config = PathBuf::from("data/config.duckdb");
     let data = PathBuf::from("data/data.duckdb");
 
     let _ = DuckDbStore::migrate_legacy_cache_to_split(&legacy, &config, &data);
 
-    let store = DuckDbStore::new_split(&config, &data, StorageMode::Both).ok()?;
+    let store =
+        DuckDbStore::new_split(&config, &data, StorageMode::Both).map_err(|e| e.to_string())?;
     let store = Arc::new(Mutex::new(store));
     if let Ok(guard) = store.lock() {
         let _ = guard.ensure_universe_loaded("data/universe.csv");
     }
-    Some(store)
+    Ok(store)
 }
@@ -0,0 +1,306 @@
+//! Loopback flux server for driving the live path in tests.
+
+use core::Candle;
+use flux_schema::{WIRE_SCHEMA_VERSION, fb};
+use time::macros::datetime;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use zeromq::{Socket, SocketRecv, SocketSend};
+
+use crate::live::{ChunkTransport, LiveConfig, build_stream_key, topic_for};
+use crate::wire::{CandleWire, encode_candles};
+
+/// Flat bar `i` minutes after 2024-01-01 00:00 UTC: open 1.0, high 2.0, low 0.5, close 1.5,
+/// volume 10.0.
+pub(crate) fn minute_candle(i: i64) -> Candle {
+    Candle::new(
+        datetime!(2024-01-01 00:00:00 UTC) + time::Duration::minutes(i),
+        1.0,
+        2.0,
+        0.5,
+        1.5,
+        10.0,
+    )
+}
+
+/// `n` consecutive [`minute_candle`]s.
+pub(crate) fn minute_candles(n: i64) -> Vec<Candle> {
+    (0..n).map(minute_candle).collect()
+}
+
+/// Bar `secs` seconds after 2026-01-01 00:00 UTC opening and closing at `close`, half a
+/// point either side, volume 1.0.
+pub(crate) fn second_candle(secs: i64, close: f64) -> Candle {
+    Candle::new(
+        datetime!(2026-01-01 00:00:00 UTC) + time::Duration::seconds(secs),
+        close,
+        close + 0.5,
+        close - 0.5,
+        close,
+        1.0,
+    )
+}
+
+/// `n` consecutive [`second_candle`]s closing at 1.0, 2.0, …, so each is distinguishable.
+pub(crate) fn second_candles(n: i64) -> Vec<Candle> {
+    (0..n).map(|i| second_candle(i, 1.0 + i as f64)).collect()
+}
+
+/// PUB + REP pair on ephemeral loopback ports. Backfill and cursor requests are served from
+/// the candles it was started with (sequence `n` is `candles[n - 1]`); live batches go out
+/// only when [`publish`](Self::publish) is called.
+pub(crate) struct MockFluxServer {
+    cfg: LiveConfig,
+    symbol: String,
+    publish_tx: mpsc::UnboundedSender<Vec<u8>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockFluxServer {
+    pub(crate) async fn start(symbol: &str, candles: Vec<Candle>) -> Self {
+        Self::bind(symbol, candles, "tcp://127.0.0.1:0", "tcp://127.0.0.1:0").await
+    }
+
+    /// Like [`start`](Self::start) but on fixed endpoints, for clients that connect first.
+    pub(crate) async fn bind(
+        symbol: &str,
+        candles: Vec<Candle>,
+        live_pub: &str,
+        chunk_rep: &str,
+    ) -> Self {
+        let mut pub_socket = zeromq::PubSocket::new();
+        let live_pub = pub_socket.bind(live_pub).await.expect("mock pub bind");
+        let mut rep_socket = zeromq::RepSocket::new();
+        let chunk_rep = rep_socket.bind(chunk_rep).await.expect("mock rep bind");
+
+        let cfg = LiveConfig {
+            live_pub: live_pub.to_string(),
+            chunk_rep: chunk_rep.to_string(),
+            chunk_transport: ChunkTransport::Req,
+            bind_local: None,
+            source_id: "SIM".to_string(),
+            interval: "1s".to_string(),
+            validate_interval: false,
+            max_initial_backfill: None,
+        };
+
+        let rep_cfg = cfg.clone();
+        let rep_symbol = symbol.to_string();
+        let rep_task = tokio::spawn(async move {
+            while let Ok(req) = rep_socket.recv().await {
+                let Ok(bytes) = Vec::<u8>::try_from(req) else {
+                    break;
+                };
+                let resp = respond(&rep_cfg, &rep_symbol, &candles, &bytes);
+                if rep_socket.send(resp.into()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (publish_tx, mut publish_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+        let topic = topic_for(&cfg, symbol);
+        let pub_task = tokio::spawn(async move {
+            while let Some(payload) = publish_rx.recv().await {
+                let mut msg = zeromq::ZmqMessage::from(topic.as_str());
+                msg.push_back(payload.into());
+                if pub_socket.send(msg).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            cfg,
+            symbol: symbol.to_string(),
+            publish_tx,
+            tasks: vec![rep_task, pub_task],
+        }
+    }
+
+    /// Client config pointing at this server.
+    pub(crate) fn config(&self) -> LiveConfig {
+        self.cfg.clone()
+    }
+
+    /// Publishes one live batch. PUB drops messages sent before a subscriber has joined, so
+    /// tests should republish until the batch shows up.
+    pub(crate) fn publish(&self, start_sequence: u64, candles: &[Candle]) {
+        let payload = encode_candle_batch(&self.cfg, &self.symbol, start_sequence, candles);
+        let _ = self.publish_tx.send(payload);
+    }
+
+    /// Stops both sockets and waits for their tasks to finish.
+    pub(crate) async fn shutdown(mut self) {
+        for task in std::mem::take(&mut self.tasks) {
+            task.abort();
+            let _ = task.await;
+        }
+    }
+}
+
+impl Drop for MockFluxServer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+fn respond(cfg: &LiveConfig, symbol: &str, candles: &[Candle], bytes: &[u8]) -> Vec<u8> {
+    let Ok(env) = fb::root_as_envelope(bytes) else {
+        return encode_error("invalid envelope");
+    };
+    if let Some(req) = env.message_as_backfill_candles_request() {
+        let from_exclusive = if req.has_from_sequence() {
+            req.from_sequence_exclusive()
+        } else {
+            0
+        };
+        let start = (from_exclusive as usize).min(candles.len());
+        let mut end = candles
+            .len()
+            .min(start.saturating_add(req.limit().max(1) as usize));
+        if req.has_end_ts_ms() {
+            let end_ts_ms = req.end_ts_ms();
+            end = start + candles[start..end].partition_point(|c| c.ts_ms() <= end_ts_ms);
+        }
+        let correlation_id = env.correlation_id();
+        return encode_backfill_response(
+            cfg,
+            symbol,
+            from_exclusive.saturating_add(1),
+            &candles[start..end],
+            correlation_id,
+        );
+    }
+    if env.message_as_get_cursor_request().is_some() {
+        return encode_get_cursor_response(cfg, symbol, candles);
+    }
+    encode_error("unsupported request")
+}
+
+pub(crate) fn encode_candle_batch(
+    cfg: &LiveConfig,
+    symbol: &str,
+    start_sequence: u64,
+    candles: &[Candle],
+) -> Vec<u8> {
+    let mut fbb = flatbuffers::FlatBufferBuilder::new();
+    let key = build_stream_key(&mut fbb, cfg, symbol);
+
+    let candle_vec = encode_candles(&mut fbb, candles.iter().map(CandleWire::from));
+    let batch = fb::CandleBatch::create(
+        &mut fbb,
+        &fb::CandleBatchArgs {
+            key: Some(key),
+            start_sequence,
+            candles: Some(candle_vec),
+        },
+    );
+
+    let env = fb::Envelope::create(
+        &mut fbb,
+        &fb::EnvelopeArgs {
+            schema_version: WIRE_SCHEMA_VERSION,
+            type_hint: fb::MessageType::CANDLE_BATCH,
+            correlation_id: None,
+            message_type: fb::Message::CandleBatch,
+            message: Some(batch.as_union_value()),
+        },
+    );
+    fb::finish_envelope_buffer(&mut fbb, env);
+    fbb.finished_data().to_vec()
+}
+
+pub(crate) fn encode_backfill_response(
+    cfg: &LiveConfig,
+    symbol: &str,
+    start_sequence: u64,
+    candles: &[Candle],
+    correlation_id: Option<&str>,
+) -> Vec<u8> {
+    let mut fbb = flatbuffers::FlatBufferBuilder::new();
+    let key = build_stream_key(&mut fbb, cfg, symbol);
+    let correlation_id = correlation_id.map(|id| fbb.create_string(id));
+
+    let candle_vec = encode_candles(&mut fbb, candles.iter().map(CandleWire::from));
+    let resp = fb::BackfillCandlesResponse::create(
+        &mut fbb,
+        &fb::BackfillCandlesResponseArgs {
+            key: Some(key),
+            start_sequence,
+            candles: Some(candle_vec),
+            has_more: false,
+            next_sequence: 0,
+        },
+    );
+
+    let env = fb::Envelope::create(
+        &mut fbb,
+        &fb::EnvelopeArgs {
+            schema_version: WIRE_SCHEMA_VERSION,
+            type_hint: fb::MessageType::BACKFILL_CANDLES_RESPONSE,
+            correlation_id,
+            message_type: fb::Message::BackfillCandlesResponse,
+            message: Some(resp.as_union_value()),
+        },
+    );
+    fb::finish_envelope_buffer(&mut fbb, env);
+    fbb.finished_data().to_vec()
+}
+
+fn encode_get_cursor_response(cfg: &LiveConfig, symbol: &str, candles: &[Candle]) -> Vec<u8> {
+    let mut fbb = flatbuffers::FlatBufferBuilder::new();
+    let key = build_stream_key(&mut fbb, cfg, symbol);
+    let cursor = fb::Cursor::create(
+        &mut fbb,
+        &fb::CursorArgs {
+            latest_sequence: candles.len() as u64,
+            latest_ts_ms: candles.last().map(Candle::ts_ms).unwrap_or(0),
+        },
+    );
+    let resp = fb::GetCursorResponse::create(
+        &mut fbb,
+        &fb::GetCursorResponseArgs {
+            key: Some(key),
+            cursor: Some(cursor),
+        },
+    );
+    let env = fb::Envelope::create(
+        &mut fbb,
+        &fb::EnvelopeArgs {
+            schema_version: WIRE_SCHEMA_VERSION,
+            type_hint: fb::MessageType::GET_CURSOR_RESPONSE,
+            correlation_id: None,
+            message_type: fb::Message::GetCursorResponse,
+            message: Some(resp.as_union_value()),
+        },
+    );
+    fb::finish_envelope_buffer(&mut fbb, env);
+    fbb.finished_data().to_vec()
+}
+
+fn encode_error(message: &str) -> Vec<u8> {
+    let mut fbb = flatbuffers::FlatBufferBuilder::new();
+    let message = fbb.create_string(message);
+    let err = fb::ErrorResponse::create(
+        &mut fbb,
+        &fb::ErrorResponseArgs {
+            code: 1,
+            message: Some(message),
+        },
+    );
+    let env = fb::Envelope::create(
+        &mut fbb,
+        &fb::EnvelopeArgs {
+            schema_version: WIRE_SCHEMA_VERSION,
+            type_hint: fb::MessageType::ERROR_RESPONSE,
+            correlation_id: None,
+            message_type: fb::Message::ErrorResponse,
+            message: Some(err.as_union_value()),
+        },
+    );
+    fb::finish_envelope_buffer(&mut fbb, env);
+    fbb.finished_data().to_vec()
+}
@@ -0,0 +1,135 @@
+//! Conversions between [`Candle`] and the FlatBuffers candle on the live wire.
+
+use core::{Candle, InvalidTimestamp};
+use flatbuffers::{FlatBufferBuilder, ForwardsUOffset, Vector, WIPOffset};
+use flux_schema::fb;
+
+/// A candle as carried on the wire: a unix-millisecond timestamp plus OHLCV.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandleWire {
+    pub ts_ms: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl CandleWire {
+    pub fn args(&self) -> fb::CandleArgs {
+        fb::CandleArgs {
+            ts_ms: self.ts_ms,
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+        }
+    }
+}
+
+impl From<&fb::Candle<'_>> for CandleWire {
+    fn from(c: &fb::Candle<'_>) -> Self {
+        Self {
+            ts_ms: c.ts_ms(),
+            open: c.open(),
+            high: c.high(),
+            low: c.low(),
+            close: c.close(),
+            volume: c.volume(),
+        }
+    }
+}
+
+impl From<&Candle> for CandleWire {
+    fn from(c: &Candle) -> Self {
+        Self {
+            ts_ms: c.ts_ms(),
+            open: c.open,
+            high: c.high,
+            low: c.low,
+            close: c.close,
+            volume: c.volume,
+        }
+    }
+}
+
+impl TryFrom<CandleWire> for Candle {
+    type Error = InvalidTimestamp;
+
+    fn try_from(w: CandleWire) -> Result<Self, Self::Error> {
+        Candle::from_ts_ms(w.ts_ms, w.open, w.high, w.low, w.close, w.volume)
+    }
+}
+
+/// Writes `candles` as a FlatBuffers vector ready for a batch or backfill response.
+pub fn encode_candles<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    candles: impl IntoIterator<Item = CandleWire>,
+) -> WIPOffset<Vector<'a, ForwardsUOffset<fb::Candle<'a>>>> {
+    let offsets: Vec<_> = candles
+        .into_iter()
+        .map(|candle| fb::Candle::create(fbb, &candle.args()))
+        .collect();
+    fbb.create_vector(&offsets)
+}
+
+/// Reads a FlatBuffers candle vector; a missing vector is an empty batch.
+pub fn decode_candles(
+    candles: Option<Vector<'_, ForwardsUOffset<fb::Candle<'_>>>>,
+) -> Result<Vec<Candle>, InvalidTimestamp> {
+    let Some(candles) = candles else {
+        return Ok(Vec::new());
+    };
+    candles
+        .iter()
+        .map(|c| Candle::try_from(CandleWire::from(&c)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use time::macros::datetime;
+
+    #[test]
+    fn candle_round_trips_through_wire_and_flatbuffers() {
+        let candle = Candle::new(
+            datetime!(2026-01-01 00:00:01.250 UTC),
+            1.0,
+            2.0,
+            0.5,
+            1.5,
+            10.0,
+        );
+        let wire = CandleWire::from(&candle);
+        assert_eq!(wire.ts_ms, 1_767_225_601_250);
+        assert_eq!(Candle::try_from(wire), Ok(candle.clone()));
+
+        let mut fbb = FlatBufferBuilder::new();
+        let vector = encode_candles(&mut fbb, [wire]);
+        fbb.finish_minimal(vector);
+        let decoded =
+            flatbuffers::root::<Vector<'_, ForwardsUOffset<fb::Candle<'_>>>>(fbb.finished_data())
+                .ok();
+        assert_eq!(decode_candles(decoded), Ok(vec![candle]));
+    }
+
+    #[test]
+    fn out_of_range_ts_ms_is_rejected() {
+        let wire = CandleWire {
+            ts_ms: i64::MAX,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 0.0,
+        };
+        assert_eq!(Candle::try_from(wire), Err(InvalidTimestamp(i64::MAX)));
+        assert_eq!(
+            InvalidTimestamp(i64::MAX).to_string(),
+            format!("invalid ts_ms={}", i64::MAX)
+        );
+    }
+}